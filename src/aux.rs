@@ -1,2 +1,8 @@
 pub mod bethe_bloch;
-pub mod plotting;
\ No newline at end of file
+pub mod config;
+pub mod error;
+pub mod export;
+pub mod i18n;
+pub mod output;
+pub mod plotting;
+pub mod store;
\ No newline at end of file
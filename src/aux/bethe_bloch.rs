@@ -1 +1,74 @@
-pub mod bb;
\ No newline at end of file
+pub mod axis;
+pub mod bb;
+pub mod bragg;
+pub mod cherenkov;
+pub mod comparison;
+pub mod compound;
+pub mod delta_rays;
+pub mod density;
+pub mod density_fit;
+pub mod dosimetry;
+pub mod electron;
+pub mod elements;
+pub mod gas;
+pub mod grid;
+pub mod ionization;
+pub mod kinematics;
+pub mod landau;
+pub mod lindhard_sorensen;
+pub mod material;
+pub mod materials;
+pub mod monte_carlo;
+pub mod muon;
+pub mod pid;
+pub mod projectile;
+pub mod radiative;
+pub mod ratio;
+pub mod range;
+pub mod reference;
+pub mod run;
+pub mod rbe;
+pub mod scan;
+pub mod scintillation;
+pub mod scattering;
+pub mod sensitivity;
+pub mod shell;
+pub mod slab;
+pub mod sobp;
+pub mod spline;
+pub mod stack;
+pub mod straggling;
+pub mod table;
+pub mod telescope;
+pub mod uncertainty;
+pub mod units;
+pub mod validation;
+pub mod wet;
+
+pub use axis::Axis;
+pub use bb::Corrections;
+pub use comparison::Comparison;
+pub use bragg::{BraggCurve, LetProfile};
+pub use compound::Compound;
+pub use density::DensityCorrectionParams;
+pub use gas::Gas;
+pub use grid::{EnergyGrid, Spacing};
+pub use landau::{EnergyLossDistribution, LossModel};
+pub use material::Material;
+pub use monte_carlo::MonteCarlo;
+pub use projectile::Projectile;
+pub use range::RangeTable;
+pub use reference::ReferenceTable;
+pub use ratio::StoppingPowerRatio;
+pub use rbe::RbeModel;
+pub use scan::{Scan, StoppingPowerMap};
+pub use scattering::LateralSpread;
+pub use scintillation::Scintillator;
+pub use shell::ShellCorrectionModel;
+pub use slab::SlabOutcome;
+pub use sobp::Sobp;
+pub use stack::Stack;
+pub use table::StoppingPowerTable;
+pub use telescope::Telescope;
+pub use uncertainty::StoppingPowerBand;
+pub use units::Unit;
\ No newline at end of file
@@ -0,0 +1,86 @@
+// This module provides the kinematic variables a stopping power table can be tabulated
+// against: kinetic energy, momentum or βγ of the projectile
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::kinematics;
+use crate::i18n::Lang;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    // Kinetic energy T (MeV)
+    #[default]
+    KineticEnergy,
+    // Momentum p (MeV/c)
+    Momentum,
+    // βγ = p/Mc, the variable of the universal dE/dx curve used for particle identification
+    BetaGamma,
+}
+
+impl Axis {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Axis::KineticEnergy => "MeV",
+            Axis::Momentum => "MeV/c",
+            Axis::BetaGamma => "",
+        }
+    }
+
+    // Column header used when writing tables
+    pub fn header(&self) -> &'static str {
+        match self {
+            Axis::KineticEnergy => "E (MeV)",
+            Axis::Momentum => "p (MeV/c)",
+            Axis::BetaGamma => "betagamma",
+        }
+    }
+
+    // Axis label used in the plots, in the language given
+    pub fn label(&self, lang: Lang) -> &'static str {
+        match self {
+            Axis::KineticEnergy => lang.labels().energy,
+            Axis::Momentum => lang.labels().momentum,
+            Axis::BetaGamma => "βγ",
+        }
+    }
+
+    // Value on this axis of a projectile of rest mass `mass` (MeV/c²) and kinetic energy `kinetic` (MeV)
+    pub fn from_kinetic(&self, kinetic: f64, mass: f64) -> f64 {
+        match self {
+            Axis::KineticEnergy => kinetic,
+            Axis::Momentum => kinematics::momentum(kinetic, mass),
+            Axis::BetaGamma => kinematics::beta_gamma(kinetic, mass),
+        }
+    }
+
+    // Kinetic energy (MeV) of a value on this axis
+    pub fn to_kinetic(&self, value: f64, mass: f64) -> f64 {
+        match self {
+            Axis::KineticEnergy => value,
+            Axis::Momentum => kinematics::kinetic_from_momentum(value, mass),
+            Axis::BetaGamma => kinematics::kinetic_from_beta_gamma(value, mass),
+        }
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.header())
+    }
+}
+
+impl FromStr for Axis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "energy" | "kinetic" | "kineticenergy" | "t" => Ok(Axis::KineticEnergy),
+            "momentum" | "p" => Ok(Axis::Momentum),
+            "betagamma" | "bg" | "βγ" => Ok(Axis::BetaGamma),
+            _ => Err(format!("Unknown axis '{}' (expected energy, momentum or betagamma)", s)),
+        }
+    }
+}
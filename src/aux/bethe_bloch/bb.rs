@@ -1,234 +1,289 @@
 // This module provide the function to calculate the stopping power using the Bethe-Bloch formula
-use std::collections::HashMap;
-use std::fs::File;
 use std::f64::consts::PI;
-use std::io::Write;
+use std::str::FromStr;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::electron::{collision_stopping_power, is_lepton};
+use super::kinematics;
+use super::lindhard_sorensen::lindhard_sorensen_correction;
+use super::shell::ShellCorrectionModel;
+use super::straggling::bohr_variance;
+use super::{muon, radiative, DensityCorrectionParams, EnergyGrid, Material, Projectile, StoppingPowerTable};
 
 // Physical constants (SI units and energy in eV unless noted)
 const ELECTRON_CHARGE: f64= 1.602176634e-19;
 const ELECTRON_MASS_0: f64 = 9.10938356e-31;
 const SPEED_OF_LIGHT: f64 = 299792458.0;
-const WATER_ATOMIC_NUMBER: f64 = 9.0;
-const PROTON_ENERGY_MeV_I: f64 = 1.0;
-const PROTON_MASS_0: f64 = 1.6726219e-27;
-const WATER_EXCITATION_ENERGY: f64 = 74.6;
-const ELECTRON_PER_VOLUME_H20: f64 = 3.3429;
 const COULOMB_CONST: f64 = 8.99e9;
-const Z_PROTON: f64 = 1.0;
+const FINE_STRUCTURE: f64 = 7.2973525693e-3;
+
+// Corrections that can be selected by name, see `Corrections::from_str`
+pub const CORRECTION_NAMES: [&str; 5] = ["density", "shell", "shell-table", "barkas", "bloch"];
+
+// Corrections applied on top of the bare Bethe-Bloch formula
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Corrections {
+    pub density: bool,
+    pub shell: bool,
+    // Backend used for the shell correction when it is enabled
+    pub shell_model: ShellCorrectionModel,
+    // Use the exact maximum energy transfer Tmax instead of the low mass-ratio approximation
+    pub exact_tmax: bool,
+    // Barkas (z³) term
+    pub barkas: bool,
+    // Bloch (z⁴) term
+    pub bloch: bool,
+    // Spin-1/2 term (Tmax/E)²/8 of the PDG form, applied to spin-1/2 projectiles only
+    pub spin: bool,
+    // Lindhard-Sørensen term for relativistic heavy ions (supersedes the Bloch term)
+    pub lindhard_sorensen: bool,
+    // Add the radiative losses (bremsstrahlung, and pair production and photonuclear for muons)
+    pub radiative: bool,
+    // Restricted energy loss: only energy transfers below this kinetic energy (MeV)
+    pub delta_ray_cutoff: Option<f64>,
+    // Sternheimer coefficients used when the density correction is enabled
+    pub density_params: DensityCorrectionParams,
+}
 
-pub fn bethe_bloch_no_corrections(n_points: &u32, energies: &mut Vec<f64>, stopping_powers: &mut Vec<f64>){
-    
-    // Derived constants
-    let proton_mass = (PROTON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
-    let electron_mass = ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT;
-    let electron_mass_eV = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
+impl Corrections {
+    pub fn none() -> Self {
+        Corrections::default()
+    }
 
-    // General constant for the Bethe–Bloch calculation
-    let const_general = (4.0 * PI * ELECTRON_CHARGE.powi(4) * COULOMB_CONST.powi(2)) / (electron_mass * ELECTRON_CHARGE * 1.0e8);
+    pub fn density(params: &DensityCorrectionParams) -> Self {
+        Corrections { density: true, density_params: *params, ..Corrections::default() }
+    }
 
-    // Ionization constant I (not used further in the calculation)
-    let I = if WATER_ATOMIC_NUMBER < 13.0 {
-        (12.0 * WATER_ATOMIC_NUMBER + 7.0) / 1e6
-    } else {
-        (9.76 * Z_PROTON + 58.8 * WATER_ATOMIC_NUMBER.powf(-0.19)) / 1e6
-    };
-    
-    // Open file for writing results
-    let mut file = File::create("output/fstopping_no_corrections.txt").expect("Unable to create file");
+    pub fn shell() -> Self {
+        Corrections { shell: true, ..Corrections::default() }
+    }
 
-    // BETHE-BLOCH WITHOUT CORRECTIONS
-    println!("Bethe-Bloch without corrections");
+    pub fn all(params: &DensityCorrectionParams) -> Self {
+        Corrections { density: true, shell: true, density_params: *params, ..Corrections::default() }
+    }
 
-    for i in 0..*n_points {
-        // Calculate proton energy in eV
-        let energy_eV = PROTON_ENERGY_MeV_I * ((i as f64 + 1.0) * 10.0) * 1e6;
+    // All corrections together with the exact Tmax
+    pub fn full(params: &DensityCorrectionParams) -> Self {
+        Corrections { exact_tmax: true, ..Corrections::all(params) }
+    }
 
-        // Calculate beta (v/c)
-        // beta = sqrt(E*(E + 2*m)) / (E + m)
-        let beta = ((energy_eV * (energy_eV + 2.0 * proton_mass)).sqrt())
-                   / (energy_eV + proton_mass);
+    // Names of the selectable corrections that are enabled, in the order of `CORRECTION_NAMES`
+    pub fn names(&self) -> Vec<&'static str> {
+        let tabulated = self.shell_model == ShellCorrectionModel::Tabulated;
+        let enabled = [self.density, self.shell && !tabulated, self.shell && tabulated, self.barkas, self.bloch];
+        CORRECTION_NAMES.iter().zip(enabled).filter(|(_, on)| *on).map(|(name, _)| *name).collect()
+    }
+}
 
-        // Bethe-Bloch formula for the stopping power (dE/dx)
-        let de_dx = (const_general * Z_PROTON.powi(2) * ELECTRON_PER_VOLUME_H20 / (beta * beta))
-                    * ((2.0 * electron_mass_eV * beta * beta / WATER_EXCITATION_ENERGY).ln()
-                       - (1.0 - beta * beta).ln()
-                       - beta * beta);
+// Parse a comma separated selection such as "density,shell,barkas"; "none" is the bare formula.
+// The density correction uses the default coefficients until `density_params` is set, and
+// "shell-table" is the shell correction interpolated in the table of the material.
+impl FromStr for Corrections {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut corrections = Corrections::none();
+        for name in s.split(',').map(|name| name.trim().to_ascii_lowercase()) {
+            match name.as_str() {
+                "none" => {}
+                "density" => corrections.density = true,
+                "shell" | "layer" => corrections.shell = true,
+                "shell-table" => {
+                    corrections.shell = true;
+                    corrections.shell_model = ShellCorrectionModel::Tabulated;
+                }
+                "barkas" => corrections.barkas = true,
+                "bloch" => corrections.bloch = true,
+                _ => return Err(format!("Unknown correction '{}' (expected none or any of {})", name, CORRECTION_NAMES.join(", "))),
+            }
+        }
+        Ok(corrections)
+    }
+}
 
-        let energy_MeV = energy_eV / 1e6;
+// Stopping power dE/dx (MeV/cm) of a projectile with kinetic energy `energy_mev` (MeV)
+pub fn stopping_power(energy_mev: f64, projectile: &Projectile, material: &Material, corrections: &Corrections) -> f64 {
 
-        energies.push(energy_MeV);
-        stopping_powers.push(de_dx);
+    let radiative = if corrections.radiative {
+        radiative_stopping_power(energy_mev, projectile, material)
+    } else {
+        0.0
+    };
 
-        // Write to file
-        writeln!(file, "{:.1}\t{:e}", energy_MeV, de_dx).expect("Unable to write data");
-        
-        println!("{:.1} MeV (dE/dx): {} MeV/cm", energy_MeV, de_dx);
+    // Electrons and positrons use the Møller/Bhabha collision stopping power
+    if is_lepton(projectile) {
+        return collision_stopping_power(energy_mev, projectile.charge > 0.0, material, corrections) + radiative;
     }
-}
-
-pub fn bethe_bloch_density_corrections(n_points: &u32, energies: &mut Vec<f64>, stopping_powers: &mut Vec<f64>, variables: &HashMap<String, f64>){
-    // Retrieve variables from the HashMap
-    let a: f64 = variables.get(&String::from("a")).copied().unwrap();
-    let x0: f64 = variables.get(&String::from("x0")).copied().unwrap();
-    let x1: f64 = variables.get(&String::from("x1")).copied().unwrap();
-    let m_param: f64 = variables.get(&String::from("m_param")).copied().unwrap();
-    let c_param: f64 = variables.get(&String::from("c_param")).copied().unwrap();
 
     // Derived constants
-    let proton_mass = (PROTON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
     let electron_mass = ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT;
-    let electron_mass_eV = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
+    let electron_mass_ev = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
 
     // General constant for the Bethe–Bloch calculation
     let const_general = (4.0 * PI * ELECTRON_CHARGE.powi(4) * COULOMB_CONST.powi(2)) / (electron_mass * ELECTRON_CHARGE * 1.0e8);
 
-    let mut file = File::create("output/fstopping_density_corrections.txt").expect("Unable to create file");
-    
-    println!("Bethe-Bloch with Density Corrections");
-
-    for i in 0..*n_points{
-        let energy_eV = PROTON_ENERGY_MeV_I * ((i as f64 + 1.0) * 10.0) * 1e6;
-
-        let beta = ((energy_eV * (energy_eV + 2.0 * proton_mass)).sqrt())
-                   / (energy_eV + proton_mass);
+    // Electron density of the target (electrons/m³)
+    let electron_density = material.electron_density * 1e6;
 
-        let bg = beta * (1.0 / (1.0 - beta * beta).sqrt());
+    // Velocity β = v/c and βγ of the projectile
+    let beta = kinematics::beta(energy_mev, projectile.mass);
+    let bg = kinematics::beta_gamma(energy_mev, projectile.mass);
 
-        let x = bg.log10();
-        let delta = if x >= x1{
-            2.0 * 10.0_f64.log10() * x + c_param   
-        } else if x0 <= x {
-            2.0 * 10.0_f64.log10() * x + c_param + a * f64::powf(x1 - x,m_param) 
-        } else{
-            0.0_f64
-        };
+    // Heavy ions pick up electrons at low velocity, so their charge is screened
+    let charge = effective_charge(projectile.charge, beta);
 
-        let de_dx = ((const_general * Z_PROTON.powi(2) * ELECTRON_PER_VOLUME_H20) / (beta.powi(2)))
-                * ((2.0 * electron_mass_eV * beta.powi(2) / WATER_EXCITATION_ENERGY).ln()
-                - (1.0 - beta.powi(2)).ln() - beta.powi(2) - delta);
-
-        let energy_MeV = energy_eV / 1e6;
-
-        energies.push(energy_MeV);
-        stopping_powers.push(de_dx);
-
-        // Write to file
-        writeln!(file, "{:.1}\t{:e}", energy_MeV, de_dx).expect("Unable to write data");
-        println!("{:.1} MeV (dE/dx): {} MeV/cm", energy_MeV, de_dx);
-    }
-}
+    // delta density correction (enters the stopping number as δ/2)
+    let delta = if corrections.density {
+        corrections.density_params.delta(bg) / 2.0
+    } else {
+        0.0
+    };
 
-pub fn bethe_bloch_layer_corrections(n_points: &u32, energies: &mut Vec<f64>, stopping_powers: &mut Vec<f64>){
+    // shell correction C/Z
+    let shell = if corrections.shell {
+        corrections.shell_model.correction(bg, material)
+    } else {
+        0.0
+    };
 
-    // Derived constants
-    let proton_mass = (PROTON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
-    let electron_mass = ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT;
-    let electron_mass_eV = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
-    
-    // General constant for the Bethe–Bloch calculation
-    let const_general = (4.0 * PI * ELECTRON_CHARGE.powi(4) * COULOMB_CONST.powi(2)) / (electron_mass * ELECTRON_CHARGE * 1.0e8);
+    // Barkas correction (sign follows the projectile charge)
+    let barkas = if corrections.barkas {
+        charge * barkas_correction(beta, bg, material.mean_excitation_energy)
+    } else {
+        0.0
+    };
 
-    // Ionization constant I (not used further in the calculation)
-    let I = if WATER_ATOMIC_NUMBER < 13.0 {
-        (12.0 * WATER_ATOMIC_NUMBER + 7.0) / 1e6
+    // Bloch correction, or the Lindhard-Sørensen correction that contains it
+    let bloch = if corrections.lindhard_sorensen {
+        lindhard_sorensen_correction(charge, beta)
+    } else if corrections.bloch {
+        bloch_correction(charge * FINE_STRUCTURE / beta)
     } else {
-        (9.76 * Z_PROTON + 58.8 * WATER_ATOMIC_NUMBER.powf(-0.19)) / 1e6
+        0.0
     };
 
+    // Spin-1/2 term with the total energy E = γMc²
+    let spin = if corrections.spin && (projectile.spin - 0.5).abs() < 1e-9 {
+        let tmax = max_energy_transfer(energy_mev, projectile);
+        (tmax / (energy_mev + projectile.mass)).powi(2) / 8.0
+    } else {
+        0.0
+    };
 
-    let mut file = File::create("output/fstopping_layer_corrections.txt").expect("Unable to create file");
+    // Bethe term: restricted to energy transfers below the delta-ray cutoff, with the
+    // exact Tmax, or with Tmax ≈ 2mc²β²γ²
+    let bethe_term = if let Some(cutoff) = corrections.delta_ray_cutoff {
+        let tmax = max_energy_transfer(energy_mev, projectile) * 1e6;
+        let t_upper = (cutoff * 1e6).min(tmax);
+        0.5 * (2.0 * electron_mass_ev * bg * bg * t_upper / material.mean_excitation_energy.powi(2)).ln()
+            - beta * beta / 2.0 * (1.0 + t_upper / tmax)
+    } else if corrections.exact_tmax {
+        let tmax = max_energy_transfer(energy_mev, projectile) * 1e6;
+        0.5 * (2.0 * electron_mass_ev * bg * bg * tmax / material.mean_excitation_energy.powi(2)).ln()
+            - beta * beta
+    } else {
+        (2.0 * electron_mass_ev * beta * beta / material.mean_excitation_energy).ln()
+            - (1.0 - beta * beta).ln()
+            - beta * beta
+    };
 
-    println!("Bethe-Bloch with Layer Correction");
+    // Bethe-Bloch formula for the stopping power (dE/dx)
+    (const_general * charge.powi(2) * electron_density / (beta * beta))
+        * (bethe_term
+           - delta
+           - shell
+           + barkas
+           + bloch
+           + spin)
+        + radiative
+}
 
-    for i in 0..*n_points{
-        let energy_eV = PROTON_ENERGY_MeV_I * ((i as f64 + 1.0) * 10.0) * 1e6;
+// Radiative stopping power (MeV/cm): bremsstrahlung for electrons and positrons, and
+// bremsstrahlung, pair production and photonuclear losses for heavier projectiles
+pub fn radiative_stopping_power(energy_mev: f64, projectile: &Projectile, material: &Material) -> f64 {
+    if is_lepton(projectile) {
+        radiative::radiative_stopping_power(energy_mev, material)
+    } else {
+        muon::radiative_stopping_power(energy_mev, projectile, material)
+    }
+}
 
-        let beta = ((energy_eV * (energy_eV + 2.0 * proton_mass)).sqrt())
-                    / (energy_eV + proton_mass);
-        
-        let bg = beta * (1.0 / (1.0 - beta * beta).sqrt());
+// Effective charge of a partially stripped ion (Barkas parameterisation)
+// z_eff = z [1 - exp(-125 β z^(-2/3))], applied only when |z| > 1
+pub fn effective_charge(charge: f64, beta: f64) -> f64 {
+    if charge.abs() <= 1.0 {
+        return charge;
+    }
 
-        // shell correction
-        let sc = (0.422377*bg.powi(-2) + 0.0304043*bg.powi(-4) - 0.00038106*bg.powi(-6))*(10.0_f64.powi(-6))*((I.powi(2)*10.0_f64.powi(-6))) 
-                    + (3.850190*bg.powi(-2)-0.1667989*bg.powi(-4) + 0.00157955*bg.powi(-6))*(10.0_f64.powi(-9))*((I.powi(3)*10.0_f64.powi(-6)));
+    charge * (1.0 - (-125.0 * beta * charge.abs().powf(-2.0 / 3.0)).exp())
+}
 
-        let de_dx = ((const_general * Z_PROTON.powi(2) * ELECTRON_PER_VOLUME_H20) / (beta.powi(2)))
-        * ((2.0 * electron_mass_eV * beta.powi(2) / WATER_EXCITATION_ENERGY).ln()
-        - (1.0 - beta.powi(2)).ln() - beta.powi(2) - 2.0*(sc / WATER_ATOMIC_NUMBER));
+// Maximum kinetic energy (MeV) transferable to a free electron in a single collision
+// Tmax = 2 m_e c² β²γ² / (1 + 2γ m_e/M + (m_e/M)²)
+pub fn max_energy_transfer(energy_mev: f64, projectile: &Projectile) -> f64 {
+    let electron_mass_mev = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE / 1e6;
+    let gamma = kinematics::gamma(energy_mev, projectile.mass);
+    let bg2 = kinematics::beta_gamma(energy_mev, projectile.mass).powi(2);
+    let ratio = electron_mass_mev / projectile.mass;
 
-        let energy_MeV = energy_eV / 1e6;
+    2.0 * electron_mass_mev * bg2 / (1.0 + 2.0 * gamma * ratio + ratio * ratio)
+}
 
-        energies.push(energy_MeV);
-        stopping_powers.push(de_dx);
+// Barkas term L1 per unit projectile charge, using Lindhard's harmonic-oscillator
+// estimate L1 = (3π/2) α (I / m_e c²) / β³ · ln(2 m_e c² β²γ² / I) with ħω0 ≈ I
+fn barkas_correction(beta: f64, bg: f64, i_barkas: f64) -> f64 {
+    let electron_mass_ev = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
+    let log_term = (2.0 * electron_mass_ev * bg * bg / i_barkas).ln().max(0.0);
 
-            
-        writeln!(file, "{:.1}\t{:e}", energy_MeV, de_dx).expect("Unable to write data");
-        println!("{:.1} MeV (dE/dx): {} MeV/cm", energy_MeV, de_dx);
-    }
+    1.5 * PI * FINE_STRUCTURE * (i_barkas / electron_mass_ev) / beta.powi(3) * log_term
 }
 
-pub fn bethe_bloch_all_corrections(n_points: &u32, energies: &mut Vec<f64>, stopping_powers: &mut Vec<f64>, variables: &HashMap<String, f64>) {
-    // Retrieve variables from the HashMap
-    let a: f64 = variables.get(&String::from("a")).copied().unwrap();
-    let x0: f64 = variables.get(&String::from("x0")).copied().unwrap();
-    let x1: f64 = variables.get(&String::from("x1")).copied().unwrap();
-    let m_param: f64 = variables.get(&String::from("m_param")).copied().unwrap();
-    let c_param: f64 = variables.get(&String::from("c_param")).copied().unwrap();
+// Bloch term z²L2 = -y² Σ 1/(n(n² + y²)) with y = zα/β
+fn bloch_correction(y: f64) -> f64 {
+    const TERMS: u32 = 20;
 
-    // Derived constants
-    let proton_mass = (PROTON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
-    let electron_mass = ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT;
-    let electron_mass_eV = (ELECTRON_MASS_0 * SPEED_OF_LIGHT * SPEED_OF_LIGHT) / ELECTRON_CHARGE;
+    let y2 = y * y;
+    let mut sum: f64 = (1..=TERMS).map(|n| {
+        let n = n as f64;
+        1.0 / (n * (n * n + y2))
+    }).sum();
 
-    // General constant for the Bethe–Bloch calculation
-    let const_general = (4.0 * PI * ELECTRON_CHARGE.powi(4) * COULOMB_CONST.powi(2)) / (electron_mass * ELECTRON_CHARGE * 1.0e8);
+    // Remaining terms behave like 1/n³
+    sum += 1.0 / (2.0 * (TERMS as f64 + 0.5).powi(2));
 
-    // Ionization constant I (not used further in the calculation)
-    let I = if WATER_ATOMIC_NUMBER < 13.0 {
-        (12.0 * WATER_ATOMIC_NUMBER + 7.0) / 1e6
-    } else {
-        (9.76 * Z_PROTON + 58.8 * WATER_ATOMIC_NUMBER.powf(-0.19)) / 1e6
-    };
+    -y2 * sum
+}
 
-    // Open file for writing results
-    let mut file = File::create("output/fstopping_all_corrections.txt").expect("Unable to create file");
-
-    // BETHE-BLOCH WITH ALL CORRECTIONS
-    println!("Bethe-Bloch with all corrections");
-
-    for i in 0..*n_points{
-        let energy_eV = PROTON_ENERGY_MeV_I * ((i as f64 + 1.0) * 10.0) * 1e6;
-    
-        let beta = ((energy_eV * (energy_eV + 2.0 * proton_mass)).sqrt())
-                        / (energy_eV + proton_mass);
-            
-        let bg = beta * (1.0 / (1.0 - beta * beta).sqrt());
-
-        // delta density correction
-        let x = bg.log10();
-        let delta = if x >= x1{
-            2.0 * 10.0_f64.log10() * x + c_param   
-        } else if x0 <= x {
-            2.0 * 10.0_f64.log10() * x + c_param + a * f64::powf(x1 - x,m_param) 
-        } else{
-            0.0_f64
-        };
-    
-        // shell correction
-        let sc = (0.422377*bg.powi(-2) + 0.0304043*bg.powi(-4) - 0.00038106*bg.powi(-6))*(10.0_f64.powi(-6))*((I.powi(2)*10.0_f64.powi(-6))) 
-                    + (3.850190*bg.powi(-2)-0.1667989*bg.powi(-4) + 0.00157955*bg.powi(-6))*(10.0_f64.powi(-9))*((I.powi(3)*10.0_f64.powi(-6)));
-    
-        let de_dx = ((const_general * Z_PROTON.powi(2) * ELECTRON_PER_VOLUME_H20) / (beta.powi(2)))
-        * ((2.0 * electron_mass_eV * beta.powi(2) / WATER_EXCITATION_ENERGY).ln()
-        - (1.0 - beta.powi(2)).ln() - beta.powi(2) - delta - 2.0*(sc / WATER_ATOMIC_NUMBER));
-    
-        let energy_MeV = energy_eV / 1e6;
-
-        energies.push(energy_MeV);
-        stopping_powers.push(de_dx);
-    
-                
-        writeln!(file, "{:.1}\t{:e}", energy_MeV, de_dx).expect("Unable to write data");
-        println!("{:.1} MeV (dE/dx): {} MeV/cm", energy_MeV, de_dx);
+// Stopping power and Bohr straggling of the projectile over every point of the energy grid,
+// with the corrections selected in `corrections`
+pub fn bethe_bloch(projectile: &Projectile, material: &Material, grid: &EnergyGrid, corrections: &Corrections) -> StoppingPowerTable {
+    bethe_bloch_with_progress(projectile, material, grid, corrections, || {})
+}
+
+// As `bethe_bloch`, calling `progress` after every grid point. The points are computed in
+// parallel, so `progress` may be called from several threads, and are kept in grid order.
+pub fn bethe_bloch_with_progress<F: Fn() + Sync>(projectile: &Projectile, material: &Material, grid: &EnergyGrid, corrections: &Corrections,
+    progress: F) -> StoppingPowerTable {
+    let points: Vec<(f64, f64, f64)> = grid
+        .energies()
+        .into_par_iter()
+        .map(|energy_mev| {
+            let de_dx = stopping_power(energy_mev, projectile, material, corrections);
+            let variance = bohr_variance(energy_mev, projectile, material);
+            progress();
+            (energy_mev, de_dx, variance)
+        })
+        .collect();
+
+    let mut table = StoppingPowerTable::with_capacity(points.len());
+    let mut straggling = Vec::with_capacity(points.len());
+    for (energy_mev, de_dx, variance) in points {
+        table.push(energy_mev, de_dx);
+        straggling.push(variance);
     }
-}
\ No newline at end of file
+
+    table.straggling = Some(straggling);
+    table
+}
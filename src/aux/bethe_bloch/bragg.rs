@@ -0,0 +1,242 @@
+// This module steps a projectile through the target to produce the Bragg curve
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{write_file, Result};
+use crate::output::{write_columns, Format, TextFormat};
+
+use super::bb::{stopping_power, Corrections};
+use super::material::AVOGADRO;
+use super::range::{cutoff, residual_range};
+use super::straggling::bohr_variance;
+use super::{Material, Projectile};
+
+// Pairs of depth (cm) and deposited energy per unit depth (MeV/cm) for one particle
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BraggCurve {
+    pub depths: Vec<f64>,
+    pub doses: Vec<f64>,
+}
+
+impl BraggCurve {
+    pub fn push(&mut self, depth: f64, dose: f64) {
+        self.depths.push(depth);
+        self.doses.push(dose);
+    }
+
+    pub fn len(&self) -> usize {
+        self.depths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depths.is_empty()
+    }
+
+    // Iterate over (depth, dose) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.depths.iter().copied().zip(self.doses.iter().copied())
+    }
+
+    // Depth and dose of the Bragg peak
+    pub fn peak(&self) -> Option<(f64, f64)> {
+        self.iter().fold(None, |best, (depth, dose)| match best {
+            Some((_, best_dose)) if best_dose >= dose => best,
+            _ => Some((depth, dose)),
+        })
+    }
+
+    // Remove the primaries lost to nuclear interactions, scaling the dose by the surviving
+    // fluence exp(-μ z) for an attenuation coefficient μ (1/cm)
+    pub fn attenuated(&self, coefficient: f64) -> BraggCurve {
+        BraggCurve {
+            depths: self.depths.clone(),
+            doses: self.iter().map(|(depth, dose)| dose * (-coefficient * depth).exp()).collect(),
+        }
+    }
+
+    // Write the curve as delimited "depth dose" lines
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            for (depth, dose) in self.iter() {
+                writeln!(file, "{}", text.join(&[text.fixed(depth, 4), text.scientific(dose)]))?;
+            }
+            Ok(())
+        })
+    }
+
+    // Write the curve in `format` with "depth, dose" columns, CSV and JSON below `metadata`
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, format: Format, text: &TextFormat, metadata: &[(&str, Value)]) -> Result<()> {
+        if format == Format::Text {
+            return self.write(path, text);
+        }
+        let (header, rows) = self.columns();
+        write_columns(path, format, metadata, &header, rows)
+    }
+
+    // Headers and rows of the "depth, dose" columns
+    pub fn columns(&self) -> (Vec<String>, Vec<Vec<f64>>) {
+        let header = vec![String::from("depth (cm)"), String::from("dose (MeV/cm)")];
+        (header, self.iter().map(|(depth, dose)| vec![depth, dose]).collect())
+    }
+}
+
+// Track-averaged and dose-averaged LET (MeV/cm) of the primaries against depth (cm)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LetProfile {
+    pub depths: Vec<f64>,
+    pub track: Vec<f64>,
+    pub dose: Vec<f64>,
+}
+
+impl LetProfile {
+    pub fn len(&self) -> usize {
+        self.depths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depths.is_empty()
+    }
+
+    // Iterate over (depth, LET_track, LET_dose) triples
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64, f64)> + '_ {
+        self.depths
+            .iter()
+            .zip(&self.track)
+            .zip(&self.dose)
+            .map(|((&depth, &track), &dose)| (depth, track, dose))
+    }
+
+    // Write the profile as delimited "depth LET_track LET_dose" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["depth (cm)", "LET_track (MeV/cm)", "LET_dose (MeV/cm)"]))?;
+            for (depth, track, dose) in self.iter() {
+                writeln!(file, "{}", text.join(&[text.fixed(depth, 4), text.scientific(track), text.scientific(dose)]))?;
+            }
+            Ok(())
+        })
+    }
+
+    // Write the profile in `format` with "depth, LET_track, LET_dose" columns, CSV and JSON below `metadata`
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, format: Format, text: &TextFormat, metadata: &[(&str, Value)]) -> Result<()> {
+        if format == Format::Text {
+            return self.write(path, text);
+        }
+        let (header, rows) = self.columns();
+        write_columns(path, format, metadata, &header, rows)
+    }
+
+    // Headers and rows of the "depth, LET_track, LET_dose" columns
+    pub fn columns(&self) -> (Vec<String>, Vec<Vec<f64>>) {
+        let header = vec![String::from("depth (cm)"), String::from("LET_track (MeV/cm)"), String::from("LET_dose (MeV/cm)")];
+        (header, self.iter().map(|(depth, track, dose)| vec![depth, track, dose]).collect())
+    }
+}
+
+// Nuclear attenuation coefficient μ (1/cm) of primaries in `material`, from a nonelastic cross
+// section (barn per entity of mass `material.a`, i.e. per molecule for water) or, by default,
+// from the nuclear interaction length of the material
+pub fn attenuation_coefficient(material: &Material, cross_section: Option<f64>) -> f64 {
+    match cross_section {
+        Some(barn) => barn * 1e-24 * AVOGADRO * material.density / material.a,
+        None => 1.0 / material.nuclear_interaction_length_cm(),
+    }
+}
+
+// Bragg curve of a projectile with initial kinetic energy `energy` (MeV), stepping
+// `step` cm at a time until it falls below the Bethe-Bloch cutoff. Panics unless `step` is
+// positive.
+pub fn bragg(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections, step: f64) -> BraggCurve {
+    assert!(step > 0.0, "the step of a Bragg curve must be positive, not {} cm", step);
+    let cutoff = cutoff(projectile);
+    let mut curve = BraggCurve::default();
+    let mut energy = energy;
+    let mut depth = 0.0;
+
+    while energy > cutoff {
+        // Midpoint estimate of the energy lost over the step
+        let de_start = stopping_power(energy, projectile, material, corrections) * step;
+        let de = stopping_power(energy - de_start / 2.0, projectile, material, corrections) * step;
+
+        // Last step: the remaining energy is spread evenly over its residual range, rather
+        // than piled up in a single step
+        if de_start >= energy - cutoff || !de.is_finite() || de >= energy - cutoff {
+            let steps = (residual_range(energy, projectile, material, corrections) / step).ceil().max(1.0);
+            for i in 0..steps as usize {
+                curve.push(depth + (i as f64 + 0.5) * step, energy / (steps * step));
+            }
+            break;
+        }
+
+        curve.push(depth + step / 2.0, de / step);
+        depth += step;
+        energy -= de;
+    }
+
+    curve
+}
+
+// Number of points of the energy spectrum quadrature, spanning ±4σ
+const SPECTRUM_POINTS: usize = 41;
+
+// LET profile of a beam of kinetic energy `energy` (MeV) stepping `step` cm at a time. The energy
+// spectrum at each depth is a Gaussian whose variance grows with the Bohr straggling and with the
+// spreading by the energy dependence of dE/dx, dσ²/dx = dΩ²/dx - 2 (dS/dE) σ². Over that spectrum
+//   LET_track = ∫ φ(E) S(E) dE / ∫ φ(E) dE
+//   LET_dose  = ∫ φ(E) S(E)² dE / ∫ φ(E) S(E) dE
+// counting only the particles still above the Bethe-Bloch cutoff. Panics unless `step` is
+// positive.
+pub fn let_profile(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections, step: f64) -> LetProfile {
+    assert!(step > 0.0, "the step of a LET profile must be positive, not {} cm", step);
+    let cutoff = cutoff(projectile);
+    let mut profile = LetProfile::default();
+    let mut energy = energy;
+    let mut variance: f64 = 0.0;
+    let mut depth = 0.0;
+    let de_dx = |e: f64| stopping_power(e, projectile, material, corrections);
+
+    while energy > cutoff {
+        let sigma = variance.sqrt();
+        let (mut fluence, mut track, mut dose) = (0.0, 0.0, 0.0);
+        for i in 0..SPECTRUM_POINTS {
+            let x = 8.0 * i as f64 / (SPECTRUM_POINTS - 1) as f64 - 4.0;
+            let e = energy + x * sigma;
+            if e <= cutoff {
+                continue;
+            }
+            let s = de_dx(e);
+            if !s.is_finite() || s <= 0.0 {
+                continue;
+            }
+            let weight = (-x * x / 2.0).exp();
+            fluence += weight;
+            track += weight * s;
+            dose += weight * s * s;
+        }
+        if fluence <= 0.0 || track <= 0.0 {
+            break;
+        }
+        profile.depths.push(depth + step / 2.0);
+        profile.track.push(track / fluence);
+        profile.dose.push(dose / track);
+
+        // Midpoint step of the mean energy, as in `bragg`
+        let de_start = de_dx(energy) * step;
+        let mid = energy - de_start / 2.0;
+        let de = de_dx(mid) * step;
+        if de_start >= energy - cutoff || !de.is_finite() || de >= energy - cutoff {
+            break;
+        }
+
+        let h = 0.01 * mid;
+        let slope = (de_dx(mid + h) - de_dx(mid - h)) / (2.0 * h);
+        variance += (bohr_variance(mid, projectile, material) - 2.0 * slope * variance) * step;
+        depth += step;
+        energy -= de;
+    }
+
+    profile
+}
@@ -0,0 +1,39 @@
+// This module provides the Cherenkov threshold, emission angle and photon yield of a
+// projectile in a material with a known refractive index
+use std::f64::consts::PI;
+
+use super::kinematics;
+use super::{Material, Projectile};
+
+// Fine-structure constant
+const FINE_STRUCTURE: f64 = 7.2973525693e-3;
+
+// Threshold velocity β = 1/n, None if the refractive index is unknown or n <= 1
+pub fn threshold_beta(material: &Material) -> Option<f64> {
+    material.refractive_index.filter(|&n| n > 1.0).map(|n| 1.0 / n)
+}
+
+// Kinetic energy (MeV) above which the projectile radiates Cherenkov light
+pub fn threshold_energy(projectile: &Projectile, material: &Material) -> Option<f64> {
+    threshold_beta(material).map(|beta| kinematics::kinetic_from_beta(beta, projectile.mass))
+}
+
+// Emission angle θ (rad) with cos θ = 1/(nβ), None below threshold
+pub fn cherenkov_angle(energy: f64, projectile: &Projectile, material: &Material) -> Option<f64> {
+    let n = material.refractive_index?;
+    let cos_theta = 1.0 / (n * kinematics::beta(energy, projectile.mass));
+    (cos_theta < 1.0).then(|| cos_theta.acos())
+}
+
+// Photons emitted per cm between the wavelengths `lambda_min` and `lambda_max` (nm), from the
+// Frank-Tamm formula dN/dx = 2πα z² sin²θ (1/λmin - 1/λmax) with a constant refractive index
+pub fn photon_yield(energy: f64, projectile: &Projectile, material: &Material, lambda_min: f64, lambda_max: f64) -> f64 {
+    match cherenkov_angle(energy, projectile, material) {
+        Some(theta) => {
+            let per_nm = 2.0 * PI * FINE_STRUCTURE * projectile.charge.powi(2) * theta.sin().powi(2)
+                * (1.0 / lambda_min - 1.0 / lambda_max);
+            per_nm * 1e7
+        }
+        None => 0.0,
+    }
+}
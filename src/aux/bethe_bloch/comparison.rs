@@ -0,0 +1,73 @@
+// This module compares the computed stopping power with external data such as SRIM or
+// Geant4 output, point by point at the energies of the external table
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::reference::ReferenceTable;
+use super::{Material, Projectile, Unit};
+
+// External values and the computed stopping power at the same energies, both in `unit`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    pub label: String,
+    pub unit: Unit,
+    pub energies: Vec<f64>,
+    pub reference: Vec<f64>,
+    pub computed: Vec<f64>,
+}
+
+impl Comparison {
+    // Percentage difference 100 (computed - reference)/reference at each energy
+    pub fn differences(&self) -> Vec<f64> {
+        self.computed.iter().zip(&self.reference).map(|(c, r)| 100.0 * (c - r) / r).collect()
+    }
+
+    // Ratio computed/reference at each energy
+    pub fn ratios(&self) -> Vec<f64> {
+        self.computed.iter().zip(&self.reference).map(|(c, r)| c / r).collect()
+    }
+
+    // Mean of the absolute percentage differences
+    pub fn mean_abs_difference(&self) -> f64 {
+        if self.energies.is_empty() {
+            return 0.0;
+        }
+        self.differences().iter().map(|d| d.abs()).sum::<f64>() / self.energies.len() as f64
+    }
+
+    // Energy and percentage difference where the difference is largest in magnitude
+    pub fn max_difference(&self) -> Option<(f64, f64)> {
+        self.energies.iter().copied().zip(self.differences()).max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+    }
+
+    // Write the comparison as delimited "energy reference computed difference" lines
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            let header = [String::from("E (MeV)"), format!("{} ({})", self.label, self.unit), format!("computed ({})", self.unit), String::from("difference (%)")];
+            writeln!(file, "# {}", text.join(&header))?;
+            for (((&energy, &reference), &computed), difference) in self.energies.iter().zip(&self.reference).zip(&self.computed).zip(self.differences()) {
+                let fields = [text.scientific(energy), text.scientific(reference), text.scientific(computed), text.scientific(difference)];
+                writeln!(file, "{}", text.join(&fields))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// Compare the (energy, dE/dx in `unit`) pairs of `reference` with the stopping power computed
+// with `corrections`. Points with a non-positive dE/dx are skipped.
+pub fn compare(reference: &ReferenceTable, label: &str, projectile: &Projectile, material: &Material, corrections: &Corrections, unit: Unit) -> Comparison {
+    let mut comparison = Comparison { label: label.to_string(), unit, ..Comparison::default() };
+    for (energy, value) in reference.iter().filter(|&(e, s)| e > 0.0 && s > 0.0) {
+        comparison.energies.push(energy);
+        comparison.reference.push(value);
+        comparison.computed.push(unit.from_mev_per_cm(stopping_power(energy, projectile, material, corrections), material));
+    }
+    comparison
+}
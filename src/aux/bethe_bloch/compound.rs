@@ -0,0 +1,110 @@
+// This module builds compound and mixture materials using the Bragg additivity rule
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+use super::elements;
+use super::Material;
+
+// A constituent of the compound, given by weight fraction
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Component {
+    z: f64,
+    a: f64,
+    mean_excitation_energy: f64,
+    weight_fraction: f64,
+}
+
+// Builder for compounds (e.g. polyethylene) and mixtures (e.g. Ar/CO2)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Compound {
+    name: String,
+    density: f64,
+    components: Vec<Component>,
+    mean_excitation_energy: Option<f64>,
+}
+
+impl Compound {
+    // Start a compound with the given name and density (g/cm³)
+    pub fn new(name: &str, density: f64) -> Self {
+        Compound {
+            name: String::from(name),
+            density,
+            components: Vec::new(),
+            mean_excitation_energy: None,
+        }
+    }
+
+    // Add an element by chemical symbol with its weight fraction
+    pub fn element(mut self, symbol: &str, weight_fraction: f64) -> Result<Self> {
+        let element = elements::by_symbol(symbol).ok_or_else(|| Error::UnknownElement(symbol.to_string()))?;
+        self.components.push(Component {
+            z: element.z,
+            a: element.a,
+            mean_excitation_energy: element.mean_excitation_energy,
+            weight_fraction,
+        });
+        Ok(self)
+    }
+
+    // Add another material (e.g. a gas of a mixture) with its weight fraction
+    pub fn material(mut self, material: &Material, weight_fraction: f64) -> Self {
+        self.components.push(Component {
+            z: material.z,
+            a: material.a,
+            mean_excitation_energy: material.mean_excitation_energy,
+            weight_fraction,
+        });
+        self
+    }
+
+    // Override the Bragg-additivity I with a measured value (eV)
+    pub fn mean_excitation_energy(mut self, mean_excitation_energy: f64) -> Self {
+        self.mean_excitation_energy = Some(mean_excitation_energy);
+        self
+    }
+
+    // Combine the components into an effective (mean atom) material. A compound without
+    // components, with a negative weight fraction or with no weight at all has none.
+    pub fn build(&self) -> Result<Material> {
+        if self.components.is_empty() {
+            return Err(Error::Invalid(format!("The compound {} has no elements", self.name)));
+        }
+        if self.components.iter().any(|c| !(c.weight_fraction.is_finite() && c.weight_fraction >= 0.0)) {
+            return Err(Error::Invalid(format!("The weight fractions of the compound {} must be positive or zero", self.name)));
+        }
+        let total_weight: f64 = self.components.iter().map(|c| c.weight_fraction).sum();
+        if total_weight <= 0.0 {
+            return Err(Error::Invalid(format!("The weight fractions of the compound {} add up to zero", self.name)));
+        }
+
+        // Electrons per unit mass (Z/A) and atoms per unit mass (1/A)
+        let mut z_over_a = 0.0;
+        let mut atoms_per_gram = 0.0;
+        let mut log_i = 0.0;
+        for component in &self.components {
+            let w = component.weight_fraction / total_weight;
+            z_over_a += w * component.z / component.a;
+            atoms_per_gram += w / component.a;
+            log_i += w * component.z / component.a * component.mean_excitation_energy.ln();
+        }
+
+        // Bragg additivity: ln I = Σ w_i (Z/A)_i ln I_i / Σ w_i (Z/A)_i
+        let mean_excitation_energy = self
+            .mean_excitation_energy
+            .unwrap_or_else(|| (log_i / z_over_a).exp());
+
+        let a = 1.0 / atoms_per_gram;
+        let z = z_over_a * a;
+
+        Ok(Material::new(&self.name, z, a, self.density, mean_excitation_energy))
+    }
+}
+
+impl TryFrom<Compound> for Material {
+    type Error = Error;
+
+    fn try_from(compound: Compound) -> Result<Self> {
+        compound.build()
+    }
+}
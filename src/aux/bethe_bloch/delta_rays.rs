@@ -0,0 +1,57 @@
+// This module computes the spectrum of knock-on electrons (delta rays) ejected by the projectile
+use super::bb::{effective_charge, max_energy_transfer};
+use super::kinematics;
+use super::{Material, Projectile};
+
+// 2π r_e² m_e c² (MeV cm²)
+const DELTA_CONST: f64 = 2.5496e-25;
+
+// Differential number of delta rays d²N/dT dx (1/(MeV cm)) with kinetic energy `kinetic` (MeV)
+// for a projectile of kinetic energy `energy` (MeV): free-electron Rutherford cross section
+// times the spin factor F(T) = 1 - β² T/Tmax [+ T²/2E² for spin 1/2]
+pub fn delta_ray_spectrum(kinetic: f64, energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let tmax = max_energy_transfer(energy, projectile);
+    if kinetic <= 0.0 || kinetic > tmax {
+        return 0.0;
+    }
+
+    let (beta2, prefactor) = prefactor(energy, projectile, material);
+    let total_energy = energy + projectile.mass;
+    let mut spin_factor = 1.0 - beta2 * kinetic / tmax;
+    if is_spin_half(projectile) {
+        spin_factor += kinetic * kinetic / (2.0 * total_energy * total_energy);
+    }
+
+    prefactor * spin_factor / (kinetic * kinetic)
+}
+
+// Number of delta rays per cm with kinetic energy above `threshold` (MeV), integrating the
+// spectrum analytically up to Tmax
+pub fn delta_rays_above(threshold: f64, energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let tmax = max_energy_transfer(energy, projectile);
+    if threshold >= tmax || threshold <= 0.0 {
+        return 0.0;
+    }
+
+    let (beta2, prefactor) = prefactor(energy, projectile, material);
+    let total_energy = energy + projectile.mass;
+    let mut integral = 1.0 / threshold - 1.0 / tmax - beta2 / tmax * (tmax / threshold).ln();
+    if is_spin_half(projectile) {
+        integral += (tmax - threshold) / (2.0 * total_energy * total_energy);
+    }
+
+    prefactor * integral
+}
+
+// β² and 2π r_e² m_e c² z² n_e / β² (MeV/cm)
+fn prefactor(energy: f64, projectile: &Projectile, material: &Material) -> (f64, f64) {
+    let beta2 = kinematics::beta_squared(energy, projectile.mass);
+    let charge = effective_charge(projectile.charge, beta2.sqrt());
+
+    (beta2, DELTA_CONST * charge * charge * material.electron_density / beta2)
+}
+
+// Only the spin-1/2 factor is implemented; other spins use the spin-0 form
+fn is_spin_half(projectile: &Projectile) -> bool {
+    (projectile.spin - 0.5).abs() < 1e-9
+}
@@ -0,0 +1,162 @@
+// This module fits the Sternheimer density-effect coefficients to a reference stopping-power
+// table by least squares on the relative residuals, with a Nelder-Mead simplex search
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::reference::ReferenceTable;
+use super::{DensityCorrectionParams, Material, Projectile};
+
+// Simplex iterations and the relative size of the initial simplex
+const MAX_ITERATIONS: usize = 2000;
+const INITIAL_STEP: f64 = 0.1;
+
+// Fitted coefficients with the residuals before and after the fit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DensityFit {
+    pub params: DensityCorrectionParams,
+    // Root mean square of the relative residuals with the initial and the fitted coefficients
+    pub initial_rms: f64,
+    pub rms: f64,
+    // Reference energies (MeV) used in the fit and the relative residuals
+    // (model - reference)/reference there with the initial and the fitted coefficients
+    pub energies: Vec<f64>,
+    pub initial_residuals: Vec<f64>,
+    pub residuals: Vec<f64>,
+    pub iterations: usize,
+}
+
+impl DensityFit {
+    // Write the residuals as delimited "energy initial fitted" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["E (MeV)", "initial residual", "fitted residual"]))?;
+            for ((&energy, &initial), &fitted) in self.energies.iter().zip(&self.initial_residuals).zip(&self.residuals) {
+                writeln!(file, "{}", text.join(&[text.scientific(energy), text.scientific(initial), text.scientific(fitted)]))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// Fit a, x0, x1, C and m starting from `corrections.density_params` (δ0 is kept), using the
+// reference points above `min_energy` MeV where the Bethe-Bloch formula applies
+pub fn fit_density_params(reference: &ReferenceTable, projectile: &Projectile, material: &Material, corrections: &Corrections, min_energy: f64) -> Option<DensityFit> {
+    let points: Vec<(f64, f64)> = reference.iter().filter(|&(e, s)| e >= min_energy && s > 0.0).collect();
+    if points.len() < 5 {
+        return None;
+    }
+
+    let corrections = Corrections { density: true, ..*corrections };
+    let initial = corrections.density_params;
+    let residuals = |params: &DensityCorrectionParams| -> Vec<f64> {
+        let corrections = Corrections { density_params: *params, ..corrections };
+        points
+            .iter()
+            .map(|&(e, s)| stopping_power(e, projectile, material, &corrections) / material.density / s - 1.0)
+            .collect()
+    };
+    let cost = |x: &[f64; 5]| -> f64 {
+        let params = from_vector(x, &initial);
+        // Keep the coefficients physical: a, m > 0 and x1 > x0
+        if params.a <= 0.0 || params.m <= 0.0 || params.x1 <= params.x0 {
+            return f64::INFINITY;
+        }
+        residuals(&params).iter().map(|r| r * r).sum()
+    };
+
+    let (best, iterations) = nelder_mead(cost, to_vector(&initial));
+    let params = from_vector(&best, &initial);
+    let rms = |r: &[f64]| (r.iter().map(|r| r * r).sum::<f64>() / r.len() as f64).sqrt();
+    let (before, after) = (residuals(&initial), residuals(&params));
+
+    Some(DensityFit {
+        params,
+        initial_rms: rms(&before),
+        rms: rms(&after),
+        energies: points.iter().map(|&(e, _)| e).collect(),
+        initial_residuals: before,
+        residuals: after,
+        iterations,
+    })
+}
+
+fn to_vector(params: &DensityCorrectionParams) -> [f64; 5] {
+    [params.a, params.x0, params.x1, params.c, params.m]
+}
+
+fn from_vector(x: &[f64; 5], initial: &DensityCorrectionParams) -> DensityCorrectionParams {
+    DensityCorrectionParams { a: x[0], x0: x[1], x1: x[2], c: x[3], m: x[4], delta0: initial.delta0 }
+}
+
+// Minimise `cost` with the Nelder-Mead simplex (reflection 1, expansion 2, contraction and
+// shrink 1/2), returning the best vertex and the number of iterations
+fn nelder_mead<F: Fn(&[f64; 5]) -> f64>(cost: F, start: [f64; 5]) -> ([f64; 5], usize) {
+    const N: usize = 5;
+
+    let mut simplex: Vec<([f64; N], f64)> = Vec::with_capacity(N + 1);
+    simplex.push((start, cost(&start)));
+    for i in 0..N {
+        let mut vertex = start;
+        vertex[i] += if vertex[i] != 0.0 { INITIAL_STEP * vertex[i] } else { INITIAL_STEP };
+        simplex.push((vertex, cost(&vertex)));
+    }
+
+    let mut iterations = 0;
+    while iterations < MAX_ITERATIONS {
+        iterations += 1;
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[N].1);
+        if (worst - best).abs() <= 1e-12 * (best.abs() + 1e-30) {
+            break;
+        }
+
+        // Centroid of every vertex except the worst
+        let mut centroid = [0.0; N];
+        for (vertex, _) in &simplex[..N] {
+            for (c, v) in centroid.iter_mut().zip(vertex) {
+                *c += v / N as f64;
+            }
+        }
+        let along = |t: f64| -> [f64; N] {
+            let mut point = [0.0; N];
+            for i in 0..N {
+                point[i] = centroid[i] + t * (simplex[N].0[i] - centroid[i]);
+            }
+            point
+        };
+
+        let reflected = along(-1.0);
+        let reflected_cost = cost(&reflected);
+        if reflected_cost < simplex[0].1 {
+            let expanded = along(-2.0);
+            let expanded_cost = cost(&expanded);
+            simplex[N] = if expanded_cost < reflected_cost { (expanded, expanded_cost) } else { (reflected, reflected_cost) };
+        } else if reflected_cost < simplex[N - 1].1 {
+            simplex[N] = (reflected, reflected_cost);
+        } else {
+            let contracted = along(0.5);
+            let contracted_cost = cost(&contracted);
+            if contracted_cost < simplex[N].1 {
+                simplex[N] = (contracted, contracted_cost);
+            } else {
+                // Shrink towards the best vertex
+                let best_vertex = simplex[0].0;
+                for (vertex, value) in simplex.iter_mut().skip(1) {
+                    for (v, b) in vertex.iter_mut().zip(&best_vertex) {
+                        *v = b + 0.5 * (*v - b);
+                    }
+                    *value = cost(vertex);
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    (simplex[0].0, iterations)
+}
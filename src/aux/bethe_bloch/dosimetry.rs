@@ -0,0 +1,73 @@
+// This module converts between particle fluence and absorbed dose, D = Φ S/ρ, for a
+// broad field of monoenergetic charged particles under charged-particle equilibrium
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::{EnergyGrid, Material, Projectile};
+
+// 1 MeV/g in Gy
+pub const MEV_PER_GRAM_IN_GY: f64 = 1.602176634e-10;
+
+// Elementary charge (C)
+const ELEMENTARY_CHARGE: f64 = 1.602176634e-19;
+
+// Particles delivered uniformly over a field of `area` cm²
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Field {
+    pub particles: f64,
+    pub area: f64,
+}
+
+impl Field {
+    pub fn new(particles: f64, area: f64) -> Self {
+        Field { particles, area }
+    }
+
+    // Field delivered by a beam current `current` (nA) during `time` s, for projectiles of `charge` e
+    pub fn from_current(current: f64, time: f64, area: f64, charge: f64) -> Self {
+        Field { particles: current * 1e-9 * time / (ELEMENTARY_CHARGE * charge.abs()), area }
+    }
+
+    // Fluence Φ (1/cm²)
+    pub fn fluence(&self) -> f64 {
+        self.particles / self.area
+    }
+}
+
+// Dose (Gy) from a fluence (1/cm²) and a mass stopping power (MeV cm²/g)
+pub fn dose_from_fluence(fluence: f64, mass_stopping_power: f64) -> f64 {
+    fluence * mass_stopping_power * MEV_PER_GRAM_IN_GY
+}
+
+// Fluence (1/cm²) delivering `dose` Gy with a mass stopping power (MeV cm²/g)
+pub fn fluence_for_dose(dose: f64, mass_stopping_power: f64) -> f64 {
+    dose / (mass_stopping_power * MEV_PER_GRAM_IN_GY)
+}
+
+// Dose (Gy) at the surface of `material` from a field of projectiles of kinetic energy `energy` (MeV)
+pub fn field_dose(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections, field: &Field) -> f64 {
+    let mass_stopping_power = stopping_power(energy, projectile, material, corrections) / material.density;
+    dose_from_fluence(field.fluence(), mass_stopping_power)
+}
+
+// Write the fluence-to-dose conversion coefficients as delimited "energy S/ρ D/Φ" lines
+pub fn write_conversion<P>(grid: &EnergyGrid, projectile: &Projectile, material: &Material, corrections: &Corrections, path: P, text: &TextFormat) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    write_file(path, |file| {
+        writeln!(file, "# {}", text.join(&["E (MeV)", "S/rho (MeV cm^2/g)", "D/Phi (Gy cm^2)"]))?;
+        for energy in grid.energies() {
+            let mass_stopping_power = stopping_power(energy, projectile, material, corrections) / material.density;
+            let fields = [text.scientific(energy), text.scientific(mass_stopping_power), text.scientific(dose_from_fluence(1.0, mass_stopping_power))];
+            writeln!(file, "{}", text.join(&fields))?;
+        }
+        Ok(())
+    })
+}
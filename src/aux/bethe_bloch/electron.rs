@@ -0,0 +1,45 @@
+// This module provides the collision stopping power of electrons (Møller) and positrons
+// (Bhabha), for which the heavy-particle Bethe-Bloch formula does not apply
+use std::f64::consts::LN_2;
+
+use super::bb::Corrections;
+use super::kinematics;
+use super::{Material, Projectile};
+
+// Electron rest energy (MeV)
+pub const ELECTRON_MASS: f64 = 0.51099895;
+
+// 2π r_e² m_e c² (MeV cm²)
+const COLLISION_CONST: f64 = 2.5496e-25;
+
+// Whether the projectile is an electron or a positron (by its rest mass)
+pub fn is_lepton(projectile: &Projectile) -> bool {
+    (projectile.mass - ELECTRON_MASS).abs() < 1e-6
+}
+
+// Collision stopping power (MeV/cm) of an electron, or a positron if `positron`, with
+// kinetic energy `energy` (MeV) (ICRU 37):
+// dE/dx = 2π r_e² m_e c² n_e / β² [ln(τ²(τ + 2) / 2(I/m_e c²)²) + F±(τ) - δ]
+pub fn collision_stopping_power(energy: f64, positron: bool, material: &Material, corrections: &Corrections) -> f64 {
+    let tau = energy / ELECTRON_MASS;
+    let gamma = kinematics::gamma(energy, ELECTRON_MASS);
+    let beta2 = kinematics::beta_squared(energy, ELECTRON_MASS);
+    let bg = kinematics::beta_gamma(energy, ELECTRON_MASS);
+    let i = material.mean_excitation_energy * 1e-6 / ELECTRON_MASS;
+
+    let f = if positron {
+        let y = tau + 2.0;
+        2.0 * LN_2 - beta2 / 12.0 * (23.0 + 14.0 / y + 10.0 / (y * y) + 4.0 / (y * y * y))
+    } else {
+        1.0 - beta2 + (tau * tau / 8.0 - (2.0 * tau + 1.0) * LN_2) / (gamma * gamma)
+    };
+
+    let delta = if corrections.density {
+        corrections.density_params.delta(bg)
+    } else {
+        0.0
+    };
+
+    COLLISION_CONST * material.electron_density / beta2
+        * ((tau * tau * (tau + 2.0) / (2.0 * i * i)).ln() + f - delta)
+}
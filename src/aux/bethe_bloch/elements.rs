@@ -0,0 +1,58 @@
+// This module provides elemental data used to build compounds and mixtures
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Element {
+    pub symbol: &'static str,
+    // Atomic number (Z) and standard atomic weight (A, g/mol)
+    pub z: f64,
+    pub a: f64,
+    // Mean excitation energy I (eV, ICRU 37)
+    pub mean_excitation_energy: f64,
+}
+
+const fn element(symbol: &'static str, z: f64, a: f64, mean_excitation_energy: f64) -> Element {
+    Element { symbol, z, a, mean_excitation_energy }
+}
+
+pub const ELEMENTS: [Element; 34] = [
+    element("H", 1.0, 1.008, 19.2),
+    element("He", 2.0, 4.0026, 41.8),
+    element("Li", 3.0, 6.94, 40.0),
+    element("Be", 4.0, 9.0122, 63.7),
+    element("B", 5.0, 10.81, 76.0),
+    element("C", 6.0, 12.011, 78.0),
+    element("N", 7.0, 14.007, 82.0),
+    element("O", 8.0, 15.999, 95.0),
+    element("F", 9.0, 18.998, 115.0),
+    element("Ne", 10.0, 20.180, 137.0),
+    element("Na", 11.0, 22.990, 149.0),
+    element("Mg", 12.0, 24.305, 156.0),
+    element("Al", 13.0, 26.982, 166.0),
+    element("Si", 14.0, 28.085, 173.0),
+    element("P", 15.0, 30.974, 173.0),
+    element("S", 16.0, 32.06, 180.0),
+    element("Cl", 17.0, 35.45, 174.0),
+    element("Ar", 18.0, 39.948, 188.0),
+    element("K", 19.0, 39.098, 190.0),
+    element("Ca", 20.0, 40.078, 191.0),
+    element("Ti", 22.0, 47.867, 233.0),
+    element("Fe", 26.0, 55.845, 286.0),
+    element("Ni", 28.0, 58.693, 311.0),
+    element("Cu", 29.0, 63.546, 322.0),
+    element("Zn", 30.0, 65.38, 330.0),
+    element("Ge", 32.0, 72.630, 350.0),
+    element("Ag", 47.0, 107.87, 470.0),
+    element("Sn", 50.0, 118.71, 488.0),
+    element("I", 53.0, 126.90, 491.0),
+    element("Xe", 54.0, 131.29, 482.0),
+    element("W", 74.0, 183.84, 727.0),
+    element("Au", 79.0, 196.97, 790.0),
+    element("Pb", 82.0, 207.2, 823.0),
+    element("U", 92.0, 238.03, 890.0),
+];
+
+// Look up an element by its chemical symbol
+pub fn by_symbol(symbol: &str) -> Option<Element> {
+    ELEMENTS.iter().find(|element| element.symbol == symbol).copied()
+}
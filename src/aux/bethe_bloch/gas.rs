@@ -0,0 +1,74 @@
+// This module describes gaseous targets (e.g. drift chamber gases) whose density follows
+// the ideal gas law with the temperature and pressure of the detector
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+use super::density::GAS_DENSITY_THRESHOLD;
+use super::Material;
+
+// Normal temperature (K) and pressure (atm) the gas presets are tabulated at
+pub const NORMAL_TEMPERATURE: f64 = 293.15;
+pub const NORMAL_PRESSURE: f64 = 1.0;
+
+// A gas whose `material` properties hold at the reference `temperature` (K) and `pressure` (atm)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Gas {
+    pub material: Material,
+    pub temperature: f64,
+    pub pressure: f64,
+}
+
+impl Gas {
+    // Gas described at 20 °C and 1 atm, like the air preset
+    pub fn new(material: Material) -> Self {
+        Gas { material, temperature: NORMAL_TEMPERATURE, pressure: NORMAL_PRESSURE }
+    }
+
+    // Gas described at another reference temperature (K) and pressure (atm)
+    pub fn with_reference(mut self, temperature: f64, pressure: f64) -> Self {
+        self.temperature = temperature;
+        self.pressure = pressure;
+        self
+    }
+
+    // Whether a material is light enough to be handled as a gas
+    pub fn is_gas(material: &Material) -> bool {
+        material.density < GAS_DENSITY_THRESHOLD
+    }
+
+    // Density ratio ρ(T, P)/ρ_ref = (P/P_ref)(T_ref/T) of an ideal gas
+    pub fn density_ratio(&self, temperature: f64, pressure: f64) -> f64 {
+        (pressure / self.pressure) * (self.temperature / temperature)
+    }
+
+    // The material at `temperature` (K) and `pressure` (atm): the density and electron density
+    // scale with the ideal gas law, the Sternheimer coefficients follow the new plasma energy and
+    // n - 1 of the refractive index scales with the density. Mass lengths (g/cm²) are unchanged.
+    pub fn at(&self, temperature: f64, pressure: f64) -> Material {
+        let ratio = self.density_ratio(temperature, pressure);
+        let material = &self.material;
+
+        Material {
+            density: material.density * ratio,
+            electron_density: material.electron_density * ratio,
+            sternheimer: material.sternheimer.map(|params| params.scaled(ratio)),
+            refractive_index: material.refractive_index.map(|n| 1.0 + (n - 1.0) * ratio),
+            ..material.clone()
+        }
+    }
+}
+
+// Material taken to the temperature (K) and pressure (atm) given, each defaulting to those of
+// its tabulation. Only gases follow them; other materials are refused unless neither is given.
+pub fn at_conditions(material: Material, temperature: Option<f64>, pressure: Option<f64>) -> Result<Material> {
+    if temperature.is_none() && pressure.is_none() {
+        return Ok(material);
+    }
+    if !Gas::is_gas(&material) {
+        return Err(Error::Invalid(format!("--temperature and --pressure only apply to gases, and {} is not one", material.name)));
+    }
+    let gas = Gas::new(material);
+    let (temperature, pressure) = (temperature.unwrap_or(gas.temperature), pressure.unwrap_or(gas.pressure));
+    Ok(gas.at(temperature, pressure))
+}
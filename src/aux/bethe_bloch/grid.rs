@@ -0,0 +1,94 @@
+// This module provides the kinetic energy grid the stopping power is evaluated on
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Spacing {
+    Linear,
+    Logarithmic,
+}
+
+impl fmt::Display for Spacing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Spacing::Linear => write!(f, "linear"),
+            Spacing::Logarithmic => write!(f, "log"),
+        }
+    }
+}
+
+impl FromStr for Spacing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "linear" | "lin" => Ok(Spacing::Linear),
+            "logarithmic" | "log" => Ok(Spacing::Logarithmic),
+            _ => Err(format!("Unknown spacing '{}' (expected linear or log)", s)),
+        }
+    }
+}
+
+// Kinetic energies (MeV) from `min` to `max` inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnergyGrid {
+    pub min: f64,
+    pub max: f64,
+    pub points: usize,
+    pub spacing: Spacing,
+}
+
+impl EnergyGrid {
+    pub fn linear(min: f64, max: f64, points: usize) -> Self {
+        EnergyGrid { min, max, points, spacing: Spacing::Linear }
+    }
+
+    pub fn logarithmic(min: f64, max: f64, points: usize) -> Self {
+        EnergyGrid { min, max, points, spacing: Spacing::Logarithmic }
+    }
+
+    // Refuse a grid whose points would not be increasing positive energies: both ends must be
+    // positive, as the logarithmic spacing and the stopping power need, and there must be at
+    // least two points from the lower end to a higher one
+    pub fn check(&self) -> Result<(), String> {
+        let positive = |energy: f64| energy.is_finite() && energy > 0.0;
+        if !positive(self.min) || !positive(self.max) {
+            return Err(format!("the energies of the grid must be positive, not {} to {} MeV", self.min, self.max));
+        }
+        if self.min >= self.max {
+            return Err(format!("the lowest energy of the grid ({} MeV) must be below the highest ({} MeV)", self.min, self.max));
+        }
+        if self.points < 2 {
+            return Err(format!("the grid needs at least 2 points, not {}", self.points));
+        }
+        Ok(())
+    }
+
+    // Energies of the grid points (MeV)
+    pub fn energies(&self) -> Vec<f64> {
+        if self.points <= 1 {
+            return vec![self.min; self.points];
+        }
+
+        let steps = (self.points - 1) as f64;
+        (0..self.points)
+            .map(|i| {
+                let t = i as f64 / steps;
+                match self.spacing {
+                    Spacing::Linear => self.min + t * (self.max - self.min),
+                    Spacing::Logarithmic => self.min * (self.max / self.min).powf(t),
+                }
+            })
+            .collect()
+    }
+}
+
+// 10 MeV to 10 GeV in 10 MeV steps
+impl Default for EnergyGrid {
+    fn default() -> Self {
+        EnergyGrid::linear(10.0, 10000.0, 1000)
+    }
+}
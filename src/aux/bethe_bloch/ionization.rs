@@ -0,0 +1,47 @@
+// This module converts deposited energy into ionization: the number of ion pairs
+// (electron-hole pairs in semiconductors) and the charge collected by a detector
+use serde::{Deserialize, Serialize};
+
+use super::bb::{stopping_power, Corrections};
+use super::slab::{energy_after_slab, SlabOutcome};
+use super::{Material, Projectile};
+
+// Elementary charge (C)
+const ELEMENTARY_CHARGE: f64 = 1.602176634e-19;
+
+// Ionization produced by a projectile crossing a detector layer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TrackIonization {
+    // Energy deposited in the layer (MeV)
+    pub deposited: f64,
+    // Ion pairs created, N = ΔE / W
+    pub ion_pairs: f64,
+    // Charge of either sign liberated (C)
+    pub charge: f64,
+    // Whether the projectile stopped inside the layer
+    pub stopped: bool,
+}
+
+// Ion pairs per cm, dE/dx / W, of a projectile with kinetic energy `energy` (MeV).
+// None if the material has no W-value.
+pub fn ion_pairs_per_cm(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections) -> Option<f64> {
+    let w_value = material.w_value?;
+    Some(stopping_power(energy, projectile, material, corrections) * 1e6 / w_value)
+}
+
+// Ionization along a track through `thickness` cm of `material`, stepping the projectile
+// so the energy loss follows the slowing down. None if the material has no W-value.
+pub fn track_ionization(energy: f64, projectile: &Projectile, material: &Material, thickness: f64, corrections: &Corrections) -> Option<TrackIonization> {
+    let w_value = material.w_value?;
+
+    let outcome = energy_after_slab(projectile, material, thickness, energy, corrections);
+    let deposited = energy - outcome.exit_energy();
+    let ion_pairs = deposited * 1e6 / w_value;
+
+    Some(TrackIonization {
+        deposited,
+        ion_pairs,
+        charge: ion_pairs * ELEMENTARY_CHARGE,
+        stopped: matches!(outcome, SlabOutcome::Stops(_)),
+    })
+}
@@ -0,0 +1,53 @@
+// This module converts between the kinematic variables of a particle of rest mass `mass`
+// (MeV/c²): kinetic energy T and total energy E (MeV), momentum p (MeV/c), β, γ and βγ
+
+// Lorentz factor γ = 1 + T/M
+pub fn gamma(kinetic: f64, mass: f64) -> f64 {
+    1.0 + kinetic / mass
+}
+
+// β² = 1 - 1/γ²
+pub fn beta_squared(kinetic: f64, mass: f64) -> f64 {
+    let gamma = gamma(kinetic, mass);
+    1.0 - 1.0 / (gamma * gamma)
+}
+
+// β = sqrt(T(T + 2M)) / (T + M)
+pub fn beta(kinetic: f64, mass: f64) -> f64 {
+    (kinetic * (kinetic + 2.0 * mass)).sqrt() / (kinetic + mass)
+}
+
+// βγ = p/M
+pub fn beta_gamma(kinetic: f64, mass: f64) -> f64 {
+    momentum(kinetic, mass) / mass
+}
+
+// Total energy E = T + M
+pub fn total_energy(kinetic: f64, mass: f64) -> f64 {
+    kinetic + mass
+}
+
+// Momentum p = sqrt(T(T + 2M))
+pub fn momentum(kinetic: f64, mass: f64) -> f64 {
+    (kinetic * (kinetic + 2.0 * mass)).sqrt()
+}
+
+// Kinetic energy from the momentum, T = sqrt(p² + M²) - M
+pub fn kinetic_from_momentum(momentum: f64, mass: f64) -> f64 {
+    momentum.hypot(mass) - mass
+}
+
+// Kinetic energy from βγ
+pub fn kinetic_from_beta_gamma(beta_gamma: f64, mass: f64) -> f64 {
+    kinetic_from_momentum(beta_gamma * mass, mass)
+}
+
+// Kinetic energy from β (0 ≤ β < 1)
+pub fn kinetic_from_beta(beta: f64, mass: f64) -> f64 {
+    kinetic_from_gamma(1.0 / (1.0 - beta * beta).sqrt(), mass)
+}
+
+// Kinetic energy from γ
+pub fn kinetic_from_gamma(gamma: f64, mass: f64) -> f64 {
+    (gamma - 1.0) * mass
+}
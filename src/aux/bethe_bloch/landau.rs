@@ -0,0 +1,288 @@
+// This module describes the energy-loss distribution in thin absorbers (Landau,
+// Vavilov or Gaussian depending on κ = ξ / Tmax) with sampling support
+use std::f64::consts::PI;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::bb::{max_energy_transfer, stopping_power};
+use super::kinematics;
+use super::straggling::bohr_variance;
+use super::{Corrections, Material, Projectile};
+
+// K/2 = 2π N_A r_e² m_e c² (MeV cm²/mol)
+const HALF_K: f64 = 0.1535375;
+const ELECTRON_MASS: f64 = 0.51099895;
+const EULER_GAMMA: f64 = 0.5772156649015329;
+
+// Location of the maximum of the Landau density φ(λ)
+const LANDAU_PEAK: f64 = -0.22278;
+
+// Number of points used to tabulate the density for sampling
+const TABLE_POINTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LossModel {
+    // κ < 0.01
+    Landau,
+    // 0.01 <= κ <= 10
+    Vavilov,
+    // κ > 10
+    Gaussian,
+}
+
+// Energy lost by a projectile of kinetic energy `energy` (MeV) crossing `thickness` cm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnergyLossDistribution {
+    pub model: LossModel,
+    // Landau scale parameter ξ (MeV)
+    pub xi: f64,
+    // ξ / Tmax
+    pub kappa: f64,
+    pub beta2: f64,
+    // Mean (dE/dx · thickness) and most probable energy loss (MeV)
+    pub mean: f64,
+    pub most_probable: f64,
+    // Variance of the Gaussian limit (MeV²)
+    pub variance: f64,
+    // Tabulated energy losses (MeV), densities (1/MeV) and cumulative probabilities
+    losses: Vec<f64>,
+    densities: Vec<f64>,
+    cdf: Vec<f64>,
+    // Precomputed amplitude and phase of the Vavilov integrand on a y grid
+    vavilov: Option<VavilovIntegrand>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct VavilovIntegrand {
+    step: f64,
+    amplitude: Vec<f64>,
+    phase: Vec<f64>,
+}
+
+impl EnergyLossDistribution {
+    pub fn new(energy: f64, projectile: &Projectile, material: &Material, thickness: f64) -> Self {
+        let beta2 = kinematics::beta_squared(energy, projectile.mass);
+        let params = material.density_correction_params();
+
+        let xi = landau_xi(energy, projectile, material, thickness);
+        let tmax = max_energy_transfer(energy, projectile);
+        let kappa = xi / tmax;
+        let mean = stopping_power(energy, projectile, material, &Corrections::all(&params)) * thickness;
+        let variance = bohr_variance(energy, projectile, material) * thickness;
+
+        let model = if kappa < 0.01 {
+            LossModel::Landau
+        } else if kappa <= 10.0 {
+            LossModel::Vavilov
+        } else {
+            LossModel::Gaussian
+        };
+
+        let mut distribution = EnergyLossDistribution {
+            model,
+            xi,
+            kappa,
+            beta2,
+            mean,
+            most_probable: most_probable_energy_loss(energy, projectile, material, thickness),
+            variance,
+            losses: Vec::new(),
+            densities: Vec::new(),
+            cdf: Vec::new(),
+            vavilov: None,
+        };
+
+        if model == LossModel::Vavilov {
+            distribution.vavilov = Some(VavilovIntegrand::new(kappa, beta2));
+        }
+
+        // Tabulation range of the energy loss
+        let sigma = variance.sqrt();
+        let (lower, upper) = match model {
+            LossModel::Landau => (distribution.most_probable - 5.0 * xi, distribution.most_probable + 100.0 * xi),
+            LossModel::Vavilov => (distribution.most_probable.min(mean) - 5.0 * xi, mean + 6.0 * sigma),
+            LossModel::Gaussian => (mean - 6.0 * sigma, mean + 6.0 * sigma),
+        };
+        distribution.tabulate(lower.max(0.0), upper);
+
+        // The Landau formula for the peak is only asymptotic for the other models
+        if model != LossModel::Landau {
+            if let Some((index, _)) = distribution
+                .densities
+                .iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(b.1))
+            {
+                distribution.most_probable = distribution.losses[index];
+            }
+        }
+
+        distribution
+    }
+
+    // Probability density (1/MeV) of losing `loss` MeV
+    pub fn density(&self, loss: f64) -> f64 {
+        match self.model {
+            LossModel::Landau => {
+                landau_density((loss - self.most_probable) / self.xi + LANDAU_PEAK) / self.xi
+            }
+            LossModel::Vavilov => match &self.vavilov {
+                Some(integrand) => integrand.density((loss - self.mean) / self.xi, self.kappa) / self.xi,
+                None => 0.0,
+            },
+            LossModel::Gaussian => {
+                let z = loss - self.mean;
+                (-z * z / (2.0 * self.variance)).exp() / (2.0 * PI * self.variance).sqrt()
+            }
+        }
+    }
+
+    // Tabulated (loss, density) pairs
+    pub fn table(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.losses.iter().copied().zip(self.densities.iter().copied())
+    }
+
+    // Draw an energy loss (MeV) by inverting the tabulated cumulative distribution
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.gen();
+        let index = self.cdf.partition_point(|&c| c < u).clamp(1, self.cdf.len() - 1);
+        let (c0, c1) = (self.cdf[index - 1], self.cdf[index]);
+        let (l0, l1) = (self.losses[index - 1], self.losses[index]);
+        if c1 > c0 {
+            l0 + (u - c0) / (c1 - c0) * (l1 - l0)
+        } else {
+            l1
+        }
+    }
+
+    fn tabulate(&mut self, lower: f64, upper: f64) {
+        let step = (upper - lower) / (TABLE_POINTS - 1) as f64;
+        self.losses = (0..TABLE_POINTS).map(|i| lower + i as f64 * step).collect();
+        self.densities = self.losses.iter().map(|&loss| self.density(loss).max(0.0)).collect();
+
+        // Trapezoidal cumulative sum, normalised over the tabulated range
+        let mut cdf = Vec::with_capacity(TABLE_POINTS);
+        let mut total = 0.0;
+        cdf.push(0.0);
+        for pair in self.densities.windows(2) {
+            total += 0.5 * (pair[0] + pair[1]) * step;
+            cdf.push(total);
+        }
+        if total > 0.0 {
+            cdf.iter_mut().for_each(|c| *c /= total);
+        }
+        self.cdf = cdf;
+    }
+}
+
+impl VavilovIntegrand {
+    // f(Δ) = 1/(π ξ) κ ∫ exp(-κ A(y)) cos(κ y (Δ - Δ̄)/ξ - κ B(y)) dy with
+    // A(y) = y Si(y) - 1 + cos(y) - β² Cin(y)
+    // B(y) = y - sin(y) - y Cin(y) - β² (Si(y) - y)
+    fn new(kappa: f64, beta2: f64) -> Self {
+        let step = 0.05;
+        let y_max = 80.0 / (kappa * PI) + 20.0;
+        let n = (y_max / step) as usize + 1;
+
+        let mut amplitude = Vec::with_capacity(n);
+        let mut phase = Vec::with_capacity(n);
+        for i in 0..n {
+            let y = i as f64 * step;
+            let (si, cin) = (sine_integral(y), cosine_integral_cin(y));
+            amplitude.push((-kappa * (y * si - 1.0 + y.cos() - beta2 * cin)).exp());
+            phase.push(kappa * (y - y.sin() - y * cin - beta2 * (si - y)));
+        }
+
+        VavilovIntegrand { step, amplitude, phase }
+    }
+
+    // Density per unit x = (Δ - Δ̄)/ξ
+    fn density(&self, x: f64, kappa: f64) -> f64 {
+        let mut sum = 0.0;
+        for (i, (amplitude, phase)) in self.amplitude.iter().zip(&self.phase).enumerate() {
+            let y = i as f64 * self.step;
+            let weight = if i == 0 { 0.5 } else { 1.0 };
+            sum += weight * amplitude * (kappa * y * x - phase).cos();
+        }
+        kappa * sum * self.step / PI
+    }
+}
+
+// Landau scale parameter ξ = (K/2) z² (Z/A) ρ x / β² (MeV)
+pub fn landau_xi(energy: f64, projectile: &Projectile, material: &Material, thickness: f64) -> f64 {
+    let beta2 = kinematics::beta_squared(energy, projectile.mass);
+
+    HALF_K * projectile.charge.powi(2) * (material.z / material.a) * material.density * thickness / beta2
+}
+
+// Most probable energy loss (MeV) in `thickness` cm
+// Δp = ξ [ln(2mc²β²γ²/I) + ln(ξ/I) + j - β² - δ(βγ)] with j = 0.200
+pub fn most_probable_energy_loss(energy: f64, projectile: &Projectile, material: &Material, thickness: f64) -> f64 {
+    let bg2 = kinematics::beta_gamma(energy, projectile.mass).powi(2);
+    let beta2 = kinematics::beta_squared(energy, projectile.mass);
+    let xi = landau_xi(energy, projectile, material, thickness);
+    let i = material.mean_excitation_energy * 1e-6;
+    let delta = material.density_correction_params().delta(bg2.sqrt());
+
+    xi * ((2.0 * ELECTRON_MASS * bg2 / i).ln() + (xi / i).ln() + 0.200 - beta2 - delta)
+}
+
+// Landau density φ(λ) = 1/π ∫ exp(-πt/2) cos(t ln t + λ t) dt, which unlike the
+// sin(πt) form does not suffer from cancellations at negative λ
+pub fn landau_density(lambda: f64) -> f64 {
+    let step = 0.02 / (1.0 + lambda.abs() / 5.0);
+    let t_max = 25.0;
+    let n = (t_max / step) as usize;
+
+    let mut sum = 0.5;
+    for i in 1..=n {
+        let t = i as f64 * step;
+        sum += (-PI * t / 2.0).exp() * (t * t.ln() + lambda * t).cos();
+    }
+    sum * step / PI
+}
+
+// Sine integral Si(x)
+fn sine_integral(x: f64) -> f64 {
+    if x <= 4.0 {
+        let mut term = x;
+        let mut sum = x;
+        for n in 1..40 {
+            let k = (2 * n) as f64;
+            term *= -x * x / (k * (k + 1.0));
+            sum += term / (k + 1.0);
+        }
+        sum
+    } else {
+        let (f, g) = auxiliary_fg(x);
+        PI / 2.0 - f * x.cos() - g * x.sin()
+    }
+}
+
+// Cin(x) = γ + ln x - Ci(x) = ∫ (1 - cos t)/t dt from 0 to x
+fn cosine_integral_cin(x: f64) -> f64 {
+    if x <= 4.0 {
+        let mut term = 1.0;
+        let mut sum = 0.0;
+        for n in 1..40 {
+            let k = (2 * n) as f64;
+            term *= -x * x / ((k - 1.0) * k);
+            sum -= term / k;
+        }
+        sum
+    } else {
+        let (f, g) = auxiliary_fg(x);
+        let ci = f * x.sin() - g * x.cos();
+        EULER_GAMMA + x.ln() - ci
+    }
+}
+
+// Rational approximations of the auxiliary functions f(x), g(x) (Abramowitz & Stegun 5.2.38-39)
+fn auxiliary_fg(x: f64) -> (f64, f64) {
+    let x2 = x * x;
+    let f = (x2 * x2 + 7.241163 * x2 + 2.463936) / (x * (x2 * x2 + 9.068580 * x2 + 7.157433));
+    let g = (x2 * x2 + 7.547478 * x2 + 1.564072) / (x2 * (x2 * x2 + 12.723684 * x2 + 15.723606));
+    (f, g)
+}
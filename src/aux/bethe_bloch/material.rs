@@ -0,0 +1,123 @@
+// This module describes the target material the projectile slows down in
+use serde::{Deserialize, Serialize};
+
+use super::DensityCorrectionParams;
+
+// Avogadro's number (1/mol)
+pub(crate) const AVOGADRO: f64 = 6.02214076e23;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Material {
+    pub name: String,
+    // Atomic number (Z) and atomic mass (A, g/mol)
+    pub z: f64,
+    pub a: f64,
+    // Density (g/cm³)
+    pub density: f64,
+    // Mean excitation energy I (eV)
+    pub mean_excitation_energy: f64,
+    // Electron density (electrons/cm³)
+    pub electron_density: f64,
+    // Tabulated Sternheimer density-effect coefficients, if known
+    pub sternheimer: Option<DensityCorrectionParams>,
+    // Tabulated radiation length X0 (g/cm²), if known
+    pub radiation_length: Option<f64>,
+    // Tabulated nuclear interaction length λI (g/cm²), if known
+    pub nuclear_interaction_length: Option<f64>,
+    // Refractive index n for visible light, if the material is transparent
+    pub refractive_index: Option<f64>,
+    // Mean energy W (eV) spent per ion pair (electron-hole pair in semiconductors), if known
+    pub w_value: Option<f64>,
+    // Tabulated (βγ, C/Z) shell corrections used by `ShellCorrectionModel::Tabulated`, if known
+    pub shell_corrections: Option<Vec<(f64, f64)>>,
+}
+
+impl Material {
+    // Build a material deriving the electron density from Z, A and the density. Compounds and
+    // mixtures are described by their mean atom: Z and A are those of the molecule (or of the
+    // mixture by mass) divided by its number of atoms, e.g. Z = 10/3 and A = 18.0153/3 for
+    // water, which keeps Z/A and gives the shell, radiative and λI terms a per-atom Z.
+    pub fn new(name: &str, z: f64, a: f64, density: f64, mean_excitation_energy: f64) -> Self {
+        Material {
+            name: String::from(name),
+            z,
+            a,
+            density,
+            mean_excitation_energy,
+            electron_density: density * AVOGADRO * z / a,
+            sternheimer: None,
+            radiation_length: None,
+            nuclear_interaction_length: None,
+            refractive_index: None,
+            w_value: None,
+            shell_corrections: None,
+        }
+    }
+
+    pub fn with_sternheimer(mut self, params: DensityCorrectionParams) -> Self {
+        self.sternheimer = Some(params);
+        self
+    }
+
+    pub fn with_radiation_length(mut self, radiation_length: f64) -> Self {
+        self.radiation_length = Some(radiation_length);
+        self
+    }
+
+    pub fn with_nuclear_interaction_length(mut self, nuclear_interaction_length: f64) -> Self {
+        self.nuclear_interaction_length = Some(nuclear_interaction_length);
+        self
+    }
+
+    pub fn with_refractive_index(mut self, refractive_index: f64) -> Self {
+        self.refractive_index = Some(refractive_index);
+        self
+    }
+
+    pub fn with_w_value(mut self, w_value: f64) -> Self {
+        self.w_value = Some(w_value);
+        self
+    }
+
+    pub fn with_shell_corrections(mut self, table: Vec<(f64, f64)>) -> Self {
+        self.shell_corrections = Some(table);
+        self
+    }
+
+    // Tabulated radiation length (g/cm²), or Tsai's estimate when the material has none
+    pub fn radiation_length(&self) -> f64 {
+        self.radiation_length.unwrap_or_else(|| super::radiative::tsai_radiation_length(self))
+    }
+
+    // Radiation length (cm)
+    pub fn radiation_length_cm(&self) -> f64 {
+        self.radiation_length() / self.density
+    }
+
+    // Tabulated nuclear interaction length (g/cm²), or the estimate λI ≈ 35 A^(1/3) g/cm²
+    // for high-energy hadrons, which underestimates λI of light compounds described by a mean atom
+    pub fn nuclear_interaction_length(&self) -> f64 {
+        self.nuclear_interaction_length.unwrap_or_else(|| 35.0 * self.a.cbrt())
+    }
+
+    // Nuclear interaction length (cm)
+    pub fn nuclear_interaction_length_cm(&self) -> f64 {
+        self.nuclear_interaction_length() / self.density
+    }
+
+    // Plasma energy ħωp = 28.816 sqrt(ρ <Z/A>) (eV)
+    pub fn plasma_energy(&self) -> f64 {
+        28.816 * (self.density * self.z / self.a).sqrt()
+    }
+
+    // Tabulated Sternheimer coefficients, or coefficients derived from the
+    // plasma energy and I when the material has none
+    pub fn density_correction_params(&self) -> DensityCorrectionParams {
+        self.sternheimer.unwrap_or_else(|| DensityCorrectionParams::from_material(self))
+    }
+
+    // Liquid water (H2O)
+    pub fn water() -> Self {
+        super::materials::water()
+    }
+}
@@ -0,0 +1,135 @@
+// This module provides built-in target materials with their ICRU-recommended
+// mean excitation energies, densities and Sternheimer density-effect coefficients,
+// radiation and nuclear interaction lengths (PDG), the visible-light refractive
+// index of the transparent ones and the W-value of detector media
+use super::{DensityCorrectionParams, Material};
+
+// Names accepted by `by_name`
+pub const NAMES: [&str; 12] = [
+    "water", "air", "aluminium", "copper", "lead", "silicon", "pmma", "bone", "soft_tissue", "mylar", "argon",
+    "scintillator",
+];
+
+// Look up a preset by name (case insensitive)
+pub fn by_name(name: &str) -> Option<Material> {
+    match name.to_ascii_lowercase().as_str() {
+        "water" => Some(water()),
+        "air" => Some(air()),
+        "aluminium" | "aluminum" => Some(aluminium()),
+        "copper" => Some(copper()),
+        "lead" => Some(lead()),
+        "silicon" => Some(silicon()),
+        "pmma" => Some(pmma()),
+        "bone" => Some(bone()),
+        "soft_tissue" | "tissue" => Some(soft_tissue()),
+        "mylar" => Some(mylar()),
+        "argon" => Some(argon()),
+        "scintillator" | "pvt" => Some(scintillator()),
+        _ => None,
+    }
+}
+
+// All presets, in the order of `NAMES`
+pub fn all() -> Vec<Material> {
+    NAMES.iter().filter_map(|name| by_name(name)).collect()
+}
+
+// Helper to build the Sternheimer coefficients in table order (-C, x0, x1, a, m, δ0)
+fn sternheimer(c: f64, x0: f64, x1: f64, a: f64, m: f64, delta0: f64) -> DensityCorrectionParams {
+    DensityCorrectionParams { a, x0, x1, c, m, delta0 }
+}
+
+// Liquid water (H2O, mean atom)
+pub fn water() -> Material {
+    Material::new("water", 10.0 / 3.0, 18.0153 / 3.0, 1.0, 75.0)
+        .with_sternheimer(sternheimer(3.5017, 0.2400, 2.8004, 0.09116, 3.4773, 0.0))
+        .with_radiation_length(36.08)
+        .with_nuclear_interaction_length(83.3)
+        .with_refractive_index(1.333)
+}
+
+// Dry air near sea level (mean atom)
+pub fn air() -> Material {
+    Material::new("air", 7.32, 14.664, 1.20479e-3, 85.7)
+        .with_sternheimer(sternheimer(10.5961, 1.7418, 4.2759, 0.10914, 3.3994, 0.0))
+        .with_radiation_length(36.62)
+        .with_nuclear_interaction_length(90.1)
+        .with_refractive_index(1.000293)
+        .with_w_value(33.97)
+}
+
+pub fn aluminium() -> Material {
+    Material::new("aluminium", 13.0, 26.9815, 2.699, 166.0)
+        .with_sternheimer(sternheimer(4.2395, 0.1708, 3.0127, 0.08024, 3.6345, 0.12))
+        .with_radiation_length(24.01)
+        .with_nuclear_interaction_length(107.2)
+}
+
+pub fn copper() -> Material {
+    Material::new("copper", 29.0, 63.546, 8.96, 322.0)
+        .with_sternheimer(sternheimer(4.4190, -0.0254, 3.2792, 0.14339, 2.9044, 0.08))
+        .with_radiation_length(12.86)
+        .with_nuclear_interaction_length(137.3)
+}
+
+pub fn lead() -> Material {
+    Material::new("lead", 82.0, 207.2, 11.35, 823.0)
+        .with_sternheimer(sternheimer(6.2018, 0.3776, 3.8073, 0.09359, 3.1608, 0.14))
+        .with_radiation_length(6.37)
+        .with_nuclear_interaction_length(199.6)
+}
+
+pub fn silicon() -> Material {
+    Material::new("silicon", 14.0, 28.0855, 2.329, 173.0)
+        .with_sternheimer(sternheimer(4.4355, 0.2015, 2.8716, 0.14921, 3.2546, 0.14))
+        .with_radiation_length(21.82)
+        .with_nuclear_interaction_length(108.4)
+        .with_w_value(3.62)
+}
+
+// Polymethyl methacrylate (C5H8O2, mean atom)
+pub fn pmma() -> Material {
+    Material::new("pmma", 3.6, 6.6744, 1.19, 74.0)
+        .with_sternheimer(sternheimer(3.3297, 0.1824, 2.6681, 0.11433, 3.3836, 0.0))
+        .with_radiation_length(40.55)
+        .with_refractive_index(1.49)
+}
+
+// Compact bone (ICRU, mean atom)
+pub fn bone() -> Material {
+    Material::new("bone", 4.415, 8.3286, 1.85, 91.9)
+        .with_sternheimer(sternheimer(3.3390, 0.0944, 3.0201, 0.05822, 3.6419, 0.0))
+}
+
+// Soft tissue (ICRP, mean atom)
+pub fn soft_tissue() -> Material {
+    Material::new("soft_tissue", 3.357, 6.0903, 1.03, 72.3)
+        .with_sternheimer(sternheimer(3.4354, 0.2211, 2.7799, 0.08926, 3.5110, 0.0))
+}
+
+// Polyethylene terephthalate window foil (C10H8O4, mean atom)
+pub fn mylar() -> Material {
+    Material::new("mylar", 4.5455, 8.7350, 1.40, 78.7)
+        .with_sternheimer(sternheimer(3.3262, 0.1562, 2.6507, 0.12679, 3.3076, 0.0))
+        .with_radiation_length(39.95)
+        .with_refractive_index(1.575)
+}
+
+// Argon gas at 20 °C and 1 atm, the usual drift chamber base gas
+pub fn argon() -> Material {
+    Material::new("argon", 18.0, 39.948, 1.662e-3, 188.0)
+        .with_sternheimer(sternheimer(11.9480, 1.7635, 4.4855, 0.19714, 2.9618, 0.0))
+        .with_radiation_length(19.55)
+        .with_nuclear_interaction_length(119.7)
+        .with_refractive_index(1.000281)
+        .with_w_value(26.4)
+}
+
+// Polyvinyltoluene plastic scintillator (C9H10, mean atom)
+pub fn scintillator() -> Material {
+    Material::new("scintillator", 64.0 / 19.0, 118.18 / 19.0, 1.032, 64.7)
+        .with_sternheimer(sternheimer(3.1997, 0.1464, 2.4855, 0.16101, 3.2393, 0.0))
+        .with_radiation_length(43.90)
+        .with_nuclear_interaction_length(81.3)
+        .with_refractive_index(1.58)
+}
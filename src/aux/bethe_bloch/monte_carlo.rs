@@ -0,0 +1,159 @@
+// This module provides a simple one-dimensional Monte Carlo transport: each history is
+// stepped through the target with the energy loss of every step sampled from a Gaussian
+// of the Bohr straggling width around the mean loss
+use std::f64::consts::PI;
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use rand::Rng;
+
+use super::bb::{stopping_power, Corrections};
+use super::range::cutoff;
+use super::straggling::bohr_variance;
+use super::{BraggCurve, Material, Projectile};
+
+// Counts in bins of equal width starting at zero depth
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    // Bin width (cm)
+    pub width: f64,
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    pub fn new(width: f64) -> Self {
+        Histogram { width, counts: Vec::new() }
+    }
+
+    pub fn fill(&mut self, x: f64) {
+        let bin = (x / self.width).max(0.0) as usize;
+        if bin >= self.counts.len() {
+            self.counts.resize(bin + 1, 0);
+        }
+        self.counts[bin] += 1;
+    }
+
+    // Iterate over (bin centre, count) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, u64)> + '_ {
+        self.counts.iter().enumerate().map(move |(i, &count)| ((i as f64 + 0.5) * self.width, count))
+    }
+
+    // Write the histogram as delimited "centre count" lines
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            for (centre, count) in self.iter() {
+                writeln!(file, "{}", text.join(&[text.fixed(centre, 4), count.to_string()]))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// Result of a Monte Carlo run
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonteCarlo {
+    pub histories: usize,
+    // Mean deposited energy per unit depth (MeV/cm) per history
+    pub dose: BraggCurve,
+    // Depths (cm) at which the histories stopped
+    pub stopping: Histogram,
+}
+
+impl MonteCarlo {
+    // Mean and standard deviation (cm) of the stopping depth
+    pub fn stopping_depth(&self) -> (f64, f64) {
+        let n = self.stopping.counts.iter().sum::<u64>() as f64;
+        if n == 0.0 {
+            return (0.0, 0.0);
+        }
+        let mean = self.stopping.iter().map(|(x, c)| x * c as f64).sum::<f64>() / n;
+        let variance = self.stopping.iter().map(|(x, c)| (x - mean).powi(2) * c as f64).sum::<f64>() / n;
+        (mean, variance.sqrt())
+    }
+
+    // Add the histories of another run with the same beam and step, e.g. to run in batches
+    pub fn merge(&mut self, other: &MonteCarlo) {
+        let histories = self.histories + other.histories;
+        if histories == 0 {
+            return;
+        }
+
+        // Mean dose per history weighted by the histories of each run
+        let (weight, other_weight) = (self.histories as f64 / histories as f64, other.histories as f64 / histories as f64);
+        let mut dose = BraggCurve::default();
+        for bin in 0..self.dose.len().max(other.dose.len()) {
+            let depth = self.dose.depths.get(bin).or(other.dose.depths.get(bin)).copied().unwrap_or_default();
+            let value = weight * self.dose.doses.get(bin).copied().unwrap_or_default()
+                + other_weight * other.dose.doses.get(bin).copied().unwrap_or_default();
+            dose.push(depth, value);
+        }
+
+        if self.stopping.counts.len() < other.stopping.counts.len() {
+            self.stopping.counts.resize(other.stopping.counts.len(), 0);
+        }
+        for (count, other) in self.stopping.counts.iter_mut().zip(&other.stopping.counts) {
+            *count += other;
+        }
+        if self.stopping.width == 0.0 {
+            self.stopping.width = other.stopping.width;
+        }
+
+        self.dose = dose;
+        self.histories = histories;
+    }
+}
+
+// Track `histories` projectiles of initial kinetic energy `energy` (MeV) in steps of `step` cm
+pub fn monte_carlo<R: Rng + ?Sized>(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections,
+    step: f64, histories: usize, rng: &mut R) -> MonteCarlo {
+    let cutoff = cutoff(projectile);
+    let mut deposited: Vec<f64> = Vec::new();
+    let mut stopping = Histogram::new(step);
+
+    for _ in 0..histories {
+        let mut energy = energy;
+        let mut bin = 0;
+
+        loop {
+            // Midpoint mean loss and Bohr width over the step
+            let de_start = stopping_power(energy, projectile, material, corrections) * step;
+            let mean = stopping_power(energy - de_start / 2.0, projectile, material, corrections) * step;
+            let sigma = (bohr_variance(energy, projectile, material) * step).sqrt();
+            let de = (mean + sigma * standard_normal(rng)).max(0.0);
+
+            if bin >= deposited.len() {
+                deposited.resize(bin + 1, 0.0);
+            }
+
+            // Last step: the remaining energy is deposited locally
+            if !mean.is_finite() || mean <= 0.0 || de_start >= energy - cutoff || de >= energy - cutoff {
+                deposited[bin] += energy;
+                stopping.fill((bin as f64 + 0.5) * step);
+                break;
+            }
+
+            deposited[bin] += de;
+            energy -= de;
+            bin += 1;
+        }
+    }
+
+    let mut dose = BraggCurve::default();
+    for (bin, total) in deposited.iter().enumerate() {
+        dose.push((bin as f64 + 0.5) * step, total / (histories.max(1) as f64 * step));
+    }
+
+    MonteCarlo { histories, dose, stopping }
+}
+
+// Box-Muller transform
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
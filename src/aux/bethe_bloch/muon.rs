@@ -0,0 +1,161 @@
+// This module provides the radiative energy losses of muons (bremsstrahlung, direct e+e-
+// pair production and photonuclear interactions), which dominate above a few hundred GeV.
+// Each process is written as b(E) (cm²/g) with -dE/dx = b(E) E ρ, following Groom et al.
+use std::f64::consts::{E, PI};
+
+use super::electron::ELECTRON_MASS;
+use super::material::AVOGADRO;
+use super::radiative::tsai_radiation_length;
+use super::{Material, Projectile};
+
+const FINE_STRUCTURE: f64 = 7.2973525693e-3;
+// Classical electron radius (cm)
+const ELECTRON_RADIUS: f64 = 2.8179403262e-13;
+// Screening constant B of the Thomas-Fermi atom
+const SCREENING: f64 = 183.0;
+
+// Simpson intervals used by the integrals over the energy fraction (must be even)
+const INTEGRATION_STEPS: usize = 200;
+
+// Radiative stopping power (MeV/cm) of a projectile with kinetic energy `energy` (MeV):
+// the sum of the bremsstrahlung, pair production and photonuclear losses
+pub fn radiative_stopping_power(energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let b = bremsstrahlung_b(energy, projectile, material)
+        + pair_production_b(energy, projectile, material)
+        + photonuclear_b(energy, projectile, material);
+
+    b * (energy + projectile.mass) * material.density
+}
+
+// Bremsstrahlung b (cm²/g), Petrukhin-Shestakov cross section with the nuclear size correction
+pub fn bremsstrahlung_b(energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let mass = projectile.mass;
+    let total = energy + mass;
+    let z = material.z;
+    let z13 = z.powf(1.0 / 3.0);
+    let v_max = 1.0 - 0.75 * E.sqrt() * mass / total * z13;
+    if v_max <= 0.0 {
+        return 0.0;
+    }
+
+    let d_n = 1.54 * material.a.powf(0.27);
+    let d_n_prime = d_n.powf(1.0 - 1.0 / z.max(1.0));
+    let prefactor = FINE_STRUCTURE * (2.0 * ELECTRON_RADIUS * ELECTRON_MASS / mass).powi(2) * z * (z + 1.0);
+
+    // v dσ/dv
+    let integrand = |v: f64| {
+        let delta = mass * mass * v / (2.0 * total * (1.0 - v));
+        let phi = (SCREENING / z13 * (mass + delta * (d_n_prime * E.sqrt() - 2.0))
+            / (d_n_prime * (ELECTRON_MASS + delta * E.sqrt() * SCREENING / z13)))
+            .ln()
+            .max(0.0);
+        prefactor * (4.0 / 3.0 - 4.0 / 3.0 * v + v * v) * phi
+    };
+
+    AVOGADRO / material.a * simpson(integrand, 0.0, v_max) * screening_scale(material)
+}
+
+// Direct e+e- pair production b (cm²/g), Kokoulin-Petrukhin cross section
+pub fn pair_production_b(energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let mass = projectile.mass;
+    let total = energy + mass;
+    let z = material.z;
+    let z13 = z.powf(1.0 / 3.0);
+    let (eps_min, eps_max) = (4.0 * ELECTRON_MASS, total - 0.75 * E.sqrt() * mass * z13);
+    if eps_max <= eps_min {
+        return 0.0;
+    }
+
+    let prefactor = 4.0 / (3.0 * PI) * z * (z + 1.0) * (FINE_STRUCTURE * ELECTRON_RADIUS).powi(2);
+    let mass_ratio = ELECTRON_MASS / mass;
+
+    // v dσ/dv = ε dσ/dε in the energy fraction v = ε/E, integrating the asymmetry ρ of the pair
+    let integrand = |v: f64| {
+        let rho_max = (1.0 - 6.0 * mass * mass / (total * total * (1.0 - v))) * (1.0 - 4.0 * ELECTRON_MASS / (total * v)).max(0.0).sqrt();
+        if rho_max <= 0.0 {
+            return 0.0;
+        }
+        let beta = v * v / (2.0 * (1.0 - v));
+        let rho_integrand = |rho: f64| {
+            let rho2 = rho * rho;
+            let xi = (mass * v / (2.0 * ELECTRON_MASS)).powi(2) * (1.0 - rho2) / (1.0 - v);
+
+            let y_e = (5.0 - rho2 + 4.0 * beta * (1.0 + rho2))
+                / (2.0 * (1.0 + 3.0 * beta) * (3.0 + 1.0 / xi).ln() - rho2 - 2.0 * beta * (2.0 - rho2));
+            let y_mu = (4.0 + rho2 + 3.0 * beta * (1.0 + rho2))
+                / ((1.0 + rho2) * (1.5 + 2.0 * beta) * (3.0 + xi).ln() + 1.0 - 1.5 * rho2);
+
+            let denominator = |y: f64| 1.0 + 2.0 * ELECTRON_MASS * E.sqrt() * SCREENING / z13 * (1.0 + xi) * (1.0 + y) / (total * v * (1.0 - rho2));
+            let l_e = (SCREENING / z13 * ((1.0 + xi) * (1.0 + y_e)).sqrt() / denominator(y_e)).ln()
+                - 0.5 * (1.0 + (1.5 * mass_ratio * z13).powi(2) * (1.0 + xi) * (1.0 + y_e)).ln();
+            let l_mu = (SCREENING / z13 / mass_ratio * ((1.0 + 1.0 / xi) * (1.0 + y_mu)).sqrt() / denominator(y_mu)).ln()
+                - (1.5 * z13 * ((1.0 + 1.0 / xi) * (1.0 + y_mu)).sqrt()).ln();
+
+            let phi_e = (((2.0 + rho2) * (1.0 + beta) + xi * (3.0 + rho2)) * (1.0 + 1.0 / xi).ln()
+                + (1.0 - rho2 - beta) / (1.0 + xi)
+                - (3.0 + rho2))
+                * l_e;
+            let phi_mu = (((1.0 + rho2) * (1.0 + 1.5 * beta) - (1.0 + 2.0 * beta) * (1.0 - rho2) / xi) * (1.0 + xi).ln()
+                + xi * (1.0 - rho2 - beta) / (1.0 + xi)
+                + (1.0 + 2.0 * beta) * (1.0 - rho2))
+                * l_mu;
+
+            let phi = phi_e.max(0.0) + mass_ratio * mass_ratio * phi_mu.max(0.0);
+            if phi.is_finite() { phi } else { 0.0 }
+        };
+        prefactor * (1.0 - v) * simpson(rho_integrand, 0.0, rho_max)
+    };
+
+    // The spectrum falls steeply with v, so integrate in ln(v)
+    let (u_min, u_max) = ((eps_min / total).ln(), (eps_max / total).ln());
+    AVOGADRO / material.a * simpson(|u: f64| integrand(u.exp()) * u.exp(), u_min, u_max) * screening_scale(material)
+}
+
+// Photonuclear b (cm²/g), Bezrukov-Bugaev cross section with the Caldwell
+// photon-nucleon cross section σ(ε) = 114.3 + 1.647 ln²(0.0213 ε/GeV) μb
+pub fn photonuclear_b(energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let mass = projectile.mass / 1000.0;
+    let total = (energy + projectile.mass) / 1000.0;
+    let (v_min, v_max) = (0.2 / total, 1.0 - mass / total);
+    if v_max <= v_min {
+        return 0.0;
+    }
+
+    let a = material.a;
+    let a_eff = if a > 1.0 { 0.22 * a + 0.78 * a.powf(0.89) } else { 1.0 };
+    let (m1, m2) = (0.54, 1.80);
+
+    // v dσ/dv
+    let integrand = |v: f64| {
+        let sigma = (114.3 + 1.647 * (0.0213 * v * total).ln().powi(2)) * 1e-30;
+        let x = 0.00282 * a.powf(1.0 / 3.0) * sigma * 1e30;
+        let g = 3.0 / x.powi(3) * (x * x / 2.0 - 1.0 + (-x).exp() * (1.0 + x));
+        let t = mass * mass * v * v / (1.0 - v);
+        let kappa = 1.0 - 2.0 / v + 2.0 / (v * v);
+
+        let bracket = 0.75 * g * (kappa * (1.0 + m1 / t).ln() - kappa * m1 / (m1 + t) - 2.0 * mass * mass / t)
+            + 0.25 * (kappa * (1.0 + m2 / t).ln() - 2.0 * mass * mass / t)
+            + mass * mass / (2.0 * t) * (0.75 * g * m1 / (m1 + t) + 0.25 * m2 / t * (1.0 + t / m2).ln());
+
+        FINE_STRUCTURE / (2.0 * PI) * a_eff * sigma * v * v * bracket.max(0.0)
+    };
+
+    AVOGADRO / a * simpson(|u: f64| integrand(u.exp()) * u.exp(), v_min.ln(), v_max.ln())
+}
+
+// Cross sections scale as Z(Z + 1)/A, which a mean atom gets wrong for compounds; the
+// ratio of radiation lengths fixes the scale for materials with a tabulated X0
+fn screening_scale(material: &Material) -> f64 {
+    tsai_radiation_length(material) / material.radiation_length()
+}
+
+// Simpson's rule over [a, b]
+fn simpson<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> f64 {
+    let h = (b - a) / INTEGRATION_STEPS as f64;
+    let mut sum = f(a) + f(b);
+    for i in 1..INTEGRATION_STEPS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * f(a + i as f64 * h);
+    }
+    sum * h / 3.0
+}
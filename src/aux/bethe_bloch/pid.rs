@@ -0,0 +1,41 @@
+// This module tabulates dE/dx against momentum for several species at once, the
+// bands a tracking detector (e.g. a TPC) uses for particle identification
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::bb::{stopping_power, Corrections};
+use super::kinematics::kinetic_from_momentum;
+use super::{projectile, Axis, EnergyGrid, Material, Projectile, StoppingPowerTable};
+
+// Species of the usual TPC identification plot
+pub const DEFAULT_SPECIES: [&str; 5] = ["proton", "kaon", "pion", "muon", "electron"];
+
+// dE/dx (MeV/cm) of one species against momentum (MeV/c)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PidCurve {
+    pub species: String,
+    pub table: StoppingPowerTable,
+}
+
+// The grid holds momenta (MeV/c) here, shared by every species so the curves line up. The
+// species are tabulated in parallel, in the order given.
+pub fn pid_curves(grid: &EnergyGrid, projectiles: &[Projectile], material: &Material, corrections: &Corrections) -> Vec<PidCurve> {
+    let momenta = grid.energies();
+    projectiles
+        .par_iter()
+        .map(|projectile| {
+            let mut table = StoppingPowerTable::with_capacity(momenta.len());
+            for &momentum in &momenta {
+                let energy = kinetic_from_momentum(momentum, projectile.mass);
+                table.push(momentum, stopping_power(energy, projectile, material, corrections));
+            }
+            table.axis = Axis::Momentum;
+            PidCurve { species: projectile.name.clone(), table }
+        })
+        .collect()
+}
+
+// Projectiles of `DEFAULT_SPECIES`
+pub fn default_projectiles() -> Vec<Projectile> {
+    DEFAULT_SPECIES.iter().filter_map(|name| projectile::by_name(name)).collect()
+}
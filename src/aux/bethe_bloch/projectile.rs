@@ -0,0 +1,110 @@
+// This module describes the charged particle slowing down in the target
+use serde::{Deserialize, Serialize};
+
+use super::electron::ELECTRON_MASS;
+
+// Atomic mass unit (MeV/c²)
+const ATOMIC_MASS_UNIT: f64 = 931.49410242;
+
+// Names accepted by `by_name`
+pub const NAMES: [&str; 10] = ["proton", "antiproton", "alpha", "muon", "pion", "kaon", "deuteron", "carbon", "electron", "positron"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Projectile {
+    pub name: String,
+    // Rest mass (MeV/c²)
+    pub mass: f64,
+    // Charge number z (units of the elementary charge), negative for negative projectiles
+    // such as antiprotons; the sign matters only for the Barkas term
+    pub charge: f64,
+    // Spin (units of ħ), which enters the delta-ray spectrum
+    pub spin: f64,
+}
+
+impl Projectile {
+    pub fn new(name: &str, mass: f64, charge: f64) -> Self {
+        Projectile { name: String::from(name), mass, charge, spin: 0.0 }
+    }
+
+    pub fn with_spin(mut self, spin: f64) -> Self {
+        self.spin = spin;
+        self
+    }
+
+    // Mass number A, the nearest whole number of atomic mass units, and one for the particles
+    // lighter than a nucleon
+    pub fn nucleons(&self) -> f64 {
+        (self.mass / ATOMIC_MASS_UNIT).round().max(1.0)
+    }
+
+    pub fn proton() -> Self {
+        Projectile::new("proton", 938.272088, 1.0).with_spin(0.5)
+    }
+
+    pub fn antiproton() -> Self {
+        Projectile::proton().antiparticle()
+    }
+
+    // Same mass and spin with the opposite charge. The electron and the positron are each
+    // other's antiparticle, and that of an antiparticle drops its "anti" prefix.
+    pub fn antiparticle(&self) -> Self {
+        let name = match self.name.as_str() {
+            "electron" => String::from("positron"),
+            "positron" => String::from("electron"),
+            name => name.strip_prefix("anti").map_or_else(|| format!("anti{}", name), String::from),
+        };
+        Projectile { name, charge: -self.charge, ..self.clone() }
+    }
+
+    pub fn alpha() -> Self {
+        Projectile::new("alpha", 3727.379, 2.0)
+    }
+
+    pub fn muon() -> Self {
+        Projectile::new("muon", 105.6583755, 1.0).with_spin(0.5)
+    }
+
+    // Charged pion (π±)
+    pub fn pion() -> Self {
+        Projectile::new("pion", 139.57039, 1.0)
+    }
+
+    // Charged kaon (K±)
+    pub fn kaon() -> Self {
+        Projectile::new("kaon", 493.677, 1.0)
+    }
+
+    pub fn deuteron() -> Self {
+        Projectile::new("deuteron", 1875.612943, 1.0).with_spin(1.0)
+    }
+
+    // Fully stripped carbon-12 ion
+    pub fn carbon() -> Self {
+        Projectile::new("carbon", 11174.86, 6.0)
+    }
+
+    pub fn electron() -> Self {
+        Projectile::new("electron", ELECTRON_MASS, -1.0).with_spin(0.5)
+    }
+
+    pub fn positron() -> Self {
+        Projectile::new("positron", ELECTRON_MASS, 1.0).with_spin(0.5)
+    }
+}
+
+// Look up a preset by name (case insensitive)
+pub fn by_name(name: &str) -> Option<Projectile> {
+    match name.to_ascii_lowercase().as_str() {
+        "proton" | "p" => Some(Projectile::proton()),
+        "antiproton" | "pbar" => Some(Projectile::antiproton()),
+        "alpha" => Some(Projectile::alpha()),
+        "muon" | "mu" => Some(Projectile::muon()),
+        "pion" | "pi" => Some(Projectile::pion()),
+        "kaon" | "k" => Some(Projectile::kaon()),
+        "deuteron" | "d" => Some(Projectile::deuteron()),
+        "carbon" | "carbon-12" | "c12" => Some(Projectile::carbon()),
+        "electron" | "e-" => Some(Projectile::electron()),
+        "positron" | "e+" => Some(Projectile::positron()),
+        _ => None,
+    }
+}
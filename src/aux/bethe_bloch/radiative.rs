@@ -0,0 +1,97 @@
+// This module provides the radiative (bremsstrahlung) stopping power of electrons and
+// positrons and the critical energy at which it equals the collision stopping power
+use super::bb::{stopping_power, Corrections};
+use super::electron::ELECTRON_MASS;
+use super::material::AVOGADRO;
+use super::{Material, Projectile};
+
+// 4 α r_e² (cm²)
+const BREMS_CONST: f64 = 2.3178e-27;
+const FINE_STRUCTURE: f64 = 7.2973525693e-3;
+
+// Simpson intervals over the photon energy (must be even)
+const INTEGRATION_STEPS: usize = 200;
+
+// Radiation length (g/cm²) from Tsai's complete-screening formula
+// 1/X0 = 4 α r_e² N_A / A [Z² (L_rad - f(Z)) + Z L'_rad]
+pub fn tsai_radiation_length(material: &Material) -> f64 {
+    let z = material.z;
+    let (l_rad, l_rad_prime) = match z.round() as u32 {
+        1 => (5.31, 6.144),
+        2 => (4.79, 5.621),
+        3 => (4.74, 5.805),
+        4 => (4.71, 5.924),
+        _ => ((184.15 * z.powf(-1.0 / 3.0)).ln(), (1194.0 * z.powf(-2.0 / 3.0)).ln()),
+    };
+
+    let inverse = BREMS_CONST * AVOGADRO / material.a * (z * z * (l_rad - coulomb_correction(z)) + z * l_rad_prime);
+    1.0 / inverse
+}
+
+// Radiative stopping power (MeV/cm) of an electron or positron with kinetic energy
+// `energy` (MeV): the photon-energy-weighted Bethe-Heitler cross section with the
+// Butcher-Messel screening functions, normalised so it tends to E/X0 at high energy
+pub fn radiative_stopping_power(energy: f64, material: &Material) -> f64 {
+    if energy <= 0.0 {
+        return 0.0;
+    }
+
+    let z = material.z;
+    let total = energy + ELECTRON_MASS;
+    let log_z = z.ln() / 3.0 + coulomb_correction(z);
+
+    // k dσ/dk per Z(Z + 1) with the photon energy k = t T
+    let integrand = |t: f64| {
+        let k = t * energy;
+        let remaining = total - k;
+        let epsilon = remaining / total;
+        let delta = 136.0 * ELECTRON_MASS / total * z.powf(-1.0 / 3.0) * k / remaining;
+        let (phi1, phi2) = if delta <= 1.0 {
+            (20.867 - 3.242 * delta + 0.625 * delta * delta, 20.029 - 1.930 * delta - 0.086 * delta * delta)
+        } else {
+            let phi = 21.12 - 4.184 * (delta + 0.952).ln();
+            (phi, phi)
+        };
+        ((1.0 + epsilon * epsilon) * (phi1 / 4.0 - log_z) - 2.0 / 3.0 * epsilon * (phi2 / 4.0 - log_z)).max(0.0)
+    };
+
+    let h = 1.0 / INTEGRATION_STEPS as f64;
+    let mut sum = integrand(0.0) + integrand(1.0);
+    for i in 1..INTEGRATION_STEPS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(i as f64 * h);
+    }
+    let integral = sum * h / 3.0 * energy;
+
+    let atoms = material.density * AVOGADRO / material.a;
+    let uncorrected = BREMS_CONST * atoms * z * (z + 1.0) * integral;
+
+    // Rescale to the tabulated radiation length when the material has one
+    uncorrected * tsai_radiation_length(material) / material.radiation_length()
+}
+
+// Critical energy (MeV): kinetic energy at which the radiative and collision stopping
+// powers are equal, searched between 1 MeV and 100 GeV
+pub fn critical_energy(projectile: &Projectile, material: &Material, corrections: &Corrections) -> Option<f64> {
+    let difference = |e: f64| radiative_stopping_power(e, material) - stopping_power(e, projectile, material, corrections);
+
+    let (mut low, mut high) = (1.0f64, 1e5f64);
+    if difference(low) > 0.0 || difference(high) < 0.0 {
+        return None;
+    }
+    for _ in 0..60 {
+        let middle = (low * high).sqrt();
+        if difference(middle) < 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Some((low * high).sqrt())
+}
+
+// Davies-Bethe-Maximon Coulomb correction f(Z)
+fn coulomb_correction(z: f64) -> f64 {
+    let a2 = (FINE_STRUCTURE * z).powi(2);
+    a2 * (1.0 / (1.0 + a2) + 0.20206 - 0.0369 * a2 + 0.0083 * a2 * a2 - 0.002 * a2 * a2 * a2)
+}
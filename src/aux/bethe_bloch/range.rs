@@ -0,0 +1,211 @@
+// This module computes the continuous-slowing-down-approximation (CSDA) range by
+// integrating the reciprocal stopping power, together with its Bohr range straggling
+use std::io::Write;
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{write_file, Result};
+use crate::output::{write_columns, Format, TextFormat};
+
+use super::bb::{stopping_power, Corrections};
+use super::spline::MonotoneSpline;
+use super::straggling::bohr_variance;
+use super::{EnergyGrid, Material, Projectile};
+
+// Default lower integration limit (MeV per nucleon); the Bethe-Bloch formula is not reliable below it
+pub const DEFAULT_CUTOFF: f64 = 1.0;
+
+// Number of Simpson intervals in ln(E) (must be even)
+const INTEGRATION_STEPS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CsdaRange {
+    // Range (cm)
+    pub length: f64,
+    // Range as mass thickness (g/cm²)
+    pub mass_thickness: f64,
+    // Range straggling sigma (cm)
+    pub straggling: f64,
+}
+
+// Kinetic energy (MeV), CSDA range (cm) and range straggling sigma (cm) of each point
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RangeTable {
+    pub energies: Vec<f64>,
+    pub ranges: Vec<f64>,
+    pub straggling: Vec<f64>,
+}
+
+impl RangeTable {
+    // Kinetic energy (MeV) of a projectile whose CSDA range is `range` cm, e.g. to
+    // reconstruct the beam energy from a measured range
+    pub fn energy_for_range(&self, range: f64) -> Option<f64> {
+        MonotoneSpline::new(self.ranges.clone(), self.energies.clone())?.evaluate(range)
+    }
+
+    // Write the ranges as delimited "energy range straggling" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["E (MeV)", "R (cm)", "sigma_R (cm)"]))?;
+            for ((&energy, &range), &sigma) in self.energies.iter().zip(&self.ranges).zip(&self.straggling) {
+                writeln!(file, "{}", text.join(&[text.scientific(energy), text.scientific(range), text.scientific(sigma)]))?;
+            }
+            Ok(())
+        })
+    }
+
+    // Write the ranges in `format` with "energy, range, straggling" columns, CSV and JSON below
+    // `metadata`, row by row
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, format: Format, text: &TextFormat, metadata: &[(&str, Value)]) -> Result<()> {
+        if format == Format::Text {
+            return self.write(path, text);
+        }
+        write_columns(path, format, metadata, &self.header(), self.rows())
+    }
+
+    // Headers and rows of the "energy, range, straggling" columns
+    pub fn columns(&self) -> (Vec<String>, Vec<Vec<f64>>) {
+        (self.header(), self.rows().collect())
+    }
+
+    // Headers of the "energy, range, straggling" columns
+    pub fn header(&self) -> Vec<String> {
+        vec![String::from("E (MeV)"), String::from("R (cm)"), String::from("sigma_R (cm)")]
+    }
+
+    // Rows of the "energy, range, straggling" columns, made as they are iterated over
+    pub fn rows(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        self.energies.iter().zip(&self.ranges).zip(&self.straggling).map(|((&energy, &range), &sigma)| vec![energy, range, sigma])
+    }
+}
+
+// Bragg-Kleeman power law R = α E^p (R in cm, E in MeV)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BraggKleeman {
+    pub alpha: f64,
+    pub p: f64,
+    // Coefficient of determination of the fit in log-log space
+    pub r_squared: f64,
+    // Largest relative deviation of the fitted range from the table
+    pub max_relative_error: f64,
+}
+
+impl BraggKleeman {
+    // Range (cm) predicted by the fit for kinetic energy `energy` (MeV)
+    pub fn range(&self, energy: f64) -> f64 {
+        self.alpha * energy.powf(self.p)
+    }
+}
+
+impl RangeTable {
+    // Least-squares fit of ln R = ln α + p ln E over the points with a positive range
+    pub fn bragg_kleeman_fit(&self) -> Option<BraggKleeman> {
+        let points: Vec<(f64, f64)> = self
+            .energies
+            .iter()
+            .zip(&self.ranges)
+            .filter(|(_, &r)| r > 0.0)
+            .map(|(e, r)| (e.ln(), r.ln()))
+            .collect();
+        if points.len() < 2 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let syy: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+        if sxx == 0.0 {
+            return None;
+        }
+
+        let p = sxy / sxx;
+        let alpha = (mean_y - p * mean_x).exp();
+        let residual: f64 = points.iter().map(|(x, y)| (y - alpha.ln() - p * x).powi(2)).sum();
+        let r_squared = if syy > 0.0 { 1.0 - residual / syy } else { 1.0 };
+
+        let mut fit = BraggKleeman { alpha, p, r_squared, max_relative_error: 0.0 };
+        fit.max_relative_error = points
+            .iter()
+            .map(|(x, y)| (fit.range(x.exp()) / y.exp() - 1.0).abs())
+            .fold(0.0, f64::max);
+        Some(fit)
+    }
+}
+
+// Lower integration limit (MeV) of `projectile`, `DEFAULT_CUTOFF` per nucleon, so that every
+// projectile is followed down to the same velocity
+pub fn cutoff(projectile: &Projectile) -> f64 {
+    DEFAULT_CUTOFF * projectile.nucleons()
+}
+
+// CSDA range at every grid point above the cutoff, starting from the residual range at the
+// cutoff. Each point is integrated on its own, in parallel.
+pub fn range_table(grid: &EnergyGrid, projectile: &Projectile, material: &Material, corrections: &Corrections) -> RangeTable {
+    let cutoff = cutoff(projectile);
+    let energies: Vec<f64> = grid.energies().into_iter().filter(|&e| e > cutoff).collect();
+    let ranges: Vec<CsdaRange> = energies.par_iter().map(|&energy| csda_range(energy, projectile, material, corrections)).collect();
+
+    let residual = residual_range(cutoff, projectile, material, corrections);
+    let mut table = RangeTable { energies: vec![cutoff], ranges: vec![residual], straggling: vec![0.0] };
+    for (energy, range) in energies.into_iter().zip(ranges) {
+        table.energies.push(energy);
+        table.ranges.push(range.length);
+        table.straggling.push(range.straggling);
+    }
+    table
+}
+
+// CSDA range of a projectile with initial kinetic energy `energy` (MeV): the integral down to
+// the cutoff of the projectile and the residual range below it
+pub fn csda_range(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections) -> CsdaRange {
+    let cutoff = cutoff(projectile);
+    let residual = residual_range(energy.min(cutoff), projectile, material, corrections);
+    let range = csda_range_from(cutoff, energy, projectile, material, corrections);
+    let length = range.length + residual;
+    CsdaRange { length, mass_thickness: length * material.density, ..range }
+}
+
+// Range (cm) of a projectile of kinetic energy `energy` (MeV) at or near the cutoff, taking
+// dE/dx ∝ E^(-1/2) as the electronic stopping power of protons behaves between 0.1 and 1 MeV:
+// R = 2E / (3 dE/dx(E))
+pub fn residual_range(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections) -> f64 {
+    let de_dx = stopping_power(energy, projectile, material, corrections);
+    if de_dx.is_finite() && de_dx > 0.0 { 2.0 * energy / (3.0 * de_dx) } else { 0.0 }
+}
+
+// CSDA range integrating 1/(dE/dx) from `cutoff` up to `energy` (both in MeV); the
+// straggling variance is σ² = ∫ (dΩ²/dx) / (dE/dx)³ dE over the same interval
+pub fn csda_range_from(cutoff: f64, energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections) -> CsdaRange {
+    if energy <= cutoff {
+        return CsdaRange::default();
+    }
+
+    // Simpson's rule in u = ln(E), where dE = E du
+    let lower = cutoff.ln();
+    let h = (energy.ln() - lower) / INTEGRATION_STEPS as f64;
+    let integrands = |u: f64| {
+        let e = u.exp();
+        let de_dx = stopping_power(e, projectile, material, corrections);
+        (e / de_dx, e * bohr_variance(e, projectile, material) / de_dx.powi(3))
+    };
+
+    let (first, last) = (integrands(lower), integrands(energy.ln()));
+    let mut length = first.0 + last.0;
+    let mut variance = first.1 + last.1;
+    for i in 1..INTEGRATION_STEPS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        let (range_term, variance_term) = integrands(lower + i as f64 * h);
+        length += weight * range_term;
+        variance += weight * variance_term;
+    }
+    let length = length * h / 3.0;
+    let variance = variance * h / 3.0;
+
+    CsdaRange { length, mass_thickness: length * material.density, straggling: variance.sqrt() }
+}
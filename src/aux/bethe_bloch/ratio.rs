@@ -0,0 +1,65 @@
+// This module computes ratios of mass stopping powers of two materials, such as the
+// water-to-air ratio that converts ionization-chamber readings into dose to water
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::{EnergyGrid, Material, Projectile};
+
+// Pairs of kinetic energy (MeV) and the ratio of mass stopping powers (S/ρ)_num / (S/ρ)_den
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoppingPowerRatio {
+    pub energies: Vec<f64>,
+    pub ratios: Vec<f64>,
+}
+
+impl StoppingPowerRatio {
+    pub fn push(&mut self, energy: f64, ratio: f64) {
+        self.energies.push(energy);
+        self.ratios.push(ratio);
+    }
+
+    pub fn len(&self) -> usize {
+        self.energies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energies.is_empty()
+    }
+
+    // Iterate over (energy, ratio) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.energies.iter().copied().zip(self.ratios.iter().copied())
+    }
+
+    // Write the ratios as delimited "energy ratio" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["E (MeV)", "ratio"]))?;
+            for (energy, ratio) in self.iter() {
+                writeln!(file, "{}", text.join(&[text.fixed(energy, 1), text.fixed(ratio, 6)]))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// Ratio of the mass stopping powers of `numerator` and `denominator` over the energy grid.
+// Each material uses its own Sternheimer coefficients for the density correction.
+pub fn stopping_power_ratio(grid: &EnergyGrid, projectile: &Projectile, numerator: &Material, denominator: &Material, corrections: &Corrections) -> StoppingPowerRatio {
+    let numerator_corrections = Corrections { density_params: numerator.density_correction_params(), ..*corrections };
+    let denominator_corrections = Corrections { density_params: denominator.density_correction_params(), ..*corrections };
+
+    let mut ratio = StoppingPowerRatio::default();
+    for energy in grid.energies() {
+        let upper = stopping_power(energy, projectile, numerator, &numerator_corrections) / numerator.density;
+        let lower = stopping_power(energy, projectile, denominator, &denominator_corrections) / denominator.density;
+        ratio.push(energy, upper / lower);
+    }
+    ratio
+}
@@ -0,0 +1,120 @@
+// This module weights depth-dose curves with the relative biological effectiveness (RBE)
+// of protons, either constant or from the LET-dependent model of McNamara et al. (2015)
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{write_file, Result};
+use crate::output::{write_columns, Format, TextFormat};
+
+use super::{BraggCurve, LetProfile};
+
+// McNamara fit parameters: RBEmax = P0 + P1 LETd / (α/β), RBEmin = P2 + P3 sqrt(α/β) LETd
+const MCNAMARA_P0: f64 = 0.99064;
+const MCNAMARA_P1: f64 = 0.35605;
+const MCNAMARA_P2: f64 = 1.1012;
+const MCNAMARA_P3: f64 = -0.0038703;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RbeModel {
+    // Clinical constant RBE (1.1 for protons)
+    Constant(f64),
+    // McNamara linear-quadratic model with the (α/β) of the reference photon response (Gy)
+    #[serde(rename = "mcnamara")]
+    McNamara { alpha_beta: f64 },
+}
+
+impl Default for RbeModel {
+    fn default() -> Self {
+        RbeModel::Constant(1.1)
+    }
+}
+
+impl RbeModel {
+    // RBE at a physical dose `dose` (Gy per fraction) and dose-averaged LET `let_dose` (keV/µm)
+    pub fn rbe(&self, dose: f64, let_dose: f64) -> f64 {
+        match *self {
+            RbeModel::Constant(rbe) => rbe,
+            RbeModel::McNamara { alpha_beta } => {
+                let rbe_max = MCNAMARA_P0 + MCNAMARA_P1 * let_dose / alpha_beta;
+                let rbe_min = MCNAMARA_P2 + MCNAMARA_P3 * alpha_beta.sqrt() * let_dose;
+                if dose <= 0.0 {
+                    return rbe_max;
+                }
+                ((alpha_beta * alpha_beta + 4.0 * dose * alpha_beta * rbe_max + 4.0 * dose * dose * rbe_min * rbe_min).sqrt()
+                    - alpha_beta)
+                    / (2.0 * dose)
+            }
+        }
+    }
+}
+
+// Physical dose (Gy), RBE and RBE-weighted dose (Gy(RBE)) against depth (cm)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RbeWeightedDose {
+    pub depths: Vec<f64>,
+    pub doses: Vec<f64>,
+    pub rbe: Vec<f64>,
+    pub weighted: Vec<f64>,
+}
+
+impl RbeWeightedDose {
+    pub fn len(&self) -> usize {
+        self.depths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depths.is_empty()
+    }
+
+    // Write the curve as delimited "depth dose RBE weighted" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["depth (cm)", "dose (Gy)", "RBE", "RBE-weighted dose (Gy(RBE))"]))?;
+            for i in 0..self.len() {
+                let fields = [text.fixed(self.depths[i], 4), text.scientific(self.doses[i]), text.fixed(self.rbe[i], 4), text.scientific(self.weighted[i])];
+                writeln!(file, "{}", text.join(&fields))?;
+            }
+            Ok(())
+        })
+    }
+
+    // Write the curve in `format` with "depth, dose, RBE, weighted" columns, CSV and JSON below `metadata`
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, format: Format, text: &TextFormat, metadata: &[(&str, Value)]) -> Result<()> {
+        if format == Format::Text {
+            return self.write(path, text);
+        }
+        let (header, rows) = self.columns();
+        write_columns(path, format, metadata, &header, rows)
+    }
+
+    // Headers and rows of the "depth, dose, RBE, weighted" columns
+    pub fn columns(&self) -> (Vec<String>, Vec<Vec<f64>>) {
+        let header = vec![String::from("depth (cm)"), String::from("dose (Gy)"), String::from("RBE"), String::from("RBE-weighted dose (Gy(RBE))")];
+        (header, (0..self.len()).map(|i| vec![self.depths[i], self.doses[i], self.rbe[i], self.weighted[i]]).collect())
+    }
+}
+
+// RBE-weighted depth dose of a Bragg curve scaled so its peak receives `peak_dose` Gy, using the
+// dose-averaged LET of the matching LET profile (computed with the same step)
+pub fn rbe_weighted_dose(curve: &BraggCurve, profile: &LetProfile, model: &RbeModel, peak_dose: f64) -> RbeWeightedDose {
+    let mut weighted = RbeWeightedDose::default();
+    let scale = match curve.peak() {
+        Some((_, peak)) if peak > 0.0 => peak_dose / peak,
+        _ => return weighted,
+    };
+
+    for ((depth, dose), let_dose) in curve.iter().zip(&profile.dose) {
+        let dose = dose * scale;
+        // LET from MeV/cm to keV/µm
+        let rbe = model.rbe(dose, 0.1 * let_dose);
+        weighted.depths.push(depth);
+        weighted.doses.push(dose);
+        weighted.rbe.push(rbe);
+        weighted.weighted.push(rbe * dose);
+    }
+    weighted
+}
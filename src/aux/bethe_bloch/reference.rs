@@ -0,0 +1,83 @@
+// This module reads reference stopping-power tables such as NIST PSTAR/ASTAR/ESTAR text exports
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{read_file, Result};
+
+// Electronic stopping power of protons in liquid water (I = 75 eV) from NIST PSTAR / ICRU 49:
+// kinetic energy (MeV) and S/ρ (MeV cm²/g)
+pub const PSTAR_WATER: [(f64, f64); 15] = [
+    (1.0, 260.8),
+    (2.0, 162.4),
+    (5.0, 79.11),
+    (10.0, 45.67),
+    (20.0, 26.07),
+    (30.0, 18.76),
+    (50.0, 12.45),
+    (70.0, 9.559),
+    (100.0, 7.289),
+    (150.0, 5.445),
+    (200.0, 4.492),
+    (300.0, 3.520),
+    (500.0, 2.743),
+    (700.0, 2.415),
+    (1000.0, 2.211),
+];
+
+// Pairs of kinetic energy (MeV) and mass stopping power (MeV cm²/g)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceTable {
+    pub energies: Vec<f64>,
+    pub stopping_powers: Vec<f64>,
+}
+
+impl ReferenceTable {
+    pub fn push(&mut self, energy: f64, stopping_power: f64) {
+        self.energies.push(energy);
+        self.stopping_powers.push(stopping_power);
+    }
+
+    pub fn len(&self) -> usize {
+        self.energies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energies.is_empty()
+    }
+
+    // Iterate over (energy, S/ρ) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.energies.iter().copied().zip(self.stopping_powers.iter().copied())
+    }
+
+    // Embedded PSTAR table of protons in water
+    pub fn pstar_water() -> Self {
+        let mut table = ReferenceTable::default();
+        for (energy, stopping_power) in PSTAR_WATER {
+            table.push(energy, stopping_power);
+        }
+        table
+    }
+
+    // Read a table from a file, see `parse`
+    pub fn read<P: AsRef<Path>>(path: P, column: usize) -> Result<Self> {
+        Ok(ReferenceTable::parse(&read_file(path)?, column))
+    }
+
+    // Parse whitespace, comma or tab separated columns: the energy (MeV) first and the stopping
+    // power (MeV cm²/g) in `column` (1 is the electronic stopping power of a PSTAR export).
+    // Header, comment and other lines that do not start with numbers are skipped.
+    pub fn parse(text: &str, column: usize) -> Self {
+        let mut table = ReferenceTable::default();
+        for line in text.lines() {
+            let fields: Vec<&str> = line.split(|c: char| c.is_whitespace() || c == ',').filter(|f| !f.is_empty()).collect();
+            let energy = fields.first().and_then(|f| f.parse::<f64>().ok());
+            let stopping_power = fields.get(column).and_then(|f| f.parse::<f64>().ok());
+            if let (Some(energy), Some(stopping_power)) = (energy, stopping_power) {
+                table.push(energy, stopping_power);
+            }
+        }
+        table
+    }
+}
@@ -0,0 +1,120 @@
+// This module lays out the stopping-power tables of a run: the variants of the corrections
+// they are computed with, the projectiles and materials of a batch run, and the tables of a
+// target over the grid of the run
+use crate::i18n::{fill, Labels};
+
+use super::bb::{bethe_bloch_with_progress, Corrections};
+use super::uncertainty::mean_excitation_band;
+use super::{Axis, DensityCorrectionParams, EnergyGrid, Material, Projectile, StoppingPowerBand, StoppingPowerTable, Unit};
+
+// One stopping-power table: the heading printed above it, the data file it is written to,
+// the suffix of its plot caption, the plot title and the corrections it is computed with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub heading: String,
+    pub file: String,
+    pub caption: String,
+    pub title: String,
+    pub corrections: Corrections,
+}
+
+impl Variant {
+    pub fn new(heading: &str, file: &str, caption: &str, title: &str, corrections: Corrections) -> Self {
+        Variant { heading: heading.to_string(), file: file.to_string(), caption: caption.to_string(), title: title.to_string(), corrections }
+    }
+
+    // Label the data file and plot with the target of a batch run
+    pub fn in_batch(self, projectile: &Projectile, material: &Material, labels: &Labels) -> Self {
+        Variant {
+            heading: format!("{} for {} in {}", self.heading, projectile.name, material.name),
+            file: format!("{}_{}_{}", projectile.name, material.name, self.file),
+            title: format!("{} ({})", self.title, fill(labels.target, &[&projectile.name, &material.name])),
+            ..self
+        }
+    }
+
+    // Short label of the corrections, naming the curve among the others of a figure
+    pub fn label<'a>(&'a self, labels: &'a Labels) -> &'a str {
+        if self.caption.is_empty() { labels.no_corrections } else { self.caption.trim() }
+    }
+
+    // Table of a selection of corrections, labelled after their names
+    pub fn selection(corrections: Corrections, labels: &Labels) -> Self {
+        let names = corrections.names();
+        if names.is_empty() {
+            return Variant::new("Bethe-Bloch without corrections", "fstopping_no_corrections", "",
+                labels.without_corrections_title, corrections);
+        }
+        let translated: Vec<&str> = names.iter().map(|&name| match name {
+            "density" => labels.density,
+            "shell" | "shell-table" => labels.shell,
+            "barkas" => "Barkas",
+            _ => "Bloch",
+        }).collect();
+        Variant {
+            heading: format!("Bethe-Bloch with {} corrections", names.join(", ")),
+            file: format!("fstopping_{}_corrections", names.join("_")),
+            caption: format!(" {}", fill(labels.corrections, &[&translated.join(", ")])),
+            title: fill(labels.corrections_title, &[&translated.join(", ")]),
+            corrections,
+        }
+    }
+}
+
+// How the tables of a run are computed: the grid, the selected corrections (the four
+// standard variants when there are none), the radiative losses, the unit and abscissa of the
+// tables and the uncertainty (%) of the mean excitation energy of their bands
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSettings {
+    pub grid: EnergyGrid,
+    pub corrections: Option<Corrections>,
+    pub radiative: bool,
+    pub unit: Unit,
+    pub axis: Axis,
+    pub i_uncertainty: Option<f64>,
+}
+
+// The four standard variants, or the single table of a selection of corrections
+pub fn variants(selection: Option<Corrections>, params: &DensityCorrectionParams, labels: &Labels) -> Vec<Variant> {
+    match selection {
+        Some(corrections) => vec![Variant::selection(Corrections { density_params: *params, ..corrections }, labels)],
+        None => vec![
+            Variant::new("Bethe-Bloch without corrections", "fstopping_no_corrections", "",
+                labels.without_corrections_title, Corrections::none()),
+            Variant::new("Bethe-Bloch with Density Corrections", "fstopping_density_corrections", &format!(" {}", labels.density_correction),
+                labels.density_title, Corrections::density(params)),
+            Variant::new("Bethe-Bloch with Layer Correction", "fstopping_layer_corrections", &format!(" {}", labels.shell_correction),
+                labels.shell_title, Corrections::shell()),
+            Variant::new("Bethe-Bloch with all corrections", "fstopping_all_corrections", &format!(" {}", labels.density_and_shell_corrections),
+                labels.density_and_shell_title, Corrections::all(params)),
+        ],
+    }
+}
+
+// Every projectile and material of a batch run, projectile by projectile
+pub fn targets(projectiles: &[Projectile], materials: &[Material]) -> Vec<(Projectile, Material)> {
+    projectiles.iter().flat_map(|p| materials.iter().map(move |m| (p.clone(), m.clone()))).collect()
+}
+
+// Every variant over the grid with the radiative losses, unit and abscissa of `settings`,
+// together with its ΔI band when there is an uncertainty, calling `progress` once per grid
+// point of every table
+pub fn stopping_tables<F: Fn() + Sync>(settings: &TableSettings, params: &DensityCorrectionParams, projectile: &Projectile, material: &Material,
+    labels: &Labels, progress: F) -> Vec<(Variant, StoppingPowerTable, Option<StoppingPowerBand>)> {
+    let finish = |table: StoppingPowerTable, material: &Material| {
+        let table = if settings.radiative { table.add_radiative(projectile, material) } else { table };
+        table.to_unit(settings.unit, material).to_axis(settings.axis, projectile.mass)
+    };
+    let tabulate = |material: &Material, corrections: &Corrections| {
+        finish(bethe_bloch_with_progress(projectile, material, &settings.grid, corrections, &progress), material)
+    };
+
+    variants(settings.corrections, params, labels)
+        .into_iter()
+        .map(|variant| {
+            let table = tabulate(material, &variant.corrections);
+            let band = settings.i_uncertainty.map(|di| mean_excitation_band(material, di, |m| tabulate(m, &variant.corrections)));
+            (variant, table, band)
+        })
+        .collect()
+}
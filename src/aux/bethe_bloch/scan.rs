@@ -0,0 +1,155 @@
+// This module tabulates dE/dx over an energy grid and a second parameter scanned with it: the
+// atomic number of the elements, or the thickness of an absorber crossed before, for color maps
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::elements::ELEMENTS;
+use super::slab::{energy_after_slab, SlabOutcome};
+use super::{EnergyGrid, Material, Projectile};
+
+// Parameter scanned along with the energy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scan {
+    // Atomic number of the elements, with their mass stopping power (MeV cm²/g)
+    #[default]
+    Z,
+    // Thickness (cm) of the material crossed, with the dE/dx (MeV/cm) after it
+    Thickness,
+}
+
+impl fmt::Display for Scan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scan::Z => write!(f, "z"),
+            Scan::Thickness => write!(f, "thickness"),
+        }
+    }
+}
+
+impl FromStr for Scan {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "z" | "element" | "elements" => Ok(Scan::Z),
+            "thickness" => Ok(Scan::Thickness),
+            _ => Err(format!("unknown scan '{}' (expected z or thickness)", s)),
+        }
+    }
+}
+
+// dE/dx at each energy (MeV) of the grid and each value of the scanned parameter, in rows of
+// the parameter: `stopping_powers[row][column]` is at `parameters[row]` and `energies[column]`.
+// It is NaN where the formula does not apply or the projectile stops in the absorber.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoppingPowerMap {
+    pub scan: Scan,
+    pub energies: Vec<f64>,
+    pub parameters: Vec<f64>,
+    pub stopping_powers: Vec<Vec<f64>>,
+}
+
+impl StoppingPowerMap {
+    // Unit of the stopping powers
+    pub fn unit(&self) -> &'static str {
+        match self.scan {
+            Scan::Z => "MeV cm²/g",
+            Scan::Thickness => "MeV/cm",
+        }
+    }
+
+    // Map of the "energy parameter dE/dx" columns of a table written by `write`, the energies
+    // running along each value of the parameter in turn
+    pub fn from_columns(scan: Scan, energies: &[f64], parameters: &[f64], stopping_powers: &[f64]) -> Self {
+        let mut map = StoppingPowerMap { scan, ..StoppingPowerMap::default() };
+        for ((&energy, &parameter), &de_dx) in energies.iter().zip(parameters).zip(stopping_powers) {
+            if map.parameters.last() != Some(&parameter) {
+                map.parameters.push(parameter);
+                map.stopping_powers.push(Vec::new());
+            }
+            if map.parameters.len() == 1 {
+                map.energies.push(energy);
+            }
+            if let Some(row) = map.stopping_powers.last_mut() {
+                row.push(de_dx);
+            }
+        }
+        map
+    }
+
+    // Write "energy parameter dE/dx" lines after a header comment, a row of the parameter after
+    // the other
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        let parameter = match self.scan {
+            Scan::Z => "Z",
+            Scan::Thickness => "t (cm)",
+        };
+        let de_dx = format!("dE/dx ({})", self.unit());
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["E (MeV)", parameter, &de_dx]))?;
+            for (value, row) in self.parameters.iter().zip(&self.stopping_powers) {
+                for (energy, de_dx) in self.energies.iter().zip(row) {
+                    writeln!(file, "{}", text.join(&[text.scientific(*energy), text.scientific(*value), text.scientific(*de_dx)]))?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+// Mass stopping power of each element of `ELEMENTS` over the grid. The elements are given a
+// unit density, which leaves dE/dx in MeV cm²/g, and no density correction, as their
+// Sternheimer coefficients are not tabulated.
+pub fn element_scan(grid: &EnergyGrid, projectile: &Projectile, corrections: &Corrections) -> StoppingPowerMap {
+    let energies = grid.energies();
+    let corrections = Corrections { density: false, ..*corrections };
+    let stopping_powers = ELEMENTS
+        .par_iter()
+        .map(|element| {
+            let material = Material::new(element.symbol, element.z, element.a, 1.0, element.mean_excitation_energy);
+            energies.iter().map(|&energy| applicable(stopping_power(energy, projectile, &material, &corrections))).collect()
+        })
+        .collect();
+    StoppingPowerMap { scan: Scan::Z, parameters: ELEMENTS.iter().map(|element| element.z).collect(), energies, stopping_powers }
+}
+
+// dE/dx of the projectile leaving each thickness (cm, in increasing order) of the material,
+// at each initial energy of the grid. The projectile is carried from one thickness to the next,
+// the initial energies being followed in parallel.
+pub fn thickness_scan(grid: &EnergyGrid, projectile: &Projectile, material: &Material, thicknesses: &[f64], corrections: &Corrections) -> StoppingPowerMap {
+    let energies = grid.energies();
+    let columns: Vec<Vec<f64>> = energies
+        .par_iter()
+        .map(|&initial| {
+            let mut column = vec![f64::NAN; thicknesses.len()];
+            let (mut energy, mut depth) = (initial, 0.0);
+            for (row, &thickness) in thicknesses.iter().enumerate() {
+                match energy_after_slab(projectile, material, thickness - depth, energy, corrections) {
+                    SlabOutcome::Exits(exit) => energy = exit,
+                    SlabOutcome::Stops(_) => break,
+                }
+                depth = thickness;
+                column[row] = applicable(stopping_power(energy, projectile, material, corrections));
+            }
+            column
+        })
+        .collect();
+
+    let stopping_powers = (0..thicknesses.len()).map(|row| columns.iter().map(|column| column[row]).collect()).collect();
+    StoppingPowerMap { scan: Scan::Thickness, energies, parameters: thicknesses.to_vec(), stopping_powers }
+}
+
+// The shell correction can drive dE/dx negative at low energy in heavy targets
+fn applicable(de_dx: f64) -> f64 {
+    if de_dx.is_finite() && de_dx > 0.0 { de_dx } else { f64::NAN }
+}
@@ -0,0 +1,111 @@
+// This module estimates multiple Coulomb scattering with the Highland formula and the
+// lateral spread of a pencil beam as it slows down (Fermi-Eyges moments)
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::kinematics;
+use super::range::cutoff;
+use super::{Material, Projectile};
+
+// Highland constant (MeV)
+const HIGHLAND_CONST: f64 = 13.6;
+
+// Gottschalk's generalized Highland constant (MeV) for thick slabs with energy loss
+const GENERALIZED_HIGHLAND_CONST: f64 = 14.1;
+
+// RMS projected scattering angle θ0 (rad) after `thickness` cm, neglecting the energy loss:
+// θ0 = 13.6 MeV / (βcp) z sqrt(x/X0) [1 + 0.038 ln(x z² / (X0 β²))]
+pub fn highland_angle(energy: f64, projectile: &Projectile, material: &Material, thickness: f64) -> f64 {
+    let beta = kinematics::beta(energy, projectile.mass);
+    let pv = kinematics::momentum(energy, projectile.mass) * beta;
+    let z = projectile.charge.abs();
+    let x = thickness / material.radiation_length_cm();
+
+    HIGHLAND_CONST / pv * z * x.sqrt() * (1.0 + 0.038 * (x * z * z / (beta * beta)).ln())
+}
+
+// Projected RMS lateral displacement σx (cm) of a pencil beam against depth (cm)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LateralSpread {
+    pub depths: Vec<f64>,
+    pub sigmas: Vec<f64>,
+}
+
+impl LateralSpread {
+    pub fn len(&self) -> usize {
+        self.depths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depths.is_empty()
+    }
+
+    // Iterate over (depth, σx) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.depths.iter().copied().zip(self.sigmas.iter().copied())
+    }
+
+    // Write the spread as delimited "depth sigma" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            writeln!(file, "# {}", text.join(&["depth (cm)", "sigma_x (cm)"]))?;
+            for (depth, sigma) in self.iter() {
+                writeln!(file, "{}", text.join(&[text.fixed(depth, 4), text.scientific(sigma)]))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// Lateral spread of a pencil beam of kinetic energy `energy` (MeV) and initial width `sigma0` (cm),
+// stepping `step` cm at a time. With Gottschalk's generalized Highland formula at depth L
+//   σx²(L) = σ0² + (14.1 z)² [1 + log10(L/X0)/9]² ∫0^L (L - u)² / ((pv)² X0) du
+// where the integral is accumulated from its moments in u along the slowing down.
+pub fn lateral_spread(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections, sigma0: f64, step: f64) -> LateralSpread {
+    let cutoff = cutoff(projectile);
+    let mut spread = LateralSpread::default();
+    let radiation_length = material.radiation_length_cm();
+    let z2 = projectile.charge * projectile.charge;
+    let inverse_pv2 = |e: f64| {
+        let pv = kinematics::momentum(e, projectile.mass) * kinematics::beta(e, projectile.mass);
+        1.0 / (pv * pv)
+    };
+
+    // Moments ∫ u^n / (pv)² du for n = 0, 1, 2
+    let (mut m0, mut m1, mut m2) = (0.0, 0.0, 0.0);
+    let mut energy = energy;
+    let mut depth = 0.0;
+
+    while energy > cutoff {
+        // Midpoint estimate of the energy lost over the step
+        let de_start = stopping_power(energy, projectile, material, corrections) * step;
+        let mid = energy - de_start / 2.0;
+        let de = stopping_power(mid, projectile, material, corrections) * step;
+        if de_start >= energy - cutoff || !de.is_finite() || de >= energy - cutoff {
+            break;
+        }
+
+        let u = depth + step / 2.0;
+        let weight = inverse_pv2(mid) * step;
+        m0 += weight;
+        m1 += weight * u;
+        m2 += weight * u * u;
+        depth += step;
+        energy -= de;
+
+        let log_factor = 1.0 + (depth / radiation_length).log10() / 9.0;
+        let integral = (depth * depth * m0 - 2.0 * depth * m1 + m2) / radiation_length;
+        let variance = GENERALIZED_HIGHLAND_CONST.powi(2) * z2 * log_factor * log_factor * integral;
+
+        spread.depths.push(depth);
+        spread.sigmas.push((sigma0 * sigma0 + variance).sqrt());
+    }
+
+    spread
+}
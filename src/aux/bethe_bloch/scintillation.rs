@@ -0,0 +1,105 @@
+// This module converts the stopping power into the light emitted by organic scintillators,
+// whose response saturates at high ionization density following Birks' law
+//   dL/dx = S dE/dx / (1 + kB dE/dx)
+use serde::{Deserialize, Serialize};
+
+use super::bb::{stopping_power, Corrections};
+use super::range::cutoff;
+use super::{EnergyGrid, Material, Projectile};
+
+// Number of Simpson intervals in ln(E) for the total light output (must be even)
+const INTEGRATION_STEPS: usize = 200;
+
+// Scintillation efficiency S (photons/MeV) and Birks' constant kB (cm/MeV)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Scintillator {
+    pub light_yield: f64,
+    pub birks: f64,
+}
+
+// Plastic scintillator: 10⁴ photons/MeV and kB = 0.0126 g cm⁻²/MeV at ρ = 1.032 g/cm³
+impl Default for Scintillator {
+    fn default() -> Self {
+        Scintillator { light_yield: 1.0e4, birks: 0.0126 / 1.032 }
+    }
+}
+
+impl Scintillator {
+    pub fn new(light_yield: f64, birks: f64) -> Self {
+        Scintillator { light_yield, birks }
+    }
+
+    // Fraction of the unquenched light, 1 / (1 + kB dE/dx), for dE/dx in MeV/cm
+    pub fn quenching_factor(&self, de_dx: f64) -> f64 {
+        1.0 / (1.0 + self.birks * de_dx)
+    }
+
+    // Photons per cm for a stopping power dE/dx (MeV/cm)
+    pub fn light_per_cm(&self, de_dx: f64) -> f64 {
+        self.light_yield * de_dx * self.quenching_factor(de_dx)
+    }
+}
+
+// Quenched response along an energy scan: dE/dx (MeV/cm) and dL/dx (photons/cm) per energy (MeV)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuenchedResponse {
+    pub energies: Vec<f64>,
+    pub stopping_powers: Vec<f64>,
+    pub light: Vec<f64>,
+}
+
+impl QuenchedResponse {
+    pub fn len(&self) -> usize {
+        self.energies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energies.is_empty()
+    }
+
+    // Iterate over (energy, dE/dx, dL/dx) triples
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64, f64)> + '_ {
+        self.energies
+            .iter()
+            .zip(&self.stopping_powers)
+            .zip(&self.light)
+            .map(|((&energy, &de_dx), &light)| (energy, de_dx, light))
+    }
+}
+
+// Light per unit length over the energy grid
+pub fn quenched_response(grid: &EnergyGrid, projectile: &Projectile, material: &Material, corrections: &Corrections, scintillator: &Scintillator) -> QuenchedResponse {
+    let mut response = QuenchedResponse::default();
+    for energy in grid.energies() {
+        let de_dx = stopping_power(energy, projectile, material, corrections);
+        response.energies.push(energy);
+        response.stopping_powers.push(de_dx);
+        response.light.push(scintillator.light_per_cm(de_dx));
+    }
+    response
+}
+
+// Total photons emitted by a projectile of kinetic energy `energy` (MeV) stopping in the scintillator,
+// L = S ∫ dE / (1 + kB dE/dx). Below the Bethe-Bloch cutoff the quenching is frozen at its cutoff value.
+pub fn light_output(energy: f64, projectile: &Projectile, material: &Material, corrections: &Corrections, scintillator: &Scintillator) -> f64 {
+    let cutoff = cutoff(projectile);
+    let quenching = |e: f64| scintillator.quenching_factor(stopping_power(e, projectile, material, corrections));
+
+    let below = scintillator.light_yield * energy.min(cutoff) * quenching(cutoff);
+    if energy <= cutoff {
+        return below;
+    }
+
+    // Simpson's rule in u = ln(E), where dE = E du
+    let lower = cutoff.ln();
+    let h = (energy.ln() - lower) / INTEGRATION_STEPS as f64;
+    let integrand = |u: f64| u.exp() * quenching(u.exp());
+
+    let mut sum = integrand(lower) + integrand(energy.ln());
+    for i in 1..INTEGRATION_STEPS {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(lower + i as f64 * h);
+    }
+
+    below + scintillator.light_yield * sum * h / 3.0
+}
@@ -0,0 +1,103 @@
+// This module measures how sensitive the stopping power is to each Sternheimer
+// density-effect coefficient by perturbing them one at a time
+use std::io::Write;
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::{DensityCorrectionParams, EnergyGrid, Material, Projectile};
+
+// Coefficients perturbed, in the order of `DensityCorrectionParams`
+pub const PARAMETERS: [&str; 5] = ["a", "x0", "x1", "C", "m"];
+
+// Fractional change (S' - S)/S of dE/dx over the energy grid when one coefficient is
+// scaled by (1 - fraction) and (1 + fraction)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Sensitivity {
+    pub parameter: &'static str,
+    pub energies: Vec<f64>,
+    pub minus: Vec<f64>,
+    pub plus: Vec<f64>,
+}
+
+impl Sensitivity {
+    // Changes (minus, plus) at the energy where the larger of the two is largest in magnitude
+    pub fn extreme(&self) -> (f64, f64) {
+        self.minus
+            .iter()
+            .zip(&self.plus)
+            .map(|(&minus, &plus)| (minus, plus))
+            .max_by(|a, b| a.0.abs().max(a.1.abs()).total_cmp(&b.0.abs().max(b.1.abs())))
+            .unwrap_or_default()
+    }
+
+    // Largest magnitude of the change over the grid
+    pub fn magnitude(&self) -> f64 {
+        let (minus, plus) = self.extreme();
+        minus.abs().max(plus.abs())
+    }
+}
+
+// Sensitivity of every coefficient of `corrections.density_params`, perturbed by `fraction`
+// (e.g. 0.05 for 5%). The density correction is switched on for the perturbed runs, which are
+// computed in parallel.
+pub fn density_sensitivity(grid: &EnergyGrid, projectile: &Projectile, material: &Material, corrections: &Corrections, fraction: f64) -> Vec<Sensitivity> {
+    let corrections = Corrections { density: true, ..*corrections };
+    let energies = grid.energies();
+    let reference: Vec<f64> = energies.iter().map(|&e| stopping_power(e, projectile, material, &corrections)).collect();
+
+    let change = |index: usize, factor: f64| -> Vec<f64> {
+        let perturbed = Corrections { density_params: perturb(&corrections.density_params, index, factor), ..corrections };
+        energies
+            .iter()
+            .zip(&reference)
+            .map(|(&e, &s)| stopping_power(e, projectile, material, &perturbed) / s - 1.0)
+            .collect()
+    };
+
+    PARAMETERS
+        .par_iter()
+        .enumerate()
+        .map(|(index, &parameter)| Sensitivity {
+            parameter,
+            energies: energies.clone(),
+            minus: change(index, 1.0 - fraction),
+            plus: change(index, 1.0 + fraction),
+        })
+        .collect()
+}
+
+// Write the changes as delimited "energy minus plus ..." lines, two columns per coefficient
+pub fn write_sensitivities<P: AsRef<Path>>(sensitivities: &[Sensitivity], path: P, text: &TextFormat) -> Result<()> {
+    write_file(path, |file| {
+        let mut header = vec![String::from("E (MeV)")];
+        header.extend(sensitivities.iter().flat_map(|s| [format!("{}-", s.parameter), format!("{}+", s.parameter)]));
+        writeln!(file, "# {}", text.join(&header))?;
+
+        let energies = sensitivities.first().map(|s| s.energies.as_slice()).unwrap_or_default();
+        for (i, &energy) in energies.iter().enumerate() {
+            let mut fields = vec![text.scientific(energy)];
+            fields.extend(sensitivities.iter().flat_map(|s| [text.scientific(s.minus[i]), text.scientific(s.plus[i])]));
+            writeln!(file, "{}", text.join(&fields))?;
+        }
+        Ok(())
+    })
+}
+
+// Scale the coefficient at `index` of `PARAMETERS` by `factor`
+fn perturb(params: &DensityCorrectionParams, index: usize, factor: f64) -> DensityCorrectionParams {
+    let mut params = *params;
+    match index {
+        0 => params.a *= factor,
+        1 => params.x0 *= factor,
+        2 => params.x1 *= factor,
+        3 => params.c *= factor,
+        _ => params.m *= factor,
+    }
+    params
+}
@@ -0,0 +1,82 @@
+// This module provides the backends for the shell correction C/Z
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{read_file, Error, Result};
+
+use super::reference::ReferenceTable;
+use super::spline::MonotoneSpline;
+use super::Material;
+
+// Lowest βγ at which the polynomial holds (about 8 MeV protons); below it the polynomial
+// grows without bound, so its value at this limit is used instead
+pub const POLYNOMIAL_LIMIT: f64 = 0.13;
+
+// How the shell correction is evaluated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellCorrectionModel {
+    // Polynomial in βγ and I, only valid for βγ ≳ 0.13 and held at that limit below it
+    #[default]
+    Polynomial,
+    // Interpolation of the (βγ, C/Z) table of the material, e.g. Bichsel's, read with
+    // `read_table`; below the table the first value is used and above it C/Z falls off like
+    // 1/(βγ)². Materials without a table use the polynomial.
+    Tabulated,
+}
+
+impl ShellCorrectionModel {
+    // Shell correction C/Z at `bg` = βγ in `material`
+    pub fn correction(&self, bg: f64, material: &Material) -> f64 {
+        match self {
+            ShellCorrectionModel::Polynomial => polynomial(bg.max(POLYNOMIAL_LIMIT), material.mean_excitation_energy) / material.z,
+            ShellCorrectionModel::Tabulated => match &material.shell_corrections {
+                Some(table) => tabulated(table, bg),
+                None => ShellCorrectionModel::Polynomial.correction(bg, material),
+            },
+        }
+    }
+}
+
+// Read a table of shell corrections: whitespace, comma or tab separated βγ and C/Z columns,
+// with βγ positive and increasing. Lines that do not start with numbers are skipped.
+pub fn read_table<P: AsRef<Path>>(path: P) -> Result<Vec<(f64, f64)>> {
+    let path = path.as_ref();
+    let table: Vec<(f64, f64)> = ReferenceTable::parse(&read_file(path)?, 1).iter().collect();
+    let invalid = |message: &str| Err(Error::Table { path: path.to_path_buf(), message: String::from(message) });
+    if table.len() < 2 {
+        return invalid("a shell-correction table needs at least two rows of βγ and C/Z");
+    }
+    if table.iter().any(|(bg, correction)| !(bg.is_finite() && *bg > 0.0 && correction.is_finite())) {
+        return invalid("βγ must be positive and C/Z finite");
+    }
+    if table.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+        return invalid("βγ must increase from row to row");
+    }
+    Ok(table)
+}
+
+// Shell correction C(I, βγ) with the mean excitation energy I in eV
+fn polynomial(bg: f64, i_shell: f64) -> f64 {
+    (0.422377*bg.powi(-2) + 0.0304043*bg.powi(-4) - 0.00038106*bg.powi(-6))*(10.0_f64.powi(-6))*i_shell.powi(2)
+        + (3.850190*bg.powi(-2)-0.1667989*bg.powi(-4) + 0.00157955*bg.powi(-6))*(10.0_f64.powi(-9))*i_shell.powi(3)
+}
+
+// Monotone spline in ln(βγ)
+fn tabulated(table: &[(f64, f64)], bg: f64) -> f64 {
+    let (first, last) = match (table.first(), table.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return 0.0,
+    };
+    if bg <= first.0 {
+        return first.1;
+    }
+    if bg >= last.0 {
+        return last.1 * (last.0 / bg).powi(2);
+    }
+
+    let xs = table.iter().map(|(x, _)| x.ln()).collect();
+    let ys = table.iter().map(|(_, y)| *y).collect();
+    MonotoneSpline::new(xs, ys).and_then(|spline| spline.evaluate(bg.ln())).unwrap_or(0.0)
+}
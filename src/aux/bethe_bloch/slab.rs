@@ -0,0 +1,64 @@
+// This module transports a projectile through a slab of finite thickness
+use serde::{Deserialize, Serialize};
+
+use super::bb::{stopping_power, Corrections};
+use super::range::cutoff;
+use super::{Material, Projectile};
+
+// Largest fraction of the kinetic energy that may be lost in a single step
+const MAX_ENERGY_FRACTION: f64 = 0.01;
+
+// Result of sending a projectile through a slab
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlabOutcome {
+    // The projectile leaves the slab with this kinetic energy (MeV)
+    Exits(f64),
+    // The projectile falls below the Bethe-Bloch cutoff at this depth (cm)
+    Stops(f64),
+}
+
+impl SlabOutcome {
+    // Exit energy (MeV), zero if the projectile stops
+    pub fn exit_energy(&self) -> f64 {
+        match *self {
+            SlabOutcome::Exits(energy) => energy,
+            SlabOutcome::Stops(_) => 0.0,
+        }
+    }
+}
+
+// Step a projectile of kinetic energy `energy` (MeV) through `thickness` cm of `material`.
+// Each step is sized so the projectile loses at most 1% of its energy, which keeps the
+// steps long at high energy and short near the end of the range.
+pub fn energy_after_slab(projectile: &Projectile, material: &Material, thickness: f64, energy: f64, corrections: &Corrections) -> SlabOutcome {
+    let cutoff = cutoff(projectile);
+    let mut energy = energy;
+    let mut depth = 0.0;
+
+    while depth < thickness {
+        // The shell correction can drive dE/dx negative at low energy in heavy targets;
+        // treat the projectile as stopped once the formula no longer applies
+        let de_dx = stopping_power(energy, projectile, material, corrections);
+        if energy <= cutoff || de_dx.is_nan() || de_dx <= 0.0 {
+            return SlabOutcome::Stops(depth);
+        }
+
+        let step = (MAX_ENERGY_FRACTION * energy / de_dx).min(thickness - depth);
+
+        // Midpoint estimate of the energy lost over the step
+        let de = stopping_power(energy - de_dx * step / 2.0, projectile, material, corrections) * step;
+        if !de.is_finite() || de <= 0.0 {
+            return SlabOutcome::Stops(depth);
+        }
+        if de >= energy - cutoff {
+            // Linear interpolation of the stopping point within the last step
+            return SlabOutcome::Stops(depth + step * (energy - cutoff) / de);
+        }
+
+        energy -= de;
+        depth += step;
+    }
+
+    SlabOutcome::Exits(energy)
+}
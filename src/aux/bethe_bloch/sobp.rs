@@ -0,0 +1,108 @@
+// This module builds a spread-out Bragg peak (SOBP) from weighted pristine Bragg curves
+use serde::{Deserialize, Serialize};
+
+use super::bb::Corrections;
+use super::bragg::bragg;
+use super::range::{csda_range, range_table};
+use super::{BraggCurve, EnergyGrid, Material, Projectile};
+
+// Passes of the multiplicative weight refinement
+const REFINEMENT_PASSES: usize = 50;
+
+// Energy layers, their weights and the resulting composite depth-dose curve
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sobp {
+    // Kinetic energies of the layers (MeV), deepest first
+    pub energies: Vec<f64>,
+    // Relative weights (fluence) of the layers, the deepest normalised to one
+    pub weights: Vec<f64>,
+    pub curve: BraggCurve,
+}
+
+impl Sobp {
+    // Largest relative deviation from the mean dose between `proximal` and `distal` (cm)
+    pub fn flatness(&self, proximal: f64, distal: f64) -> f64 {
+        let plateau: Vec<f64> = self.curve.iter().filter(|(d, _)| (proximal..=distal).contains(d)).map(|(_, dose)| dose).collect();
+        if plateau.is_empty() {
+            return 0.0;
+        }
+        let mean = plateau.iter().sum::<f64>() / plateau.len() as f64;
+        plateau.iter().map(|dose| (dose / mean - 1.0).abs()).fold(0.0, f64::max)
+    }
+}
+
+// Flat SOBP between `proximal` and `distal` depths (cm). Layers have CSDA ranges spaced
+// `spacing` cm apart from the distal edge; the curves are sampled every `step` cm.
+pub fn sobp(proximal: f64, distal: f64, spacing: f64, step: f64, projectile: &Projectile, material: &Material, corrections: &Corrections) -> Sobp {
+    // Energies whose ranges cover the interval, from the range-energy table
+    let layers = ((distal - proximal) / spacing).floor() as usize + 1;
+    let max_energy = (1..).map(|k| 10.0 * 2f64.powi(k)).find(|&e| csda_range(e, projectile, material, corrections).length > distal).unwrap_or(10000.0);
+    let table = range_table(&EnergyGrid::logarithmic(1.0, max_energy, 200), projectile, material, corrections);
+    let energies: Vec<f64> = (0..layers).filter_map(|i| table.energy_for_range(distal - i as f64 * spacing)).collect();
+
+    // Pristine curves smeared by the range straggling, on the common depth grid of the deepest one
+    let curves: Vec<BraggCurve> = energies
+        .iter()
+        .map(|&e| smear(&bragg(e, projectile, material, corrections, step), csda_range(e, projectile, material, corrections).straggling, step))
+        .collect();
+    let points = curves.first().map_or(0, BraggCurve::len);
+    let dose_at = |curve: &BraggCurve, k: usize| curve.doses.get(k).copied().unwrap_or(0.0);
+    let peaks: Vec<usize> = curves.iter().map(|curve| (0..curve.len()).max_by(|&a, &b| curve.doses[a].total_cmp(&curve.doses[b])).unwrap_or(0)).collect();
+
+    let composite = |weights: &[f64]| -> Vec<f64> {
+        (0..points).map(|k| curves.iter().zip(weights).map(|(curve, w)| w * dose_at(curve, k)).sum()).collect()
+    };
+
+    // Start from the deepest layer and fill each shallower peak up to its dose
+    let mut weights = vec![0.0; curves.len()];
+    if let Some(first) = weights.first_mut() {
+        *first = 1.0;
+    }
+    let target = curves.first().map_or(0.0, |curve| dose_at(curve, peaks[0]));
+    for i in 1..curves.len() {
+        let dose = composite(&weights)[peaks[i]];
+        weights[i] = ((target - dose) / dose_at(&curves[i], peaks[i])).max(0.0);
+    }
+
+    // Refine: scale each weight by the ratio of target to delivered dose at its peak
+    for _ in 0..REFINEMENT_PASSES {
+        let dose = composite(&weights);
+        for (weight, &peak) in weights.iter_mut().zip(&peaks) {
+            if dose[peak] > 0.0 {
+                *weight *= target / dose[peak];
+            }
+        }
+    }
+
+    // Normalise to the deepest layer
+    let norm = weights.first().copied().filter(|&w| w > 0.0).unwrap_or(1.0);
+    weights.iter_mut().for_each(|w| *w /= norm);
+
+    let mut curve = BraggCurve::default();
+    for (k, dose) in composite(&weights).into_iter().enumerate() {
+        curve.push(curves[0].depths[k], dose);
+    }
+
+    Sobp { energies, weights, curve }
+}
+
+// Convolve a curve with a Gaussian of width `sigma` (cm), extending it by 4σ past its end
+fn smear(curve: &BraggCurve, sigma: f64, step: f64) -> BraggCurve {
+    if sigma.is_nan() || sigma <= 0.0 {
+        return curve.clone();
+    }
+
+    let extra = (4.0 * sigma / step).ceil() as usize;
+    let mut smeared = BraggCurve::default();
+    for k in 0..curve.len() + extra {
+        let depth = (k as f64 + 0.5) * step;
+        let dose: f64 = curve
+            .iter()
+            .map(|(d, dose)| dose * (-0.5 * ((depth - d) / sigma).powi(2)).exp())
+            .sum::<f64>()
+            * step
+            / (sigma * (2.0 * std::f64::consts::PI).sqrt());
+        smeared.push(depth, dose);
+    }
+    smeared
+}
@@ -0,0 +1,57 @@
+// This module provides monotone cubic (Fritsch-Carlson) interpolation, which never
+// overshoots between the tabulated points and so keeps monotonic tables monotonic
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonotoneSpline {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    slopes: Vec<f64>,
+}
+
+impl MonotoneSpline {
+    // Spline through (xs, ys); the abscissae must be strictly increasing with at least two points
+    pub fn new(xs: Vec<f64>, ys: Vec<f64>) -> Option<Self> {
+        if xs.len() < 2 || xs.len() != ys.len() || xs.windows(2).any(|w| w[1] <= w[0]) {
+            return None;
+        }
+
+        let n = xs.len();
+        let widths: Vec<f64> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+        let secants: Vec<f64> = (0..n - 1).map(|k| (ys[k + 1] - ys[k]) / widths[k]).collect();
+
+        // Interior slopes from the weighted harmonic mean of neighbouring secants,
+        // flat at local extrema; one-sided secants at both ends
+        let mut slopes = vec![0.0; n];
+        slopes[0] = secants[0];
+        slopes[n - 1] = secants[n - 2];
+        for k in 1..n - 1 {
+            let (left, right) = (secants[k - 1], secants[k]);
+            if left * right > 0.0 {
+                let w1 = 2.0 * widths[k] + widths[k - 1];
+                let w2 = widths[k] + 2.0 * widths[k - 1];
+                slopes[k] = (w1 + w2) / (w1 / left + w2 / right);
+            }
+        }
+
+        Some(MonotoneSpline { xs, ys, slopes })
+    }
+
+    // Interpolated value at `x`, or None outside the tabulated interval
+    pub fn evaluate(&self, x: f64) -> Option<f64> {
+        let (first, last) = (self.xs[0], self.xs[self.xs.len() - 1]);
+        if !(first..=last).contains(&x) {
+            return None;
+        }
+
+        let k = self.xs.partition_point(|&xk| xk <= x).clamp(1, self.xs.len() - 1) - 1;
+        let h = self.xs[k + 1] - self.xs[k];
+        let t = (x - self.xs[k]) / h;
+
+        // Cubic Hermite basis
+        let h00 = (1.0 + 2.0 * t) * (1.0 - t) * (1.0 - t);
+        let h10 = t * (1.0 - t) * (1.0 - t);
+        let h01 = t * t * (3.0 - 2.0 * t);
+        let h11 = t * t * (t - 1.0);
+
+        Some(h00 * self.ys[k] + h10 * h * self.slopes[k] + h01 * self.ys[k + 1] + h11 * h * self.slopes[k + 1])
+    }
+}
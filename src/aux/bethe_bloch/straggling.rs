@@ -0,0 +1,16 @@
+// This module computes energy-loss straggling (fluctuations around the mean dE/dx)
+use super::bb::effective_charge;
+use super::kinematics;
+use super::{Material, Projectile};
+
+// 4π r_e² (m_e c²)² (MeV² cm²)
+const BOHR_CONST: f64 = 2.6055e-25;
+
+// Bohr energy-loss variance per unit path length Ω²/Δx (MeV²/cm), including the
+// relativistic factor (1 - β²/2) / (1 - β²)
+pub fn bohr_variance(energy: f64, projectile: &Projectile, material: &Material) -> f64 {
+    let beta2 = kinematics::beta_squared(energy, projectile.mass);
+    let charge = effective_charge(projectile.charge, beta2.sqrt());
+
+    BOHR_CONST * charge * charge * material.electron_density * (1.0 - beta2 / 2.0) / (1.0 - beta2)
+}
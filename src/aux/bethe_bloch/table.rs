@@ -0,0 +1,195 @@
+// This module provides the table returned by the Bethe-Bloch routines and the
+// optional step that writes it to disk
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{write_file, Result};
+use crate::output::{write_columns, Format, TextFormat};
+
+use super::bb::radiative_stopping_power;
+use super::spline::MonotoneSpline;
+use super::{Axis, Material, Projectile, Unit};
+
+// Pairs of kinetic energy (MeV) and stopping power dE/dx in `unit`, optionally with
+// the Bohr straggling variance per unit length Ω²/Δx (MeV²/cm) of each point. After
+// `to_axis` the energies hold the momentum (MeV/c) or βγ of each point instead.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoppingPowerTable {
+    pub energies: Vec<f64>,
+    pub stopping_powers: Vec<f64>,
+    pub unit: Unit,
+    pub axis: Axis,
+    pub straggling: Option<Vec<f64>>,
+}
+
+impl StoppingPowerTable {
+    pub fn with_capacity(capacity: usize) -> Self {
+        StoppingPowerTable {
+            energies: Vec::with_capacity(capacity),
+            stopping_powers: Vec::with_capacity(capacity),
+            unit: Unit::MevPerCm,
+            axis: Axis::KineticEnergy,
+            straggling: None,
+        }
+    }
+
+    pub fn push(&mut self, energy: f64, stopping_power: f64) {
+        self.energies.push(energy);
+        self.stopping_powers.push(stopping_power);
+    }
+
+    pub fn len(&self) -> usize {
+        self.energies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energies.is_empty()
+    }
+
+    // Iterate over (energy, dE/dx) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.energies.iter().copied().zip(self.stopping_powers.iter().copied())
+    }
+
+    // Add the radiative stopping power of `projectile` to a table in MeV/cm of kinetic energies
+    pub fn add_radiative(mut self, projectile: &Projectile, material: &Material) -> StoppingPowerTable {
+        for (energy, de_dx) in self.energies.iter().zip(self.stopping_powers.iter_mut()) {
+            *de_dx += radiative_stopping_power(*energy, projectile, material);
+        }
+        self
+    }
+
+    // Convert the stopping powers to another unit
+    pub fn to_unit(&self, unit: Unit, material: &Material) -> StoppingPowerTable {
+        StoppingPowerTable {
+            energies: self.energies.clone(),
+            stopping_powers: self
+                .stopping_powers
+                .iter()
+                .map(|s| unit.from_mev_per_cm(self.unit.to_mev_per_cm(*s, material), material))
+                .collect(),
+            unit,
+            axis: self.axis,
+            straggling: self.straggling.clone(),
+        }
+    }
+
+    // Express the abscissae on another axis for a projectile of rest mass `mass` (MeV/c²)
+    pub fn to_axis(&self, axis: Axis, mass: f64) -> StoppingPowerTable {
+        StoppingPowerTable {
+            energies: self
+                .energies
+                .iter()
+                .map(|x| axis.from_kinetic(self.axis.to_kinetic(*x, mass), mass))
+                .collect(),
+            axis,
+            ..self.clone()
+        }
+    }
+
+    // Mass stopping power (MeV cm²/g), dividing dE/dx by the material density
+    pub fn to_mass_stopping_power(&self, material: &Material) -> StoppingPowerTable {
+        self.to_unit(Unit::MevCm2PerG, material)
+    }
+
+    // Kinetic energy (MeV, or the value on the table axis) at which dE/dx equals `stopping_power` (in the
+    // table unit). The search runs on the falling branch below the minimum of the table, where dE/dx is monotonic.
+    pub fn energy_for_dedx(&self, stopping_power: f64) -> Option<f64> {
+        let minimum = self.minimum_index()?;
+        let xs = self.stopping_powers[..=minimum].iter().rev().copied().collect();
+        let ys = self.energies[..=minimum].iter().rev().copied().collect();
+        MonotoneSpline::new(xs, ys)?.evaluate(stopping_power)
+    }
+
+    // Minimum ionizing point: energy (MeV, or the table axis) and dE/dx (table unit) of the minimum,
+    // refined by a parabola through the neighbouring points in ln(E). None if the minimum lies at an end of the table.
+    pub fn minimum_ionizing(&self) -> Option<(f64, f64)> {
+        let k = self.minimum_index()?;
+        if k == 0 || k + 1 >= self.len() {
+            return None;
+        }
+
+        let (x0, x1, x2) = (self.energies[k - 1].ln(), self.energies[k].ln(), self.energies[k + 1].ln());
+        let (y0, y1, y2) = (self.stopping_powers[k - 1], self.stopping_powers[k], self.stopping_powers[k + 1]);
+
+        // Vertex of the parabola through the three points (divided differences)
+        let d01 = (y1 - y0) / (x1 - x0);
+        let d12 = (y2 - y1) / (x2 - x1);
+        let curvature = (d12 - d01) / (x2 - x0);
+        if curvature <= 0.0 {
+            return Some((self.energies[k], y1));
+        }
+        let x = 0.5 * (x0 + x1) - d01 / (2.0 * curvature);
+        let y = y0 + d01 * (x - x0) + curvature * (x - x0) * (x - x1);
+
+        Some((x.exp(), y))
+    }
+
+    // Index of the smallest stopping power
+    fn minimum_index(&self) -> Option<usize> {
+        self.stopping_powers.iter().enumerate().min_by(|a, b| a.1.total_cmp(b.1)).map(|(k, _)| k)
+    }
+
+    // Write the table as delimited "energy dE/dx [Ω²/Δx]" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            match &self.straggling {
+                Some(straggling) => {
+                    writeln!(file, "# {}", text.join(&[self.axis.to_string(), format!("dE/dx ({})", self.unit), String::from("Omega^2/dx (MeV^2/cm)")]))?;
+                    for ((energy, de_dx), &variance) in self.iter().zip(straggling) {
+                        writeln!(file, "{}", text.join(&[self.format_abscissa(energy, text), text.scientific(de_dx), text.scientific(variance)]))?;
+                    }
+                }
+                None => {
+                    writeln!(file, "# {}", text.join(&[self.axis.to_string(), format!("dE/dx ({})", self.unit)]))?;
+                    for (energy, de_dx) in self.iter() {
+                        writeln!(file, "{}", text.join(&[self.format_abscissa(energy, text), text.scientific(de_dx)]))?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    // Write the table in `format` with "energy, dE/dx[, Ω²/Δx]" columns, CSV and JSON below
+    // `metadata` and with every value in full precision, row by row
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, format: Format, text: &TextFormat, metadata: &[(&str, Value)]) -> Result<()> {
+        if format == Format::Text {
+            return self.write(path, text);
+        }
+        write_columns(path, format, metadata, &self.header(), self.rows())
+    }
+
+    // Headers and rows of the "energy, dE/dx[, Ω²/Δx]" columns
+    pub fn columns(&self) -> (Vec<String>, Vec<Vec<f64>>) {
+        (self.header(), self.rows().collect())
+    }
+
+    // Headers of the "energy, dE/dx[, Ω²/Δx]" columns
+    pub fn header(&self) -> Vec<String> {
+        let mut header = vec![self.axis.header().to_string(), format!("dE/dx ({})", self.unit)];
+        if self.straggling.is_some() {
+            header.push(String::from("Omega^2/dx (MeV^2/cm)"));
+        }
+        header
+    }
+
+    // Rows of the "energy, dE/dx[, Ω²/Δx]" columns, made as they are iterated over
+    pub fn rows(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        self.iter().enumerate().map(move |(point, (energy, de_dx))| match &self.straggling {
+            Some(straggling) => vec![energy, de_dx, straggling.get(point).copied().unwrap_or(f64::NAN)],
+            None => vec![energy, de_dx],
+        })
+    }
+
+    // Kinetic energies keep one decimal, while βγ and momenta span decades and use exponents
+    fn format_abscissa(&self, value: f64, text: &TextFormat) -> String {
+        match self.axis {
+            Axis::KineticEnergy => text.fixed(value, 1),
+            Axis::Momentum | Axis::BetaGamma => text.scientific(value),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+// This module simulates a ΔE-E telescope: a thin transmission detector followed by a thick
+// stopping detector, whose (ΔE, E) correlation separates particle species ("banana plot")
+use std::io::Write;
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::Corrections;
+use super::slab::energy_after_slab;
+use super::stack::Layer;
+use super::{EnergyGrid, Material, Projectile};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Telescope {
+    // Thin ΔE detector
+    pub delta: Layer,
+    // Thick residual-energy detector behind it
+    pub stop: Layer,
+}
+
+// Energies (MeV) deposited in the two detectors over an energy scan of one species
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelescopeLocus {
+    pub species: String,
+    pub energies: Vec<f64>,
+    pub delta_e: Vec<f64>,
+    pub residual_e: Vec<f64>,
+}
+
+impl TelescopeLocus {
+    pub fn len(&self) -> usize {
+        self.energies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energies.is_empty()
+    }
+
+    // Iterate over (incident energy, ΔE, E) triples
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64, f64)> + '_ {
+        self.energies
+            .iter()
+            .zip(&self.delta_e)
+            .zip(&self.residual_e)
+            .map(|((&energy, &delta), &residual)| (energy, delta, residual))
+    }
+}
+
+impl Telescope {
+    pub fn new(delta_material: Material, delta_thickness: f64, stop_material: Material, stop_thickness: f64) -> Self {
+        Telescope {
+            delta: Layer { material: delta_material, thickness: delta_thickness },
+            stop: Layer { material: stop_material, thickness: stop_thickness },
+        }
+    }
+
+    // Energies (MeV) deposited in the ΔE and E detectors by a projectile of kinetic energy `energy`
+    // (MeV). Each detector uses its own Sternheimer coefficients.
+    pub fn response(&self, energy: f64, projectile: &Projectile, corrections: &Corrections) -> (f64, f64) {
+        let after_delta = deposit(&self.delta, energy, projectile, corrections);
+        let after_stop = deposit(&self.stop, after_delta, projectile, corrections);
+        (energy - after_delta, after_delta - after_stop)
+    }
+
+    // (ΔE, E) locus over the energy grid, keeping only the energies that reach the E detector.
+    // The responses are computed in parallel.
+    pub fn locus(&self, grid: &EnergyGrid, projectile: &Projectile, corrections: &Corrections) -> TelescopeLocus {
+        let energies = grid.energies();
+        let responses: Vec<(f64, f64)> = energies.par_iter().map(|&energy| self.response(energy, projectile, corrections)).collect();
+
+        let mut locus = TelescopeLocus { species: projectile.name.clone(), ..TelescopeLocus::default() };
+        for (energy, (delta, residual)) in energies.into_iter().zip(responses) {
+            if residual > 0.0 {
+                locus.energies.push(energy);
+                locus.delta_e.push(delta);
+                locus.residual_e.push(residual);
+            }
+        }
+        locus
+    }
+}
+
+// Exit energy (MeV) from one detector, zero if the projectile stops in it
+fn deposit(layer: &Layer, energy: f64, projectile: &Projectile, corrections: &Corrections) -> f64 {
+    if energy <= 0.0 {
+        return 0.0;
+    }
+    let corrections = Corrections { density_params: layer.material.density_correction_params(), ..*corrections };
+    energy_after_slab(projectile, &layer.material, layer.thickness, energy, &corrections).exit_energy()
+}
+
+// Write the loci as delimited "energy ΔE E" lines, one gnuplot data block per species
+pub fn write_loci<P: AsRef<Path>>(loci: &[TelescopeLocus], path: P, text: &TextFormat) -> Result<()> {
+    write_file(path, |file| {
+        for locus in loci {
+            writeln!(file, "# {}", locus.species)?;
+            writeln!(file, "# {}", text.join(&["E0 (MeV)", "dE (MeV)", "E (MeV)"]))?;
+            for (energy, delta, residual) in locus.iter() {
+                writeln!(file, "{}", text.join(&[text.scientific(energy), text.scientific(delta), text.scientific(residual)]))?;
+            }
+            writeln!(file)?;
+            writeln!(file)?;
+        }
+        Ok(())
+    })
+}
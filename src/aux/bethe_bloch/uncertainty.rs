@@ -0,0 +1,77 @@
+// This module propagates the uncertainty of the mean excitation energy I through the
+// stopping power, giving lower and upper bound curves
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{write_file, Result};
+use crate::output::{write_columns, Format, TextFormat};
+
+use super::{Material, StoppingPowerTable};
+
+// Lower and upper bounds of the stopping power at every point of a table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StoppingPowerBand {
+    pub lower: StoppingPowerTable,
+    pub upper: StoppingPowerTable,
+}
+
+impl StoppingPowerBand {
+    // Write the band as delimited "energy lower upper" lines after a header comment
+    pub fn write<P: AsRef<Path>>(&self, path: P, text: &TextFormat) -> Result<()> {
+        write_file(path, |file| {
+            let header = [self.lower.axis.to_string(), format!("lower ({})", self.lower.unit), format!("upper ({})", self.upper.unit)];
+            writeln!(file, "# {}", text.join(&header))?;
+            for ((energy, lower), (_, upper)) in self.lower.iter().zip(self.upper.iter()) {
+                writeln!(file, "{}", text.join(&[text.scientific(energy), text.scientific(lower), text.scientific(upper)]))?;
+            }
+            Ok(())
+        })
+    }
+
+    // Write the band in `format` with "energy, lower, upper" columns, CSV and JSON below
+    // `metadata`, row by row
+    pub fn write_with<P: AsRef<Path>>(&self, path: P, format: Format, text: &TextFormat, metadata: &[(&str, Value)]) -> Result<()> {
+        if format == Format::Text {
+            return self.write(path, text);
+        }
+        write_columns(path, format, metadata, &self.header(), self.rows())
+    }
+
+    // Headers and rows of the "energy, lower, upper" columns
+    pub fn columns(&self) -> (Vec<String>, Vec<Vec<f64>>) {
+        (self.header(), self.rows().collect())
+    }
+
+    // Headers of the "energy, lower, upper" columns
+    pub fn header(&self) -> Vec<String> {
+        vec![self.lower.axis.header().to_string(), format!("lower ({})", self.lower.unit), format!("upper ({})", self.upper.unit)]
+    }
+
+    // Rows of the "energy, lower, upper" columns, made as they are iterated over
+    pub fn rows(&self) -> impl Iterator<Item = Vec<f64>> + '_ {
+        self.lower.iter().zip(self.upper.iter()).map(|((energy, lower), (_, upper))| vec![energy, lower, upper])
+    }
+}
+
+// Band spanned by tabulating the material with I - ΔI and I + ΔI (ΔI = `uncertainty` eV).
+// A larger I lowers dE/dx, but the bounds are taken point by point so corrections with the
+// opposite trend are also covered.
+pub fn mean_excitation_band<F>(material: &Material, uncertainty: f64, tabulate: F) -> StoppingPowerBand
+where
+    F: Fn(&Material) -> StoppingPowerTable,
+{
+    let low_i = tabulate(&Material { mean_excitation_energy: material.mean_excitation_energy - uncertainty, ..material.clone() });
+    let high_i = tabulate(&Material { mean_excitation_energy: material.mean_excitation_energy + uncertainty, ..material.clone() });
+
+    let mut lower = low_i.clone();
+    let mut upper = low_i;
+    for ((lo, up), s) in lower.stopping_powers.iter_mut().zip(upper.stopping_powers.iter_mut()).zip(&high_i.stopping_powers) {
+        *lo = lo.min(*s);
+        *up = up.max(*s);
+    }
+
+    StoppingPowerBand { lower, upper }
+}
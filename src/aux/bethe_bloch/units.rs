@@ -0,0 +1,66 @@
+// This module provides the units stopping powers can be reported in
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::Material;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unit {
+    // Linear stopping power (MeV/cm)
+    #[default]
+    MevPerCm,
+    // Linear stopping power common in microdosimetry (keV/µm)
+    KevPerMicron,
+    // Mass stopping power (MeV cm²/g)
+    MevCm2PerG,
+}
+
+impl Unit {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::MevPerCm => "MeV/cm",
+            Unit::KevPerMicron => "keV/µm",
+            Unit::MevCm2PerG => "MeV cm²/g",
+        }
+    }
+
+    // Convert a value in MeV/cm to this unit
+    pub fn from_mev_per_cm(&self, value: f64, material: &Material) -> f64 {
+        match self {
+            Unit::MevPerCm => value,
+            Unit::KevPerMicron => value * 0.1,
+            Unit::MevCm2PerG => value / material.density,
+        }
+    }
+
+    // Convert a value in this unit to MeV/cm
+    pub fn to_mev_per_cm(&self, value: f64, material: &Material) -> f64 {
+        match self {
+            Unit::MevPerCm => value,
+            Unit::KevPerMicron => value / 0.1,
+            Unit::MevCm2PerG => value * material.density,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl FromStr for Unit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace(' ', "").as_str() {
+            "mev/cm" | "mev_cm" => Ok(Unit::MevPerCm),
+            "kev/um" | "kev/µm" | "kev_um" => Ok(Unit::KevPerMicron),
+            "mevcm2/g" | "mevcm²/g" | "mev_cm2_g" | "mass" => Ok(Unit::MevCm2PerG),
+            _ => Err(format!("Unknown unit '{}' (expected MeV/cm, keV/um or MeV cm2/g)", s)),
+        }
+    }
+}
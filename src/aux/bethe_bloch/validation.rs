@@ -0,0 +1,66 @@
+// This module compares the stopping power of each correction variant with a reference table
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{write_file, Result};
+use crate::output::TextFormat;
+
+use super::bb::{stopping_power, Corrections};
+use super::reference::ReferenceTable;
+use super::{Material, Projectile};
+
+// Relative deviation (model - reference)/reference of one correction variant at each reference energy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Validation {
+    pub label: String,
+    pub energies: Vec<f64>,
+    pub deviations: Vec<f64>,
+}
+
+impl Validation {
+    // Root mean square of the deviations
+    pub fn rms(&self) -> f64 {
+        if self.deviations.is_empty() {
+            return 0.0;
+        }
+        (self.deviations.iter().map(|d| d * d).sum::<f64>() / self.deviations.len() as f64).sqrt()
+    }
+
+    // Energy and deviation where the deviation is largest in magnitude
+    pub fn max_deviation(&self) -> Option<(f64, f64)> {
+        self.energies.iter().copied().zip(self.deviations.iter().copied()).max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+    }
+}
+
+// Deviation of each labelled correction variant from the mass stopping powers of `reference`
+pub fn validate(reference: &ReferenceTable, projectile: &Projectile, material: &Material, variants: &[(&str, Corrections)]) -> Vec<Validation> {
+    variants
+        .iter()
+        .map(|(label, corrections)| Validation {
+            label: label.to_string(),
+            energies: reference.energies.clone(),
+            deviations: reference
+                .iter()
+                .map(|(e, s)| stopping_power(e, projectile, material, corrections) / material.density / s - 1.0)
+                .collect(),
+        })
+        .collect()
+}
+
+// Write the deviations as delimited "energy reference deviation ..." lines, one column per variant
+pub fn write_validations<P: AsRef<Path>>(reference: &ReferenceTable, validations: &[Validation], path: P, text: &TextFormat) -> Result<()> {
+    write_file(path, |file| {
+        let mut header = vec![String::from("E (MeV)"), String::from("reference (MeV cm^2/g)")];
+        header.extend(validations.iter().map(|validation| validation.label.clone()));
+        writeln!(file, "# {}", text.join(&header))?;
+
+        for (i, (energy, stopping_power)) in reference.iter().enumerate() {
+            let mut fields = vec![text.scientific(energy), text.scientific(stopping_power)];
+            fields.extend(validations.iter().map(|validation| text.scientific(validation.deviations[i])));
+            writeln!(file, "{}", text.join(&fields))?;
+        }
+        Ok(())
+    })
+}
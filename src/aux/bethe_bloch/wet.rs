@@ -0,0 +1,24 @@
+// This module computes the water-equivalent thickness (WET) of a slab
+use super::bb::Corrections;
+use super::range::csda_range;
+use super::slab::energy_after_slab;
+use super::{materials, Material, Projectile};
+
+// Water-equivalent thickness (cm) of `thickness` cm of `material` for a beam of kinetic
+// energy `energy` (MeV): the water depth producing the same energy loss as the slab.
+// If the projectile stops inside the slab the WET equals its range in water.
+pub fn water_equivalent_thickness(energy: f64, projectile: &Projectile, material: &Material, thickness: f64, corrections: &Corrections) -> f64 {
+    let water = materials::water();
+    let slab_corrections = with_density_params(corrections, material);
+    let water_corrections = with_density_params(corrections, &water);
+
+    let exit_energy = energy_after_slab(projectile, material, thickness, energy, &slab_corrections).exit_energy();
+
+    csda_range(energy, projectile, &water, &water_corrections).length
+        - csda_range(exit_energy, projectile, &water, &water_corrections).length
+}
+
+// The Sternheimer coefficients are material specific
+fn with_density_params(corrections: &Corrections, material: &Material) -> Corrections {
+    Corrections { density_params: material.density_correction_params(), ..*corrections }
+}
@@ -0,0 +1,141 @@
+// This module reads run configurations from TOML or YAML files, so that a run with many
+// options can be repeated without a long command line
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{read_file, Error, Result};
+
+// Options of a run. Every one of them can also be given on the command line, which takes
+// precedence; names (materials, particles, units, ...) are those accepted there.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    pub material: Option<String>,
+    pub particle: Option<String>,
+    // Temperature (K) and pressure (atm) of a gas target
+    pub temperature: Option<f64>,
+    pub pressure: Option<f64>,
+    // Table of βγ and C/Z columns with the shell corrections of the material, see `shell::read_table`
+    pub shell_table: Option<PathBuf>,
+    // Correction names, e.g. ["density", "shell", "barkas"]
+    pub corrections: Option<Vec<String>>,
+    pub radiative: Option<bool>,
+    // Whether the curves of every stopping-power table are also drawn on a single figure, and
+    // the four variants of the corrections as the panels of another
+    pub overlay: Option<bool>,
+    pub summary: Option<bool>,
+    pub unit: Option<String>,
+    pub axis: Option<String>,
+    // Initial energy (MeV) of a Bragg curve
+    pub bragg: Option<f64>,
+    // Whether missing density-effect coefficients may be asked for on standard input
+    pub interactive: Option<bool>,
+    // Threads of the parallel computations, one per core when unset
+    pub threads: Option<u32>,
+    pub grid: GridConfig,
+    pub density: DensityConfig,
+    pub output: OutputSection,
+    pub plot: PlotSection,
+}
+
+// Energy grid of the stopping-power tables (MeV)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GridConfig {
+    pub emin: Option<f64>,
+    pub emax: Option<f64>,
+    pub points: Option<usize>,
+    pub spacing: Option<String>,
+}
+
+// Sternheimer density-effect coefficients
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DensityConfig {
+    pub a: Option<f64>,
+    pub x0: Option<f64>,
+    pub x1: Option<f64>,
+    pub c: Option<f64>,
+    pub m: Option<f64>,
+    pub delta0: Option<f64>,
+}
+
+// Where the data files and plots are written
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputSection {
+    pub dir: Option<PathBuf>,
+    // Whether the plots are drawn, and written as gnuplot scripts with their data
+    pub plots: Option<bool>,
+    pub scripts: Option<bool>,
+    // Backend drawing the plots, "plotters", "gnuplot", "terminal" or "viewer", and their format, "png", "svg",
+    // "pdf" or "eps"
+    pub backend: Option<String>,
+    pub plot_format: Option<String>,
+    // Fixed ranges [min, max] of the axes of every plot, instead of those of the data
+    pub x_range: Option<(f64, f64)>,
+    pub y_range: Option<(f64, f64)>,
+    // Points and guide lines marked on every plot, as on the command line: "x,y,label" and
+    // "value,label"
+    pub annotations: Option<Vec<String>>,
+    pub vlines: Option<Vec<String>>,
+    pub hlines: Option<Vec<String>>,
+    // Language of the labels of the plots, "es" or "en"
+    pub lang: Option<String>,
+    // Template the plot files are named after, e.g. "{particle}_{material}_{corrections}_{date}",
+    // and whether those of earlier runs are replaced
+    pub plot_name: Option<String>,
+    pub force: Option<bool>,
+    // Format of the tables, "text", "csv", "json", "hdf5", "parquet" or "root"
+    pub format: Option<String>,
+    // Decimal places and notation ("fixed" or "scientific") of every number of the text
+    // tables, and their column delimiter ("tab", "space", "comma" or the delimiter itself)
+    pub precision: Option<usize>,
+    pub notation: Option<String>,
+    pub delimiter: Option<String>,
+    // SQLite database the runs are appended to
+    pub store: Option<PathBuf>,
+    // Markup of the exported tables, "latex" or "markdown", the energies (or depths) of their
+    // rows and their significant figures
+    pub export: Option<String>,
+    pub export_at: Option<Vec<f64>>,
+    pub digits: Option<u8>,
+}
+
+// Style of the plots
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlotSection {
+    // Size [width, height] of the images in pixels at 96 dpi, and their resolution
+    pub size: Option<(u32, u32)>,
+    pub dpi: Option<u32>,
+    // Gnuplot terminal of the PNG images, "pngcairo" by default
+    pub terminal: Option<String>,
+    // Colors of the figures, "light" or "dark"
+    pub theme: Option<String>,
+    // Colors the curves take in turn, e.g. ["blue", "#e41a1c"]
+    pub palette: Option<Vec<String>>,
+    pub line_width: Option<f64>,
+    pub font: Option<String>,
+    pub font_size: Option<u32>,
+    // Corner of the legend, "top-right", "top-left", "bottom-right", "bottom-left" or "none"
+    pub legend: Option<String>,
+    pub grid: Option<bool>,
+    // Base of the logarithmic axes
+    pub log_base: Option<f64>,
+}
+
+impl RunConfig {
+    // Read a configuration file, choosing TOML or YAML from the extension
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = read_file(path)?;
+        let config = match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("toml") => toml::from_str(&text).map_err(|e| e.to_string()),
+            Some("yaml" | "yml") => serde_yaml::from_str(&text).map_err(|e| e.to_string()),
+            _ => Err(String::from("unknown configuration format (expected .toml, .yaml or .yml)")),
+        };
+        config.map_err(|message| Error::Config { path: path.to_path_buf(), message })
+    }
+}
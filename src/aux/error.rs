@@ -0,0 +1,102 @@
+// This module defines the error type shared by the library and the command line tool
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use super::output::create_dir;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    // Reading or writing a data file failed
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+
+    // Reading interactive input failed
+    #[error("unable to read standard input: {0}")]
+    Input(#[source] io::Error),
+
+    // Writing results to standard output failed
+    #[error("unable to write standard output: {0}")]
+    Output(#[source] io::Error),
+
+    // gnuplot could not be started to draw a plot
+    #[cfg(feature = "gnuplot")]
+    #[error("unable to plot '{title}' (is gnuplot installed?): {source}")]
+    Plot { title: String, source: gnuplot::GnuplotInitError },
+
+    // A plot could not be shown in a window
+    #[cfg(feature = "viewer")]
+    #[error("unable to show '{title}': {message}")]
+    View { title: String, message: String },
+
+    // A plot could not be drawn into its image
+    #[error("unable to draw {}: {message}", path.display())]
+    Draw { path: PathBuf, message: String },
+
+    // Writing a table to an HDF5 file failed
+    #[cfg(feature = "hdf5")]
+    #[error("{}: {source}", path.display())]
+    Hdf5 { path: PathBuf, source: hdf5::Error },
+
+    // Adding a run or a table to an SQLite database failed
+    #[error("{}: {source}", path.display())]
+    Store { path: PathBuf, source: rusqlite::Error },
+
+    // A plot file of an earlier run would be replaced
+    #[error("{} exists, give --force to replace it", .0.display())]
+    Exists(PathBuf),
+
+    // A configuration file could not be parsed or holds an invalid value
+    #[error("{}: {message}", path.display())]
+    Config { path: PathBuf, message: String },
+
+    // A table given to be plotted, or one of shell corrections, could not be read
+    #[error("{}: {message}", path.display())]
+    Table { path: PathBuf, message: String },
+
+    // The options or the input of a run cannot be computed, e.g. a range below the cutoff
+    #[error("{0}")]
+    Invalid(String),
+
+    #[error("unknown element symbol '{0}'")]
+    UnknownElement(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// Create `path`, and its directory when missing, and fill it with `write` through a buffer,
+// attaching the path to any I/O error. The buffer is flushed before returning, as dropping it
+// would flush it without reporting the errors.
+pub fn write_file<P, F>(path: P, write: F) -> Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+{
+    let path = path.as_ref();
+    let mut file = create_file(path)?;
+    write(&mut file).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+    finish_file(path, file)
+}
+
+// Create `path`, and its directory when missing, to be written through a buffer and closed
+// with `finish_file`
+pub fn create_file<P: AsRef<Path>>(path: P) -> Result<BufWriter<File>> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        create_dir(dir)?;
+    }
+    File::create(path).map(BufWriter::new).map_err(|source| Error::Io { path: path.to_path_buf(), source })
+}
+
+// Write out what is left in the buffer of a file created by `create_file`
+pub fn finish_file<P: AsRef<Path>>(path: P, file: BufWriter<File>) -> Result<()> {
+    file.into_inner().map(drop).map_err(|error| Error::Io { path: path.as_ref().to_path_buf(), source: error.into_error() })
+}
+
+// Read `path` to a string, attaching the path to any I/O error
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
+    let path = path.as_ref();
+    std::fs::read_to_string(path).map_err(|source| Error::Io { path: path.to_path_buf(), source })
+}
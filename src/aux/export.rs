@@ -0,0 +1,172 @@
+// This module exports tables for publications: the rows nearest to chosen energies (or
+// depths), rounded to a number of significant figures, as a LaTeX tabular or a Markdown table
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{write_file, Result};
+use crate::output::comment;
+
+// Significant figures of the exported values unless asked otherwise
+pub const DEFAULT_DIGITS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Markup {
+    Latex,
+    Markdown,
+}
+
+impl Markup {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Markup::Latex => "tex",
+            Markup::Markdown => "md",
+        }
+    }
+}
+
+impl fmt::Display for Markup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Markup::Latex => write!(f, "latex"),
+            Markup::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+impl FromStr for Markup {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "latex" | "tex" => Ok(Markup::Latex),
+            "markdown" | "md" => Ok(Markup::Markdown),
+            _ => Err(format!("Unknown markup '{}' (expected latex or markdown)", s)),
+        }
+    }
+}
+
+// Markup of the exported tables, the values of the first column (the energy, or the depth of
+// a Bragg curve) whose nearest rows are exported, every row when empty, and the significant
+// figures of the values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Export {
+    pub markup: Markup,
+    pub at: Vec<f64>,
+    pub digits: usize,
+}
+
+impl Export {
+    pub fn new(markup: Markup) -> Self {
+        Export { markup, at: Vec::new(), digits: DEFAULT_DIGITS }
+    }
+
+    // Write the selected rows of the columns named in `header`, with `metadata` as comments:
+    // a tabular of right-aligned columns between horizontal rules in LaTeX, a pipe table in
+    // Markdown
+    pub fn write<P: AsRef<Path>>(&self, path: P, metadata: &[(&str, Value)], header: &[String], rows: &[Vec<f64>]) -> Result<()> {
+        let rows = self.select(rows);
+        write_file(path, |file| match self.markup {
+            Markup::Latex => {
+                for (key, value) in metadata {
+                    writeln!(file, "% {}: {}", key, comment(value))?;
+                }
+                writeln!(file, "\\begin{{tabular}}{{{}}}", "r".repeat(header.len()))?;
+                writeln!(file, "\\hline")?;
+                let header: Vec<String> = header.iter().map(|field| latex_text(field)).collect();
+                writeln!(file, "{} \\\\", header.join(" & "))?;
+                writeln!(file, "\\hline")?;
+                for row in rows {
+                    let fields: Vec<String> = row.iter().map(|&value| self.latex_number(value)).collect();
+                    writeln!(file, "{} \\\\", fields.join(" & "))?;
+                }
+                writeln!(file, "\\hline")?;
+                writeln!(file, "\\end{{tabular}}")
+            }
+            Markup::Markdown => {
+                for (key, value) in metadata {
+                    writeln!(file, "<!-- {}: {} -->", key, comment(value))?;
+                }
+                let header: Vec<String> = header.iter().map(|field| field.replace('|', "\\|")).collect();
+                writeln!(file, "| {} |", header.join(" | "))?;
+                writeln!(file, "|{}", " ---: |".repeat(header.len()))?;
+                for row in rows {
+                    let fields: Vec<String> = row.iter().map(|&value| self.markdown_number(value)).collect();
+                    writeln!(file, "| {} |", fields.join(" | "))?;
+                }
+                Ok(())
+            }
+        })
+    }
+
+    // Rows whose first value is the nearest to one of the `at` values, in the order of the table
+    pub fn select<'a>(&self, rows: &'a [Vec<f64>]) -> Vec<&'a [f64]> {
+        if self.at.is_empty() {
+            return rows.iter().map(Vec::as_slice).collect();
+        }
+        let distance = |row: &Vec<f64>, value: f64| (row.first().copied().unwrap_or(f64::NAN) - value).abs();
+        let mut indices: Vec<usize> = self
+            .at
+            .iter()
+            .filter_map(|&value| (0..rows.len()).min_by(|&i, &j| distance(&rows[i], value).total_cmp(&distance(&rows[j], value))))
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|i| rows[i].as_slice()).collect()
+    }
+
+    fn latex_number(&self, value: f64) -> String {
+        match significant(value, self.digits) {
+            (mantissa, Some(exponent)) => format!("${} \\times 10^{{{}}}$", mantissa, exponent),
+            (number, None) => format!("${}$", number),
+        }
+    }
+
+    fn markdown_number(&self, value: f64) -> String {
+        match significant(value, self.digits) {
+            (mantissa, Some(exponent)) => format!("{} × 10<sup>{}</sup>", mantissa, exponent),
+            (number, None) => number,
+        }
+    }
+}
+
+// `value` rounded to `digits` significant figures: in fixed notation when its exponent is
+// between -3 and the number of digits, otherwise as a mantissa and a power of ten
+pub fn significant(value: f64, digits: usize) -> (String, Option<i32>) {
+    if !value.is_finite() || value == 0.0 {
+        return (value.to_string(), None);
+    }
+    let digits = digits.clamp(1, 17);
+    let scientific = format!("{:.*e}", digits - 1, value);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    if (-3..digits as i32).contains(&exponent) {
+        let decimals = (digits as i32 - 1 - exponent).max(0) as usize;
+        (format!("{:.*}", decimals, value), None)
+    } else {
+        (mantissa.to_string(), Some(exponent))
+    }
+}
+
+// Escape the characters with a meaning in LaTeX text
+fn latex_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '^' => escaped.push_str("\\^{}"),
+            '~' => escaped.push_str("\\~{}"),
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,290 @@
+// This module translates the text of the plots (axis labels, titles and legends) and of the
+// prompts of the command line: one set of labels per language, Spanish by default as the plots
+// always were
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+// Language of the labels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lang {
+    #[default]
+    Es,
+    En,
+}
+
+impl Lang {
+    pub fn labels(&self) -> &'static Labels {
+        match self {
+            Lang::Es => &SPANISH,
+            Lang::En => &ENGLISH,
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lang::Es => write!(f, "es"),
+            Lang::En => write!(f, "en"),
+        }
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "es" | "spanish" | "español" => Ok(Lang::Es),
+            "en" | "english" => Ok(Lang::En),
+            _ => Err(format!("unknown language '{}' (expected es or en)", s)),
+        }
+    }
+}
+
+// Text of one language. Labels of quantities name their unit; templates have their values
+// (the names of the target, a unit, ...) in place of each "{}", filled in with `fill`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Labels {
+    // Axes
+    pub energy: &'static str,
+    pub momentum: &'static str,
+    pub depth: &'static str,
+    pub stopping_power: &'static str,
+    pub csda_range: &'static str,
+    pub dose: &'static str,
+    pub dose_gy: &'static str,
+    pub mass_ratio: &'static str,
+    pub light: &'static str,
+    pub residual_energy: &'static str,
+    pub parameter: &'static str,
+    pub atomic_number: &'static str,
+    pub thickness: &'static str,
+    pub change: &'static str,
+    pub relative_residual: &'static str,
+    pub computed_over_reference: &'static str,
+
+    // Legends and marked points, and the target of a curve, e.g. "proton in water"
+    pub minimum_ionizing: &'static str,
+    pub bragg_peak: &'static str,
+    pub uncertainty: &'static str,
+    pub range_straggling: &'static str,
+    pub collision: &'static str,
+    pub radiative: &'static str,
+    pub unquenched: &'static str,
+    pub physical: &'static str,
+    pub rbe_weighted: &'static str,
+    pub initial: &'static str,
+    pub fitted: &'static str,
+    pub target: &'static str,
+
+    // Corrections: the curves of each variant, their names and the titles of their figures
+    pub no_corrections: &'static str,
+    pub density_correction: &'static str,
+    pub shell_correction: &'static str,
+    pub density_and_shell_corrections: &'static str,
+    pub corrections: &'static str,
+    pub density: &'static str,
+    pub shell: &'static str,
+    pub all: &'static str,
+    pub without_corrections_title: &'static str,
+    pub density_title: &'static str,
+    pub shell_title: &'static str,
+    pub density_and_shell_title: &'static str,
+    pub corrections_title: &'static str,
+
+    // Titles of the other figures
+    pub summary_title: &'static str,
+    pub stopping_power_in: &'static str,
+    pub stopping_comparison: &'static str,
+    pub range_title: &'static str,
+    pub validation_title: &'static str,
+    pub density_fit_title: &'static str,
+    pub external_comparison: &'static str,
+    pub monte_carlo_bragg: &'static str,
+    pub particle_identification: &'static str,
+    pub losses_title: &'static str,
+    pub ratio_title: &'static str,
+    pub spread_title: &'static str,
+    pub scintillation_title: &'static str,
+    pub sensitivity_title: &'static str,
+    pub sobp_title: &'static str,
+    pub stack_title: &'static str,
+    pub telescope_title: &'static str,
+    pub bragg_title: &'static str,
+    pub let_title: &'static str,
+    pub rbe_title: &'static str,
+    pub elements_title: &'static str,
+    pub thickness_title: &'static str,
+
+    // Prompts for the density-effect coefficients; `yes` is the answer accepting them besides "y"
+    pub incomplete_density: &'static str,
+    pub ask_density: &'static str,
+    pub yes: &'static str,
+    pub enter_value: &'static str,
+    pub default_density: &'static str,
+}
+
+// Replace each "{}" of a template with the next of `values`
+pub fn fill(template: &str, values: &[&str]) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (index, part) in parts.enumerate() {
+        text.push_str(values.get(index).copied().unwrap_or_default());
+        text.push_str(part);
+    }
+    text
+}
+
+pub const SPANISH: Labels = Labels {
+    energy: "Energía (MeV)",
+    momentum: "Momento (MeV/c)",
+    depth: "Profundidad (cm)",
+    stopping_power: "Poder de frenado ({})",
+    csda_range: "Alcance CSDA (cm)",
+    dose: "Dosis (MeV/cm)",
+    dose_gy: "Dosis (Gy)",
+    mass_ratio: "Cociente de poderes de frenado másicos",
+    light: "Luz de centelleo (fotones/cm)",
+    residual_energy: "E residual (MeV)",
+    parameter: "Parámetro",
+    atomic_number: "Número atómico Z",
+    thickness: "Espesor (cm)",
+    change: "Cambio en dE/dx (%)",
+    relative_residual: "Residuo relativo (%)",
+    computed_over_reference: "Calculado / referencia",
+
+    minimum_ionizing: "Mínimo de ionización",
+    bragg_peak: "Pico de Bragg",
+    uncertainty: "Incertidumbre",
+    range_straggling: "Dispersión del alcance (±σ)",
+    collision: "colisión",
+    radiative: "radiativo",
+    unquenched: "sin extinción",
+    physical: "física",
+    rbe_weighted: "ponderada por RBE",
+    initial: "Inicial",
+    fitted: "Ajustado",
+    target: "{} en {}",
+
+    no_corrections: "Sin correcciones",
+    density_correction: "Correcion Densidad",
+    shell_correction: "Correcion Capa",
+    density_and_shell_corrections: "Correciones Densidad y Capa",
+    corrections: "Correcciones {}",
+    density: "densidad",
+    shell: "capa",
+    all: "Todas",
+    without_corrections_title: "Poder de Frenado en función de la energía SIN correcciones",
+    density_title: "Poder de Frenado en función de la energía con correccion de densidad",
+    shell_title: "Poder de Frenado en función de la energía con correccion de capa",
+    density_and_shell_title: "Poder de Frenado en función de la energía con correcciones de densidad y capa",
+    corrections_title: "Poder de Frenado en función de la energía con correcciones de {}",
+
+    summary_title: "Poder de frenado de {} en {} según las correcciones",
+    stopping_power_in: "Poder de frenado en {}",
+    stopping_comparison: "Comparación de poderes de frenado",
+    range_title: "Alcance CSDA en función de la energía",
+    validation_title: "Validación frente a {}",
+    density_fit_title: "Residuos del ajuste de densidad",
+    external_comparison: "Comparación con datos externos",
+    monte_carlo_bragg: "Curva de Bragg Monte Carlo",
+    particle_identification: "Identificación de partículas en {}",
+    losses_title: "Pérdidas por colisión y radiativas",
+    ratio_title: "Cociente de poderes de frenado",
+    spread_title: "Dispersión lateral del haz",
+    scintillation_title: "Respuesta de centelleo con extinción de Birks",
+    sensitivity_title: "Sensibilidad a los parámetros de densidad",
+    sobp_title: "Pico de Bragg extendido",
+    stack_title: "Curva dosis-profundidad del apilamiento",
+    telescope_title: "Telescopio ΔE-E",
+    bragg_title: "Curva de Bragg",
+    let_title: "LET en profundidad",
+    rbe_title: "Dosis ponderada por RBE",
+    elements_title: "Poder de frenado másico de {} en los elementos",
+    thickness_title: "Poder de frenado de {} tras atravesar {}",
+
+    incomplete_density: "No se han dado en la línea de órdenes todos los parámetros de la corrección de densidad de {}.",
+    ask_density: "¿Desea introducirlos por la entrada estándar? (s/n): ",
+    yes: "s",
+    enter_value: "Introduzca el valor de {} (por defecto {}): ",
+    default_density: "Se usan los parámetros de la corrección de densidad por defecto.",
+};
+
+pub const ENGLISH: Labels = Labels {
+    energy: "Energy (MeV)",
+    momentum: "Momentum (MeV/c)",
+    depth: "Depth (cm)",
+    stopping_power: "Stopping power ({})",
+    csda_range: "CSDA range (cm)",
+    dose: "Dose (MeV/cm)",
+    dose_gy: "Dose (Gy)",
+    mass_ratio: "Mass stopping-power ratio",
+    light: "Scintillation light (photons/cm)",
+    residual_energy: "Residual E (MeV)",
+    parameter: "Parameter",
+    atomic_number: "Atomic number Z",
+    thickness: "Thickness (cm)",
+    change: "Change in dE/dx (%)",
+    relative_residual: "Relative residual (%)",
+    computed_over_reference: "Computed / reference",
+
+    minimum_ionizing: "Minimum ionizing",
+    bragg_peak: "Bragg peak",
+    uncertainty: "Uncertainty",
+    range_straggling: "Range straggling (±σ)",
+    collision: "collision",
+    radiative: "radiative",
+    unquenched: "unquenched",
+    physical: "physical",
+    rbe_weighted: "RBE-weighted",
+    initial: "Initial",
+    fitted: "Fitted",
+    target: "{} in {}",
+
+    no_corrections: "No corrections",
+    density_correction: "Density correction",
+    shell_correction: "Shell correction",
+    density_and_shell_corrections: "Density and shell corrections",
+    corrections: "{} corrections",
+    density: "density",
+    shell: "shell",
+    all: "All",
+    without_corrections_title: "Stopping power against energy without corrections",
+    density_title: "Stopping power against energy with the density correction",
+    shell_title: "Stopping power against energy with the shell correction",
+    density_and_shell_title: "Stopping power against energy with the density and shell corrections",
+    corrections_title: "Stopping power against energy with the {} corrections",
+
+    summary_title: "Stopping power of {} in {} by corrections",
+    stopping_power_in: "Stopping power in {}",
+    stopping_comparison: "Stopping-power comparison",
+    range_title: "CSDA range against energy",
+    validation_title: "Validation against {}",
+    density_fit_title: "Residuals of the density fit",
+    external_comparison: "Comparison with external data",
+    monte_carlo_bragg: "Monte Carlo Bragg curve",
+    particle_identification: "Particle identification in {}",
+    losses_title: "Collision and radiative losses",
+    ratio_title: "Stopping-power ratio",
+    spread_title: "Lateral beam spread",
+    scintillation_title: "Scintillation response with Birks quenching",
+    sensitivity_title: "Sensitivity to the density parameters",
+    sobp_title: "Spread-out Bragg peak",
+    stack_title: "Depth-dose curve of the stack",
+    telescope_title: "ΔE-E telescope",
+    bragg_title: "Bragg curve",
+    let_title: "LET against depth",
+    rbe_title: "RBE-weighted dose",
+    elements_title: "Mass stopping power of {} across the elements",
+    thickness_title: "Stopping power of {} behind {}",
+
+    incomplete_density: "Delta correction parameters for {} were not fully provided on the command line.",
+    ask_density: "Would you like to input them via standard input? (y/n): ",
+    yes: "y",
+    enter_value: "Enter value for {} (default {}): ",
+    default_density: "Using default delta correction parameters.",
+};
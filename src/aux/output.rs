@@ -0,0 +1,822 @@
+// This module decides where data files and plots are written, and in which format
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::bethe_bloch::{Corrections, EnergyGrid, Material, Projectile};
+use crate::error::{create_file, finish_file, read_file, write_file, Error, Result};
+use crate::export::Export;
+use crate::i18n::Lang;
+use crate::plotting::backend::{Backend, PlotFormat};
+use crate::plotting::chart::{Annotation, Guide};
+use crate::plotting::style::PlotStyle;
+use crate::store::Store;
+
+// Description of the run a data file comes from, as (key, value) pairs such as
+// ("material", "water") or ("points", 1000)
+pub type Metadata = Vec<(&'static str, Value)>;
+
+// File collecting every table of a run in the HDF5 format
+#[cfg(feature = "hdf5")]
+pub const HDF5_FILE: &str = "tables.h5";
+
+// Format of the tables: tab separated text under a header comment, CSV with a header row
+// below the metadata as "# key: value" comment lines, a JSON document of the metadata and
+// the result arrays, or with the features of the same names a group of the HDF5_FILE of the
+// run, an Apache Parquet file or a ROOT file of TTrees
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    #[default]
+    Text,
+    Csv,
+    Json,
+    #[cfg(feature = "hdf5")]
+    Hdf5,
+    #[cfg(feature = "parquet")]
+    Parquet,
+    #[cfg(feature = "root")]
+    Root,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Text => "txt",
+            Format::Csv => "csv",
+            Format::Json => "json",
+            #[cfg(feature = "hdf5")]
+            Format::Hdf5 => "h5",
+            #[cfg(feature = "parquet")]
+            Format::Parquet => "parquet",
+            #[cfg(feature = "root")]
+            Format::Root => "root",
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Csv => write!(f, "csv"),
+            Format::Json => write!(f, "json"),
+            #[cfg(feature = "hdf5")]
+            Format::Hdf5 => write!(f, "hdf5"),
+            #[cfg(feature = "parquet")]
+            Format::Parquet => write!(f, "parquet"),
+            #[cfg(feature = "root")]
+            Format::Root => write!(f, "root"),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" | "txt" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            #[cfg(feature = "hdf5")]
+            "hdf5" | "h5" => Ok(Format::Hdf5),
+            #[cfg(not(feature = "hdf5"))]
+            "hdf5" | "h5" => Err(String::from("HDF5 output needs a build with --features hdf5")),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(Format::Parquet),
+            #[cfg(not(feature = "parquet"))]
+            "parquet" => Err(String::from("Parquet output needs a build with --features parquet")),
+            #[cfg(feature = "root")]
+            "root" => Ok(Format::Root),
+            #[cfg(not(feature = "root"))]
+            "root" => Err(String::from("ROOT output needs a build with --features root")),
+            _ => Err(format!("Unknown format '{}' (expected text, csv, json, hdf5, parquet or root)", s)),
+        }
+    }
+}
+
+// Notation of the numbers of text tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Notation {
+    Fixed,
+    Scientific,
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Notation::Fixed => write!(f, "fixed"),
+            Notation::Scientific => write!(f, "scientific"),
+        }
+    }
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fixed" => Ok(Notation::Fixed),
+            "scientific" | "sci" => Ok(Notation::Scientific),
+            _ => Err(format!("Unknown notation '{}' (expected fixed or scientific)", s)),
+        }
+    }
+}
+
+// How text tables write their numbers and separate their columns. Each column has its own
+// notation and decimal places (energies with one decimal, stopping powers in scientific
+// notation with the digits they need, ...), which `notation` and `precision` replace when
+// given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextFormat {
+    pub notation: Option<Notation>,
+    pub precision: Option<usize>,
+    pub delimiter: String,
+}
+
+impl Default for TextFormat {
+    fn default() -> Self {
+        TextFormat { notation: None, precision: None, delimiter: String::from("\t") }
+    }
+}
+
+impl TextFormat {
+    // A number of a column in fixed notation with `decimals` places
+    pub fn fixed(&self, value: f64, decimals: usize) -> String {
+        self.number(value, Notation::Fixed, Some(decimals))
+    }
+
+    // A number of a column in scientific notation
+    pub fn scientific(&self, value: f64) -> String {
+        self.number(value, Notation::Scientific, None)
+    }
+
+    // Fields of a line, separated by the delimiter
+    pub fn join<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        fields.iter().map(AsRef::as_ref).collect::<Vec<_>>().join(&self.delimiter)
+    }
+
+    // A number of a column in `notation` with `precision` decimal places, or as many as needed
+    pub fn number(&self, value: f64, notation: Notation, precision: Option<usize>) -> String {
+        match (self.notation.unwrap_or(notation), self.precision.or(precision)) {
+            (Notation::Fixed, Some(precision)) => format!("{:.*}", precision, value),
+            (Notation::Fixed, None) => value.to_string(),
+            (Notation::Scientific, Some(precision)) => format!("{:.*e}", precision, value),
+            (Notation::Scientific, None) => format!("{:e}", value),
+        }
+    }
+}
+
+// How plot files are named: after a template whose {title}, {particle}, {material},
+// {corrections} and {date} are replaced (by letters, digits and underscores, and the date as
+// YYYY-MM-DD), or after the title in letters, digits and underscores without a template. A template without {title} is
+// followed by it, so that the figures of a run do not share a name. Plots that are not of a
+// single selection of corrections have no `corrections`, written "default".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlotNaming {
+    pub template: Option<String>,
+    pub particle: String,
+    pub material: String,
+    pub corrections: String,
+}
+
+impl PlotNaming {
+    // File name of the plot with the given title, without extension
+    pub fn stem(&self, title: &str) -> String {
+        let Some(template) = &self.template else { return slug(title) };
+        let corrections = if self.corrections.is_empty() { "default" } else { &self.corrections };
+        let template = if template.contains("{title}") { template.clone() } else { format!("{}_{{title}}", template) };
+        template
+            .replace("{title}", &slug(title))
+            .replace("{particle}", &slug(&self.particle))
+            .replace("{material}", &slug(&self.material))
+            .replace("{corrections}", &slug(corrections))
+            .replace("{date}", &today())
+    }
+}
+
+// Directory receiving the data files and plots (`output` by default), whether the plots are
+// drawn at all, by which backend, in which format, on which fixed ranges, in which style and
+// language, with which axis labels instead of those of each plot and which points and guide
+// lines marked on every plot besides their own, whether tables are also
+// printed point by point on standard output, the format of the tables, the database the tables
+// are also added to, their LaTeX or Markdown export, whether the figures are also written as
+// gnuplot scripts, how text tables write numbers, how plot files are named and whether those of
+// earlier runs may be replaced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputConfig {
+    pub dir: PathBuf,
+    pub plots: bool,
+    pub backend: Backend,
+    pub plot_format: PlotFormat,
+    pub x_range: Option<(f64, f64)>,
+    pub y_range: Option<(f64, f64)>,
+    pub style: PlotStyle,
+    pub lang: Lang,
+    pub x_label: Option<String>,
+    pub y_label: Option<String>,
+    pub annotations: Vec<Annotation>,
+    pub guides: Vec<Guide>,
+    pub stdout: bool,
+    pub format: Format,
+    #[serde(skip)]
+    pub store: Option<Store>,
+    pub export: Option<Export>,
+    pub scripts: bool,
+    pub text: TextFormat,
+    pub naming: PlotNaming,
+    pub force: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        OutputConfig::new("output")
+    }
+}
+
+impl OutputConfig {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        OutputConfig {
+            dir: dir.into(),
+            plots: true,
+            backend: Backend::default(),
+            plot_format: PlotFormat::default(),
+            x_range: None,
+            y_range: None,
+            style: PlotStyle::default(),
+            lang: Lang::default(),
+            x_label: None,
+            y_label: None,
+            annotations: Vec::new(),
+            guides: Vec::new(),
+            stdout: false,
+            format: Format::default(),
+            store: None,
+            export: None,
+            scripts: false,
+            text: TextFormat::default(),
+            naming: PlotNaming::default(),
+            force: false,
+        }
+    }
+
+    // Create the directory and any missing parents
+    pub fn create(&self) -> Result<()> {
+        create_dir(&self.dir)
+    }
+
+    // Path of a data file inside the directory
+    pub fn path(&self, file_name: &str) -> PathBuf {
+        self.dir.join(file_name)
+    }
+
+    // Path of a table named `stem`, with the extension of the format. HDF5 tables are groups
+    // of a single file, so theirs is the path of the group below the file.
+    pub fn table_path(&self, stem: &str) -> PathBuf {
+        match self.format {
+            #[cfg(feature = "hdf5")]
+            Format::Hdf5 => self.path(HDF5_FILE).join(stem),
+            _ => self.path(&format!("{}.{}", stem, self.format.extension())),
+        }
+    }
+
+    // Add the table `name` with its metadata and the (header, rows) of `columns` to the
+    // database and write its LaTeX or Markdown export, when asked for
+    pub fn record<F>(&self, name: &str, metadata: &[(&str, Value)], columns: F) -> Result<()>
+    where
+        F: FnOnce() -> (Vec<String>, Vec<Vec<f64>>),
+    {
+        if self.store.is_none() && self.export.is_none() {
+            return Ok(());
+        }
+        let (header, rows) = columns();
+        if let Some(store) = &self.store {
+            store.add_table(name, metadata, &header, &rows)?;
+        }
+        if let Some(export) = &self.export {
+            export.write(self.path(&format!("{}.{}", name, export.markup.extension())), metadata, &header, &rows)?;
+        }
+        Ok(())
+    }
+
+    // Path of the image of a plot with the given title, in the format of the plots
+    pub fn plot_path(&self, title: &str) -> String {
+        self.plot_file(title, self.plot_format.extension()).to_string_lossy().into_owned()
+    }
+
+    // Path of a file of a plot with the given title, named as the image with another extension
+    pub fn plot_file(&self, title: &str, extension: &str) -> PathBuf {
+        self.path(&format!("{}.{}", self.naming.stem(title), extension))
+    }
+
+    // Files of the table named `stem`: the table in the output format and its LaTeX or Markdown
+    // export, when asked for
+    pub fn table_files(&self, stem: &str) -> Vec<PathBuf> {
+        let mut files = vec![self.table_path(stem)];
+        files.extend(self.export.as_ref().map(|export| self.path(&format!("{}.{}", stem, export.markup.extension()))));
+        files
+    }
+
+    // Files of the plot with the given title: the image, unless the plots are skipped or drawn
+    // on the standard output, and the gnuplot script and data of --scripts
+    pub fn plot_files(&self, title: &str) -> Vec<PathBuf> {
+        let mut extensions = Vec::new();
+        if self.plots && self.backend.writes_images() {
+            extensions.push(self.plot_format.extension());
+        }
+        if self.scripts {
+            extensions.extend(["plt", "dat"]);
+        }
+        extensions.into_iter().map(|extension| self.plot_file(title, extension)).collect()
+    }
+
+    // Refuse to replace the file of an earlier run, unless forced to
+    pub fn check_new(&self, path: &Path) -> Result<()> {
+        if !self.force && path.exists() {
+            return Err(Error::Exists(path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    // Refuse to replace any of the files of an earlier run, checked before a run writes the first
+    // of them so that it does not stop halfway
+    pub fn check_new_files<I: IntoIterator<Item = PathBuf>>(&self, paths: I) -> Result<()> {
+        paths.into_iter().try_for_each(|path| self.check_new(&path))
+    }
+}
+
+// Lower-case letters, digits and single underscores for the other characters of `text`, with
+// the marks of accented letters dropped
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.to_lowercase().chars() {
+        let c = match c {
+            'á' | 'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ñ' => 'n',
+            'ç' => 'c',
+            c => c,
+        };
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_end_matches('_').to_string()
+}
+
+// Today's date (UTC) as YYYY-MM-DD, from the days since 1970-01-01 by H. Hinnant's
+// civil_from_days
+fn today() -> String {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs() / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + 400 * era + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// Description of the run a table comes from: the projectile, the material and the corrections
+pub fn metadata(projectile: &Projectile, material: &Material, corrections: &Corrections) -> Metadata {
+    vec![
+        ("generator", json!(format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")))),
+        ("projectile", json!(projectile.name)),
+        ("material", json!(material.name)),
+        ("density_g_cm3", json!(material.density)),
+        ("mean_excitation_energy_ev", json!(material.mean_excitation_energy)),
+        ("corrections", json!(corrections.names())),
+        ("radiative", json!(corrections.radiative)),
+    ]
+}
+
+// Description of a table tabulated on an energy grid
+pub fn grid_metadata(projectile: &Projectile, material: &Material, corrections: &Corrections, grid: &EnergyGrid) -> Metadata {
+    let mut metadata = metadata(projectile, material, corrections);
+    metadata.extend([
+        ("emin_mev", json!(grid.min)),
+        ("emax_mev", json!(grid.max)),
+        ("points", json!(grid.points)),
+        ("spacing", json!(grid.spacing.to_string())),
+    ]);
+    metadata
+}
+
+// Create `dir` and any missing parents, attaching the path to any I/O error
+pub fn create_dir<P: AsRef<Path>>(dir: P) -> Result<()> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir).map_err(|source| Error::Io { path: dir.to_path_buf(), source })
+}
+
+// Write the columns named in `header`, one row per record, below `metadata`: as "# key: value"
+// comment lines and a header row in CSV, as the "metadata" and "results" objects of a JSON
+// document (the results holding an array per column), as a group of datasets in HDF5, as the
+// double columns and key-value metadata of a Parquet file, as the branches of a ROOT TTree or
+// as tab separated text under a header comment
+pub fn write_columns<P, R>(path: P, format: Format, metadata: &[(&str, Value)], header: &[String], rows: impl IntoIterator<Item = R>) -> Result<()>
+where
+    P: AsRef<Path>,
+    R: AsRef<[f64]>,
+{
+    let mut writer = ColumnWriter::create(path, format, metadata, header)?;
+    for row in rows {
+        writer.row(row.as_ref())?;
+    }
+    writer.finish()
+}
+
+// Writer of the columns of `write_columns` taking a row at a time. Text and CSV rows go
+// through a buffer to the file as they come; the other formats hold whole columns, so their
+// rows are gathered and written by `finish`. Only a finished table is complete, and a writer
+// dropped without `finish` logs an error.
+pub struct ColumnWriter<'a> {
+    path: PathBuf,
+    format: Format,
+    metadata: &'a [(&'a str, Value)],
+    header: &'a [String],
+    // None once finished, or once a row could not be written
+    sink: Option<Sink>,
+}
+
+enum Sink {
+    File(BufWriter<File>),
+    Rows(Vec<Vec<f64>>),
+}
+
+impl<'a> ColumnWriter<'a> {
+    // Create the file of a text or CSV table and write what comes before the rows
+    pub fn create<P: AsRef<Path>>(path: P, format: Format, metadata: &'a [(&'a str, Value)], header: &'a [String]) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let sink = match format {
+            Format::Text | Format::Csv => {
+                let mut file = create_file(&path)?;
+                let mut write = || -> std::io::Result<()> {
+                    if format == Format::Text {
+                        return writeln!(file, "# {}", header.join("\t"));
+                    }
+                    for (key, value) in metadata {
+                        writeln!(file, "# {}: {}", key, comment(value))?;
+                    }
+                    let header: Vec<String> = header.iter().map(|field| csv_field(field)).collect();
+                    writeln!(file, "{}", header.join(","))
+                };
+                write().map_err(|source| Error::Io { path: path.clone(), source })?;
+                Sink::File(file)
+            }
+            _ => Sink::Rows(Vec::new()),
+        };
+        Ok(ColumnWriter { path, format, metadata, header, sink: Some(sink) })
+    }
+
+    pub fn row(&mut self, row: &[f64]) -> Result<()> {
+        match &mut self.sink {
+            Some(Sink::File(file)) => {
+                let (fields, delimiter): (Vec<String>, &str) = match self.format {
+                    Format::Csv => (row.iter().map(|value| value.to_string()).collect(), ","),
+                    _ => (row.iter().map(|value| format!("{:e}", value)).collect(), "\t"),
+                };
+                writeln!(file, "{}", fields.join(delimiter)).map_err(|source| {
+                    self.sink = None;
+                    Error::Io { path: self.path.clone(), source }
+                })
+            }
+            Some(Sink::Rows(rows)) => {
+                rows.push(row.to_vec());
+                Ok(())
+            }
+            None => Err(self.unwritten()),
+        }
+    }
+
+    // Flush the text of the file, or write the gathered rows in their format
+    pub fn finish(mut self) -> Result<()> {
+        let rows = match self.sink.take() {
+            Some(Sink::File(file)) => return finish_file(&self.path, file),
+            Some(Sink::Rows(rows)) => rows,
+            None => return Err(self.unwritten()),
+        };
+        let (path, metadata, header) = (self.path.as_path(), self.metadata, self.header);
+        match self.format {
+            Format::Json => {
+                let columns = columns(header.len(), rows);
+                let metadata: Map<String, Value> = metadata.iter().map(|(key, value)| (key.to_string(), value.clone())).collect();
+                let results: Map<String, Value> = header.iter().cloned().zip(columns.into_iter().map(Value::from)).collect();
+                let document = json!({ "metadata": metadata, "results": results });
+                write_file(path, |file| {
+                    serde_json::to_writer_pretty(&mut *file, &document)?;
+                    writeln!(file)
+                })
+            }
+            #[cfg(feature = "hdf5")]
+            Format::Hdf5 => write_hdf5(path, metadata, header, rows),
+            #[cfg(feature = "parquet")]
+            Format::Parquet => {
+                let columns = columns(header.len(), rows);
+                write_file(path, |file| write_parquet(file, metadata, header, &columns).map_err(std::io::Error::other))
+            }
+            #[cfg(feature = "root")]
+            Format::Root => write_root(path, metadata, header, rows),
+            // Text and CSV rows went to the file as they came
+            Format::Text | Format::Csv => Ok(()),
+        }
+    }
+
+    // Error of a table whose earlier row could not be written
+    fn unwritten(&self) -> Error {
+        Error::Io { path: self.path.clone(), source: std::io::Error::other("an earlier row could not be written") }
+    }
+}
+
+impl Drop for ColumnWriter<'_> {
+    fn drop(&mut self) {
+        if self.sink.is_some() {
+            log::error!("{} was left incomplete, as its table was not finished", self.path.display());
+        }
+    }
+}
+
+// Write the columns as the datasets of a group, `path` being the path of the group below the
+// HDF5 file (created, or added to when it exists). The group holds the metadata as attributes
+// and each dataset, named after the quantity of its header, the unit as an attribute.
+#[cfg(feature = "hdf5")]
+fn write_hdf5<R: AsRef<[f64]>>(path: &Path, metadata: &[(&str, Value)], header: &[String], rows: impl IntoIterator<Item = R>) -> Result<()> {
+    use hdf5::types::VarLenUnicode;
+
+    let (file_path, name) = (path.parent().unwrap_or(path), path.file_name().unwrap_or_default().to_string_lossy());
+    if let Some(dir) = file_path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        create_dir(dir)?;
+    }
+    let text = |value: &str| value.parse::<VarLenUnicode>().map_err(|error| hdf5::Error::from(error.to_string()));
+    let columns = columns(header.len(), rows);
+
+    let write = || -> hdf5::Result<()> {
+        let file = if file_path.exists() { hdf5::File::append(file_path)? } else { hdf5::File::create(file_path)? };
+        // A table written again, e.g. by a --watch run, replaces the previous one
+        if file.link_exists(&name) {
+            file.unlink(&name)?;
+        }
+        let group = file.create_group(&name)?;
+        for (key, value) in metadata {
+            match value {
+                Value::Number(number) => group.new_attr::<f64>().create(*key)?.write_scalar(&number.as_f64().unwrap_or(f64::NAN))?,
+                Value::Bool(flag) => group.new_attr::<bool>().create(*key)?.write_scalar(flag)?,
+                value => group.new_attr::<VarLenUnicode>().create(*key)?.write_scalar(&text(&comment(value))?)?,
+            }
+        }
+        for (label, column) in header.iter().zip(&columns) {
+            // "dE/dx (MeV/cm)" is the dataset dE_dx with the unit MeV/cm, as / separates HDF5 paths
+            let (quantity, unit) = quantity_unit(label);
+            let dataset = group.new_dataset_builder().with_data(column.as_slice()).create(quantity.replace('/', "_").as_str())?;
+            dataset.new_attr::<VarLenUnicode>().create("unit")?.write_scalar(&text(unit)?)?;
+        }
+        Ok(())
+    };
+    write().map_err(|source| Error::Hdf5 { path: file_path.to_path_buf(), source })
+}
+
+// Write the columns as one row group of required doubles named after the header, and the
+// metadata as the key-value metadata of the file
+#[cfg(feature = "parquet")]
+fn write_parquet(file: &mut BufWriter<File>, metadata: &[(&str, Value)], header: &[String], columns: &[Vec<f64>]) -> parquet::errors::Result<()> {
+    use std::sync::Arc;
+
+    use parquet::basic::{Repetition, Type as PhysicalType};
+    use parquet::data_type::DoubleType;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::format::KeyValue;
+    use parquet::schema::types::Type;
+
+    let fields = header
+        .iter()
+        .map(|name| Type::primitive_type_builder(name, PhysicalType::DOUBLE).with_repetition(Repetition::REQUIRED).build().map(Arc::new))
+        .collect::<parquet::errors::Result<Vec<_>>>()?;
+    let schema = Arc::new(Type::group_type_builder("table").with_fields(fields).build()?);
+    let key_values = metadata.iter().map(|(key, value)| KeyValue::new(key.to_string(), comment(value))).collect();
+    let properties = Arc::new(WriterProperties::builder().set_key_value_metadata(Some(key_values)).build());
+
+    let mut writer = SerializedFileWriter::new(file, schema, properties)?;
+    let mut row_group = writer.next_row_group()?;
+    for column in columns {
+        if let Some(mut writer) = row_group.next_column()? {
+            writer.typed::<DoubleType>().write_batch(column, None, None)?;
+            writer.close()?;
+        }
+    }
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+// Write the columns as the branches of a TTree named after the file, "dE/dx (MeV/cm)" being
+// the branch dE_dx, and the metadata and the unit of each branch as the string branches key
+// and value of a second TTree, "metadata". Graphs are drawn from the tree, e.g. with
+// tree->Draw("dE_dx:E").
+#[cfg(feature = "root")]
+fn write_root<R: AsRef<[f64]>>(path: &Path, metadata: &[(&str, Value)], header: &[String], rows: impl IntoIterator<Item = R>) -> Result<()> {
+    use oxyroot::{RootFile, WriterTree};
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        create_dir(dir)?;
+    }
+    let mut table = WriterTree::new(path.file_stem().unwrap_or_default().to_string_lossy());
+    let mut entries: Vec<(String, String)> = metadata.iter().map(|(key, value)| (key.to_string(), comment(value))).collect();
+    for (label, column) in header.iter().zip(columns(header.len(), rows)) {
+        let (quantity, unit) = quantity_unit(label);
+        let branch: String = quantity.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+        entries.push((format!("{}_unit", branch), unit.to_string()));
+        table.new_branch(branch, column.into_iter());
+    }
+    let mut tree = WriterTree::new("metadata");
+    let (keys, values): (Vec<String>, Vec<String>) = entries.into_iter().unzip();
+    tree.new_branch("key", keys.into_iter());
+    tree.new_branch("value", values.into_iter());
+
+    let mut write = || -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut file = RootFile::create(path)?;
+        table.write(&mut file)?;
+        tree.write(&mut file)?;
+        file.close()?;
+        Ok(())
+    };
+    write().map_err(|error| Error::Io { path: path.to_path_buf(), source: std::io::Error::other(error) })
+}
+
+// Quantity and unit of a column header such as "dE/dx (MeV/cm)"
+#[cfg(any(feature = "hdf5", feature = "root"))]
+fn quantity_unit(label: &str) -> (&str, &str) {
+    label.rsplit_once(" (").map_or((label, ""), |(quantity, unit)| (quantity, unit.strip_suffix(')').unwrap_or(unit)))
+}
+
+// Split records of `width` values into one vector per column
+fn columns<R: AsRef<[f64]>>(width: usize, rows: impl IntoIterator<Item = R>) -> Vec<Vec<f64>> {
+    let mut columns = vec![Vec::new(); width];
+    for row in rows {
+        for (column, value) in columns.iter_mut().zip(row.as_ref()) {
+            column.push(*value);
+        }
+    }
+    columns
+}
+
+// Metadata value of a comment line: strings unquoted and lists comma separated ("none" when empty)
+pub(crate) fn comment(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) if items.is_empty() => String::from("none"),
+        Value::Array(items) => items.iter().map(comment).collect::<Vec<_>>().join(","),
+        value => value.to_string(),
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Columns of a table written by an earlier run, named after its header (empty when the table
+// has none), with the metadata of CSV and JSON tables
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    pub metadata: Map<String, Value>,
+    pub header: Vec<String>,
+    pub columns: Vec<Vec<f64>>,
+}
+
+impl Table {
+    // Read a JSON document of the metadata and results, or a text or CSV table, told apart by
+    // the extension of `path`
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = read_file(path)?;
+        let extension = path.extension().map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+        let table = match extension.as_deref() {
+            Some("json") => Table::parse_json(&text),
+            _ => Ok(Table::parse(&text)),
+        };
+        table
+            .and_then(|table| if table.columns.first().is_some_and(|column| !column.is_empty()) { Ok(table) } else { Err(String::from("no rows of numbers")) })
+            .map_err(|message| Error::Table { path: path.to_path_buf(), message })
+    }
+
+    // Parse text or CSV lines: rows of numbers separated by tabs, commas, semicolons or spaces,
+    // "# key: value" comment lines of metadata, and a header as the last other comment or line
+    // before the rows
+    pub fn parse(text: &str) -> Self {
+        let mut table = Table::default();
+        let mut header = None;
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(comment) = line.strip_prefix('#').map(str::trim) {
+                match comment.split_once(": ").filter(|(key, _)| is_key(key)) {
+                    Some((key, value)) => {
+                        table.metadata.insert(key.to_string(), Value::String(value.to_string()));
+                    }
+                    None if table.columns.is_empty() => header = Some(comment),
+                    None => {}
+                }
+                continue;
+            }
+            let fields: Vec<&str> = line.split(|c: char| c.is_whitespace() || c == ',' || c == ';').filter(|field| !field.is_empty()).collect();
+            match fields.iter().map(|field| field.parse::<f64>()).collect::<std::result::Result<Vec<f64>, _>>() {
+                Ok(row) => {
+                    if table.columns.is_empty() {
+                        table.columns = vec![Vec::new(); row.len()];
+                    }
+                    for (column, value) in table.columns.iter_mut().zip(row) {
+                        column.push(value);
+                    }
+                }
+                Err(_) if table.columns.is_empty() => header = Some(line),
+                Err(_) => {}
+            }
+        }
+        table.header = header.map(|header| header_fields(header, table.columns.len())).unwrap_or_default();
+        table
+    }
+
+    // Parse the "metadata" and "results" objects of a JSON document, nulls being NaN
+    pub fn parse_json(text: &str) -> std::result::Result<Self, String> {
+        let document: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+        let results = document.get("results").and_then(Value::as_object).ok_or("no \"results\" object")?;
+        let metadata = document.get("metadata").and_then(Value::as_object).cloned().unwrap_or_default();
+        let mut table = Table { metadata, ..Table::default() };
+        for (name, values) in results {
+            let values = values.as_array().ok_or_else(|| format!("\"{}\" is not an array", name))?;
+            table.header.push(name.clone());
+            table.columns.push(values.iter().map(|value| value.as_f64().unwrap_or(f64::NAN)).collect());
+        }
+        Ok(table)
+    }
+
+    // Metadata value of `key` as text, lists comma separated
+    pub fn metadata_text(&self, key: &str) -> Option<String> {
+        self.metadata.get(key).map(comment)
+    }
+}
+
+// Metadata keys are lower-case words joined by underscores, unlike the headers of text tables
+fn is_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// Names of the `width` columns of a header line: separated by tabs, commas (quoted as in CSV),
+// semicolons or spaces, a unit in parentheses staying with its quantity. Headers of another
+// width are dropped.
+fn header_fields(line: &str, width: usize) -> Vec<String> {
+    let splits: [fn(&str) -> Vec<String>; 4] = [
+        |line| line.split('\t').map(|field| field.trim().to_string()).collect(),
+        csv_fields,
+        |line| line.split(';').map(|field| field.trim().to_string()).collect(),
+        |line| {
+            let mut fields: Vec<String> = Vec::new();
+            for word in line.split_whitespace() {
+                match fields.last_mut() {
+                    Some(field) if word.starts_with('(') => {
+                        field.push(' ');
+                        field.push_str(word);
+                    }
+                    _ => fields.push(word.to_string()),
+                }
+            }
+            fields
+        },
+    ];
+    splits.iter().map(|split| split(line)).find(|fields| fields.len() == width).unwrap_or_default()
+}
+
+// Fields of a CSV line, unquoting those written by `csv_field`
+fn csv_fields(line: &str) -> Vec<String> {
+    let (mut fields, mut field, mut quoted) = (Vec::new(), String::new(), false);
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
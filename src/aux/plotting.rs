@@ -1 +1,11 @@
-pub mod plot;
\ No newline at end of file
+pub mod backend;
+pub mod chart;
+#[cfg(feature = "gnuplot")]
+pub mod gnuplot;
+pub mod plot;
+pub mod plotters;
+pub mod style;
+pub mod terminal;
+pub mod vector;
+#[cfg(feature = "viewer")]
+pub mod viewer;
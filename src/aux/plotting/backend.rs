@@ -0,0 +1,140 @@
+// This module defines the backends the charts are drawn with: plotters, in pure Rust, gnuplot,
+// an external program, in builds with the gnuplot feature, text on the standard output, or
+// windows in builds with the viewer feature; and the formats of the images they draw
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::chart::Chart;
+use super::style::PlotStyle;
+use crate::error::Result;
+
+// Something that draws a chart into an image file
+pub trait PlotBackend {
+    // Draw `chart` in `style` into `path`, in the format of its extension (a PNG image when it
+    // is none of the others)
+    fn draw(&self, chart: &Chart, style: &PlotStyle, path: &Path) -> Result<()>;
+}
+
+// Backend of the plots, plotters by default as it needs no other program
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    #[default]
+    Plotters,
+    #[cfg(feature = "gnuplot")]
+    Gnuplot,
+    Terminal,
+    #[cfg(feature = "viewer")]
+    Viewer,
+}
+
+impl Backend {
+    pub fn renderer(&self) -> &'static dyn PlotBackend {
+        match self {
+            Backend::Plotters => &super::plotters::Plotters,
+            #[cfg(feature = "gnuplot")]
+            Backend::Gnuplot => &super::gnuplot::Gnuplot,
+            Backend::Terminal => &super::terminal::Terminal,
+            #[cfg(feature = "viewer")]
+            Backend::Viewer => &super::viewer::Viewer,
+        }
+    }
+
+    // Whether the plots are image files, rather than drawn on the standard output or shown
+    pub fn writes_images(&self) -> bool {
+        match self {
+            Backend::Terminal => false,
+            #[cfg(feature = "viewer")]
+            Backend::Viewer => false,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Backend::Plotters => write!(f, "plotters"),
+            #[cfg(feature = "gnuplot")]
+            Backend::Gnuplot => write!(f, "gnuplot"),
+            Backend::Terminal => write!(f, "terminal"),
+            #[cfg(feature = "viewer")]
+            Backend::Viewer => write!(f, "viewer"),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plotters" => Ok(Backend::Plotters),
+            #[cfg(feature = "gnuplot")]
+            "gnuplot" => Ok(Backend::Gnuplot),
+            #[cfg(not(feature = "gnuplot"))]
+            "gnuplot" => Err(String::from("gnuplot plots need a build with --features gnuplot")),
+            "terminal" => Ok(Backend::Terminal),
+            #[cfg(feature = "viewer")]
+            "viewer" => Ok(Backend::Viewer),
+            #[cfg(not(feature = "viewer"))]
+            "viewer" => Err(String::from("the plot viewer needs a build with --features viewer")),
+            _ => Err(format!("unknown plot backend '{}' (expected plotters, gnuplot, terminal or viewer)", s)),
+        }
+    }
+}
+
+// Format of the images: PNG, or SVG, PDF and EPS documents of vector graphics (e.g. for LaTeX)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlotFormat {
+    #[default]
+    Png,
+    Svg,
+    Pdf,
+    Eps,
+}
+
+impl PlotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PlotFormat::Png => "png",
+            PlotFormat::Svg => "svg",
+            PlotFormat::Pdf => "pdf",
+            PlotFormat::Eps => "eps",
+        }
+    }
+
+    // Format of an image named with the extension of a format, PNG otherwise
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("svg") => PlotFormat::Svg,
+            Some("pdf") => PlotFormat::Pdf,
+            Some("eps") => PlotFormat::Eps,
+            _ => PlotFormat::Png,
+        }
+    }
+}
+
+impl fmt::Display for PlotFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl FromStr for PlotFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Ok(PlotFormat::Png),
+            "svg" => Ok(PlotFormat::Svg),
+            "pdf" => Ok(PlotFormat::Pdf),
+            "eps" => Ok(PlotFormat::Eps),
+            _ => Err(format!("unknown plot format '{}' (expected png, svg, pdf or eps)", s)),
+        }
+    }
+}
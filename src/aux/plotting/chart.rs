@@ -0,0 +1,333 @@
+// This module describes figures independently of the backend drawing them: a chart is one or
+// more panels of axes, each with its labels, scales and series of points, or a color map
+use serde::{Deserialize, Serialize};
+
+// Room left around the data by the ranges of the axes that are not fixed, as a fraction of
+// their span (of that of their logarithm on logarithmic axes) on each side
+pub const PADDING: f64 = 0.05;
+
+// A figure, named after its title, of panels drawn over the same image
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chart {
+    pub title: String,
+    pub panels: Vec<Panel>,
+}
+
+impl Chart {
+    // A figure of a single panel, titled as the figure
+    pub fn single(panel: Panel) -> Self {
+        Chart { title: panel.title.clone(), panels: vec![panel] }
+    }
+}
+
+// Axes with their title, labels and series, and the points and lines marked over them, above a
+// color map when there is one. `area` is the part of the image they take, all of it when
+// missing; `x_ticks` are labelled ticks replacing the automatic ones, whose labels are left out
+// when `x_tick_labels` is false (e.g. above a panel sharing the abscissa).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Panel {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub x_axis: Axis,
+    pub y_axis: Axis,
+    pub x_ticks: Vec<(f64, String)>,
+    pub x_tick_labels: bool,
+    pub area: Option<Area>,
+    pub series: Vec<Series>,
+    pub annotations: Vec<Annotation>,
+    pub guides: Vec<Guide>,
+    pub heatmap: Option<Heatmap>,
+}
+
+impl Panel {
+    pub fn new(title: &str, x_label: &str, y_label: &str) -> Self {
+        Panel {
+            title: title.to_string(),
+            x_label: x_label.to_string(),
+            y_label: y_label.to_string(),
+            x_axis: Axis::default(),
+            y_axis: Axis::default(),
+            x_ticks: Vec::new(),
+            x_tick_labels: true,
+            area: None,
+            series: Vec::new(),
+            annotations: Vec::new(),
+            guides: Vec::new(),
+            heatmap: None,
+        }
+    }
+
+    // Fixed range of the abscissa, or that of the series and marks with some padding (around the
+    // labelled ticks, of boxes, without it), or that of the cells of the color map
+    pub fn x_range(&self) -> (f64, f64) {
+        if let Some(range) = self.x_axis.range {
+            return range;
+        }
+        if let Some(heatmap) = &self.heatmap {
+            let log = self.x_axis.log_base.is_some();
+            return extent(heatmap.x_edges(log).into_iter(), log, 0.0);
+        }
+        if !self.x_ticks.is_empty() {
+            let (min, max) = extent(self.x_ticks.iter().map(|(x, _)| *x), false, 0.0);
+            return (min - 0.5, max + 0.5);
+        }
+        let values = self.series.iter().flat_map(|series| series.xs.iter().copied())
+            .chain(self.annotations.iter().map(|annotation| annotation.x))
+            .chain(self.guides.iter().filter(|guide| guide.direction == Direction::Vertical).map(|guide| guide.value));
+        extent(values, self.x_axis.log_base.is_some(), PADDING)
+    }
+
+    // Fixed range of the ordinate, or that of the series and marks with some padding, from zero
+    // with boxes, or that of the cells of the color map
+    pub fn y_range(&self) -> (f64, f64) {
+        if let Some(range) = self.y_axis.range {
+            return range;
+        }
+        if let Some(heatmap) = &self.heatmap {
+            let log = self.y_axis.log_base.is_some();
+            return extent(heatmap.y_edges(log).into_iter(), log, 0.0);
+        }
+        let mut values: Vec<f64> = self.annotations.iter().map(|annotation| annotation.y).collect();
+        values.extend(self.guides.iter().filter(|guide| guide.direction == Direction::Horizontal).map(|guide| guide.value));
+        for series in &self.series {
+            values.extend(&series.ys);
+            match &series.style {
+                Style::Band { upper, .. } => values.extend(upper),
+                Style::Boxes { .. } => values.push(0.0),
+                _ => {}
+            }
+        }
+        extent(values.into_iter(), self.y_axis.log_base.is_some(), PADDING)
+    }
+}
+
+// Smallest and largest finite values (positive ones on logarithmic axes) widened by `padding`
+// of their span, or by a factor of 2 (by 1 on linear axes) around a single value, and from 1
+// to 10 without any
+fn extent(values: impl Iterator<Item = f64>, log: bool, padding: f64) -> (f64, f64) {
+    let (min, max) = values
+        .filter(|value| value.is_finite() && (!log || *value > 0.0))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+    match (min, max) {
+        (min, max) if min < max && log => {
+            let factor = (max / min).powf(padding);
+            (min / factor, max * factor)
+        }
+        (min, max) if min < max => (min - padding * (max - min), max + padding * (max - min)),
+        (value, _) if value.is_finite() && log => (value / 2.0, value * 2.0),
+        (value, _) if value.is_finite() => (value - 1.0, value + 1.0),
+        _ => (1.0, 10.0),
+    }
+}
+
+// Scale of an axis: logarithmic of base `log_base` when given, and fixed to `range` (otherwise
+// that of the data)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Axis {
+    pub log_base: Option<f64>,
+    pub range: Option<(f64, f64)>,
+}
+
+impl Axis {
+    pub fn log(base: f64) -> Self {
+        Axis { log_base: Some(base), range: None }
+    }
+}
+
+// Fractions of the image width (left, right) and height (bottom, top) bounding the axes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Area {
+    pub left: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub top: f64,
+}
+
+impl Area {
+    // Axes of the cell at `row` (from the top) and `column` of a grid of panels, leaving room
+    // for the title above them, the labels of the abscissa below and those of the ordinate on
+    // their left
+    pub fn cell(row: usize, column: usize, rows: usize, columns: usize) -> Self {
+        let (width, height) = (1.0 / columns as f64, 1.0 / rows as f64);
+        let (left, top) = (column as f64 * width, 1.0 - row as f64 * height);
+        Area { left: left + 0.09, right: left + width - 0.02, bottom: top - height + 0.1, top: top - 0.08 }
+    }
+}
+
+// Points drawn in a color (a gnuplot name such as "dark-green", or "#rrggbb"), listed in the
+// legend under `caption` when there is one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Series {
+    pub caption: Option<String>,
+    pub xs: Vec<f64>,
+    pub ys: Vec<f64>,
+    pub style: Style,
+    pub color: String,
+}
+
+impl Series {
+    pub fn new(caption: Option<&str>, xs: &[f64], ys: &[f64], style: Style, color: &str) -> Self {
+        Series { caption: caption.map(str::to_string), xs: xs.to_vec(), ys: ys.to_vec(), style, color: color.to_string() }
+    }
+
+    pub fn lines(caption: &str, xs: &[f64], ys: &[f64], color: &str) -> Self {
+        Series::new(Some(caption), xs, ys, Style::Lines, color)
+    }
+
+    pub fn points(caption: &str, xs: &[f64], ys: &[f64], color: &str) -> Self {
+        Series::new(Some(caption), xs, ys, Style::Points, color)
+    }
+
+    // Band filled between the `lower` and `upper` bounds at each x
+    pub fn band(caption: Option<&str>, xs: &[f64], lower: &[f64], upper: &[f64], color: &str, alpha: f64) -> Self {
+        Series::new(caption, xs, lower, Style::Band { upper: upper.to_vec(), alpha }, color)
+    }
+
+    // Band filled `errors` below and above `ys`, e.g. one standard deviation around a curve
+    pub fn error_band(caption: Option<&str>, xs: &[f64], ys: &[f64], errors: &[f64], color: &str, alpha: f64) -> Self {
+        let lower: Vec<f64> = ys.iter().zip(errors).map(|(y, error)| y - error).collect();
+        let upper: Vec<f64> = ys.iter().zip(errors).map(|(y, error)| y + error).collect();
+        Series::band(caption, xs, &lower, &upper, color, alpha)
+    }
+}
+
+// How the points of a series are drawn: joined by lines, as open circles, both, as boxes from
+// zero, or as a band filled between them (the lower bounds) and `upper`. Fills are drawn with
+// an opacity `alpha` between 0 and 1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Style {
+    Lines,
+    Points,
+    LinesPoints,
+    Boxes { alpha: f64 },
+    Band { upper: Vec<f64>, alpha: f64 },
+}
+
+// A point marked on the axes, e.g. the minimum ionizing point, labelled beside it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub x: f64,
+    pub y: f64,
+    pub label: String,
+    pub color: String,
+}
+
+impl Annotation {
+    pub fn new(x: f64, y: f64, label: &str, color: &str) -> Self {
+        Annotation { x, y, label: label.to_string(), color: color.to_string() }
+    }
+}
+
+// A dashed line across the axes at `value` of the abscissa (vertical) or of the ordinate
+// (horizontal), e.g. a threshold energy, labelled at its top or right end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Guide {
+    pub direction: Direction,
+    pub value: f64,
+    pub label: String,
+    pub color: String,
+}
+
+impl Guide {
+    pub fn vertical(x: f64, label: &str, color: &str) -> Self {
+        Guide { direction: Direction::Vertical, value: x, label: label.to_string(), color: color.to_string() }
+    }
+
+    pub fn horizontal(y: f64, label: &str, color: &str) -> Self {
+        Guide { direction: Direction::Horizontal, value: y, label: label.to_string(), color: color.to_string() }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Vertical,
+    Horizontal,
+}
+
+// Values on a grid drawn as cells colored by them, with a color bar labelled `label`, e.g. dE/dx
+// over energy and atomic number: `values[row][column]` is at `ys[row]` and `xs[column]`. Each
+// cell reaches halfway to its neighbours on the scales of the axes, and the colors follow the
+// logarithm of the values when `log`. Cells of values that cannot be drawn (NaN, or not
+// positive on a logarithmic scale) are left empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Heatmap {
+    pub xs: Vec<f64>,
+    pub ys: Vec<f64>,
+    pub values: Vec<Vec<f64>>,
+    pub label: String,
+    pub log: bool,
+}
+
+impl Heatmap {
+    pub fn new(xs: &[f64], ys: &[f64], values: &[Vec<f64>], label: &str, log: bool) -> Self {
+        Heatmap { xs: xs.to_vec(), ys: ys.to_vec(), values: values.to_vec(), label: label.to_string(), log }
+    }
+
+    // Bounds of the columns and rows, one more than them, on linear or logarithmic axes
+    pub fn x_edges(&self, log: bool) -> Vec<f64> {
+        edges(&self.xs, log)
+    }
+
+    pub fn y_edges(&self, log: bool) -> Vec<f64> {
+        edges(&self.ys, log)
+    }
+
+    // Smallest and largest values that can be drawn, from 1 to 10 without any
+    pub fn limits(&self) -> (f64, f64) {
+        let (min, max) = self.values.iter().flatten().copied()
+            .filter(|value| self.drawable(*value))
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+        match (min, max) {
+            (min, max) if min < max => (min, max),
+            (value, _) if value.is_finite() => extent(std::iter::once(value), self.log, 0.0),
+            _ => (1.0, 10.0),
+        }
+    }
+
+    // Fraction of the color bar, between the limits, of a value that can be drawn
+    pub fn fraction(&self, value: f64, (min, max): (f64, f64)) -> Option<f64> {
+        let fraction = if self.log { (value / min).ln() / (max / min).ln() } else { (value - min) / (max - min) };
+        Some(fraction.clamp(0.0, 1.0)).filter(|_| self.drawable(value))
+    }
+
+    // Value at a fraction of the color bar
+    pub fn value(&self, fraction: f64, (min, max): (f64, f64)) -> f64 {
+        if self.log { min * (max / min).powf(fraction) } else { min + fraction * (max - min) }
+    }
+
+    // Value of the cell holding a point, on linear or logarithmic axes
+    pub fn cell(&self, x: f64, y: f64, x_log: bool, y_log: bool) -> Option<f64> {
+        let find = |edges: Vec<f64>, value: f64| edges.windows(2).position(|pair| (pair[0].min(pair[1])..=pair[0].max(pair[1])).contains(&value));
+        let (column, row) = (find(self.x_edges(x_log), x)?, find(self.y_edges(y_log), y)?);
+        self.values.get(row)?.get(column).copied().filter(|value| self.drawable(*value))
+    }
+
+    pub fn drawable(&self, value: f64) -> bool {
+        value.is_finite() && (!self.log || value > 0.0)
+    }
+}
+
+// Midpoints between the centres, and the ends as far beyond the first and last ones, on the
+// scale of the axis; a single centre is given the width of a padded range around it
+fn edges(centres: &[f64], log: bool) -> Vec<f64> {
+    let scale = |value: f64| if log { value.ln() } else { value };
+    let unscale = |value: f64| if log { value.exp() } else { value };
+    match centres {
+        [] => Vec::new(),
+        [centre] => {
+            let (min, max) = extent(std::iter::once(*centre), log, PADDING);
+            vec![min, max]
+        }
+        _ => {
+            let scaled: Vec<f64> = centres.iter().map(|centre| scale(*centre)).collect();
+            let (first, last) = (scaled[0], scaled[scaled.len() - 1]);
+            let mut edges = vec![unscale(first - (scaled[1] - first) / 2.0)];
+            edges.extend(scaled.windows(2).map(|pair| unscale((pair[0] + pair[1]) / 2.0)));
+            edges.push(unscale(last + (last - scaled[scaled.len() - 2]) / 2.0));
+            edges
+        }
+    }
+}
@@ -0,0 +1,345 @@
+// This module draws charts with gnuplot, which has to be installed, and writes them as gnuplot
+// scripts reading their data from text files
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
+
+use gnuplot::BorderLocation2D::{Bottom, Left, Right, Top};
+use gnuplot::{AlignType, ArrowType, ArrowheadType, AutoOption, Axes2D, AxesCommon, Caption, Color, Coordinate, DashType, FillAlpha, Figure, Fix, LegendOption, LineStyle, LineWidth, MarkerColor, MarkerSymbol, PaletteType, PlotOption, PointSymbol, TextAlign, TextColor, TextOffset, Tick};
+use gnuplot::MarginSide::{MarginBottom, MarginLeft, MarginRight, MarginTop};
+
+use super::backend::{PlotBackend, PlotFormat};
+use super::chart::{Axis, Chart, Direction, Heatmap, Panel, Style};
+use super::style::{Legend, PlotStyle, BASE_DPI, COLORMAP};
+use super::terminal::{number, scaled};
+use crate::error::{write_file, Error, Result};
+
+// Backend starting gnuplot for every chart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Gnuplot;
+
+impl PlotBackend for Gnuplot {
+    fn draw(&self, chart: &Chart, style: &PlotStyle, path: &Path) -> Result<()> {
+        figure(chart, style, path).show().map_err(|source| Error::Plot { title: chart.title.clone(), source })?;
+        Ok(())
+    }
+}
+
+// The gnuplot figure drawing a chart into `path`, one set of axes per panel. PNG images have
+// the pixels, fonts and lines of the resolution of the style; vector documents are at 96 dpi.
+// The background, border and text take the colors of the theme.
+fn figure(chart: &Chart, style: &PlotStyle, path: &Path) -> Figure {
+    let (background, foreground) = (style.theme.background(), style.theme.foreground());
+    let options = format!("font \"{},{}\" background \"{}\"", style.font, style.font_size, background);
+    let text = [TextColor(foreground)];
+    let inches = |pixels: u32| f64::from(pixels) / f64::from(BASE_DPI);
+    let terminal = match PlotFormat::of(path) {
+        PlotFormat::Svg => format!("svg size {},{} {}", style.width, style.height, options),
+        PlotFormat::Pdf => format!("pdfcairo size {:.2}in,{:.2}in {}", inches(style.width), inches(style.height), options),
+        PlotFormat::Eps => format!("epscairo size {:.2}in,{:.2}in {}", inches(style.width), inches(style.height), options),
+        PlotFormat::Png => {
+            let (width, height) = style.pixels();
+            let terminal = style.terminal.as_deref().unwrap_or("pngcairo");
+            format!("{} size {},{} {} fontscale {} linewidth {}", terminal, width, height, options, style.scale(), style.scale())
+        }
+    };
+    let legend = match style.legend {
+        Legend::TopRight => Some((1.0, 1.0, AlignType::AlignRight, AlignType::AlignTop)),
+        Legend::TopLeft => Some((0.0, 1.0, AlignType::AlignLeft, AlignType::AlignTop)),
+        Legend::BottomRight => Some((1.0, 0.0, AlignType::AlignRight, AlignType::AlignBottom)),
+        Legend::BottomLeft => Some((0.0, 0.0, AlignType::AlignLeft, AlignType::AlignBottom)),
+        Legend::None => None,
+    };
+
+    let mut fg = Figure::new();
+    fg.set_terminal(&terminal, &path.to_string_lossy());
+    for panel in &chart.panels {
+        let (panel, y_ticks) = match &panel.heatmap {
+            Some(_) => {
+                let (panel, y_ticks) = linearized(panel);
+                (Cow::Owned(panel), y_ticks)
+            }
+            None => (Cow::Borrowed(panel), Vec::new()),
+        };
+        let panel = panel.as_ref();
+        let axes = fg.axes2d();
+        if let Some(area) = panel.area {
+            let margins = [MarginLeft(area.left as f32), MarginRight(area.right as f32), MarginTop(area.top as f32), MarginBottom(area.bottom as f32)];
+            axes.set_margins(&margins);
+        }
+
+        // Scales, with the ranges of the data unless fixed
+        let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+        axes.set_x_log(panel.x_axis.log_base);
+        axes.set_y_log(panel.y_axis.log_base);
+        axes.set_x_range(Fix(x_min), Fix(x_max));
+        axes.set_y_range(Fix(y_min), Fix(y_max));
+        axes.set_border(true, &[Bottom, Left, Top, Right], &[Color(foreground)]);
+        if !panel.x_ticks.is_empty() {
+            axes.set_x_ticks_custom(panel.x_ticks.iter().map(|(x, label)| Tick::Major(*x, AutoOption::Fix(label.clone()))), &[], &text);
+        } else {
+            // Hidden tick labels are drawn in the color of the background
+            let color = if panel.x_tick_labels { foreground } else { background };
+            axes.set_x_ticks(Some((AutoOption::Auto, 0)), &[], &[TextColor(color)]);
+        }
+        if !y_ticks.is_empty() {
+            axes.set_y_ticks_custom(y_ticks.iter().map(|(y, label)| Tick::Major(*y, AutoOption::Fix(label.clone()))), &[], &text);
+        } else {
+            axes.set_y_ticks(Some((AutoOption::Auto, 0)), &[], &text);
+        }
+
+        if style.grid {
+            axes.set_x_grid(true).set_y_grid(true);
+        }
+        if let Some((x, y, horizontal, vertical)) = legend {
+            axes.set_legend(Coordinate::Graph(x), Coordinate::Graph(y), &[LegendOption::Placement(horizontal, vertical)], &text);
+        }
+
+        // Set titles and labels
+        if !panel.title.is_empty() {
+            axes.set_title(&panel.title, &text);
+        }
+        if !panel.x_label.is_empty() {
+            axes.set_x_label(&panel.x_label, &text);
+        }
+        if !panel.y_label.is_empty() {
+            axes.set_y_label(&panel.y_label, &text);
+        }
+
+        if let Some(heatmap) = &panel.heatmap {
+            draw_image(axes, heatmap, foreground);
+        }
+        for series in &panel.series {
+            let mut options: Vec<PlotOption<&str>> = vec![Color(&series.color)];
+            if let Some(caption) = series.caption.as_deref().filter(|_| legend.is_some()) {
+                options.push(Caption(caption));
+            }
+            match &series.style {
+                Style::Lines => axes.lines(&series.xs, &series.ys, &[options, vec![LineWidth(style.line_width)]].concat()),
+                Style::Points => axes.points(&series.xs, &series.ys, &[options, vec![PointSymbol('O')]].concat()),
+                Style::LinesPoints => axes.lines_points(&series.xs, &series.ys, &[options, vec![PointSymbol('O'), LineWidth(style.line_width)]].concat()),
+                Style::Boxes { alpha } => axes.boxes(&series.xs, &series.ys, &[options, vec![FillAlpha(*alpha)]].concat()),
+                Style::Band { upper, alpha } => axes.fill_between(&series.xs, &series.ys, upper, &[options, vec![FillAlpha(*alpha)]].concat()),
+            };
+        }
+
+        // Guides and marked points inside the ranges, each labelled in its color: vertical
+        // guides right of their top, horizontal ones above their right end
+        let (x_inside, y_inside) = (|x: f64| (x_min..=x_max).contains(&x), |y: f64| (y_min..=y_max).contains(&y));
+        for guide in &panel.guides {
+            let value = Coordinate::Axis(guide.value);
+            let (start, end, align, offset) = match guide.direction {
+                Direction::Vertical if x_inside(guide.value) => ((value, Coordinate::Graph(0.0)), (value, Coordinate::Graph(1.0)), AlignType::AlignLeft, (0.5, -1.0)),
+                Direction::Horizontal if y_inside(guide.value) => ((Coordinate::Graph(0.0), value), (Coordinate::Graph(1.0), value), AlignType::AlignRight, (-0.5, 0.5)),
+                _ => continue,
+            };
+            let line = [Color(guide.color.as_str()), LineStyle(DashType::Dash), ArrowType(ArrowheadType::NoArrow), LineWidth(style.line_width)];
+            axes.arrow(start.0, start.1, end.0, end.1, &line);
+            let label = [TextColor(guide.color.as_str()), TextAlign(align), TextOffset(offset.0, offset.1)];
+            axes.label(&guide.label, end.0, end.1, &label);
+        }
+        for annotation in panel.annotations.iter().filter(|annotation| x_inside(annotation.x) && y_inside(annotation.y)) {
+            let color = annotation.color.as_str();
+            let label = [TextColor(color), MarkerSymbol('O'), MarkerColor(color), TextOffset(1.0, 0.5)];
+            axes.label(&annotation.label, Coordinate::Axis(annotation.x), Coordinate::Axis(annotation.y), &label);
+        }
+    }
+    fg
+}
+
+// gnuplot draws images on evenly spaced pixels of linear axes, so a panel with a color map is
+// drawn on the logarithms of the values of its logarithmic axes, with ticks at the powers of
+// their base labelled with those values: those of the abscissa replace the labelled ticks of
+// the panel, and those of the ordinate are returned
+fn linearized(panel: &Panel) -> (Panel, Vec<(f64, String)>) {
+    let (x_log, y_log) = (panel.x_axis.log_base, panel.y_axis.log_base);
+    let linear = |log: Option<f64>| move |value: f64| if log.is_none() || value > 0.0 { scaled(value, log) } else { f64::NAN };
+    let (x, y) = (linear(x_log), linear(y_log));
+    let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+    let powers = |log: Option<f64>, (min, max): (f64, f64)| -> Vec<(f64, String)> {
+        match log {
+            Some(base) => (min.ceil() as i32..=max.floor() as i32).map(|power| (f64::from(power), number(base.powi(power)))).collect(),
+            None => Vec::new(),
+        }
+    };
+
+    let mut linear = panel.clone();
+    linear.x_axis = Axis { log_base: None, range: Some((x(x_min), x(x_max))) };
+    linear.y_axis = Axis { log_base: None, range: Some((y(y_min), y(y_max))) };
+    linear.x_ticks = match x_log {
+        Some(_) if panel.x_ticks.is_empty() => powers(x_log, (x(x_min), x(x_max))),
+        _ => panel.x_ticks.iter().map(|(value, label)| (x(*value), label.clone())).collect(),
+    };
+    for series in &mut linear.series {
+        series.xs.iter_mut().for_each(|value| *value = x(*value));
+        series.ys.iter_mut().for_each(|value| *value = y(*value));
+        if let Style::Band { upper, .. } = &mut series.style {
+            upper.iter_mut().for_each(|value| *value = y(*value));
+        }
+    }
+    for annotation in &mut linear.annotations {
+        (annotation.x, annotation.y) = (x(annotation.x), y(annotation.y));
+    }
+    for guide in &mut linear.guides {
+        guide.value = match guide.direction {
+            Direction::Vertical => x(guide.value),
+            Direction::Horizontal => y(guide.value),
+        };
+    }
+    if let Some(heatmap) = &mut linear.heatmap {
+        heatmap.xs.iter_mut().for_each(|value| *value = x(*value));
+        heatmap.ys.iter_mut().for_each(|value| *value = y(*value));
+    }
+    (linear, powers(y_log, (y(y_min), y(y_max))))
+}
+
+// A color map on linear axes as an image, colored as in the other backends with its color bar
+// on the right, labelled in `foreground`. Cells that are not evenly spaced are drawn as pixels
+// as wide as the narrowest of them, each in the color of the cell under its centre.
+fn draw_image(axes: &mut Axes2D, heatmap: &Heatmap, foreground: &str) {
+    let (xs, ys) = (evenly_spaced(&heatmap.xs), evenly_spaced(&heatmap.ys));
+    let (Some(&x_first), Some(&x_last), Some(&y_first), Some(&y_last)) = (xs.first(), xs.last(), ys.first(), ys.last()) else {
+        return;
+    };
+    let pixels: Vec<f64> = ys.iter().flat_map(|&y| xs.iter().map(move |&x| heatmap.cell(x, y, false, false).unwrap_or(f64::NAN))).collect();
+
+    let (min, max) = heatmap.limits();
+    let last = (COLORMAP.len() - 1) as f32;
+    let palette = COLORMAP.iter().enumerate().map(|(index, (r, g, b))| (index as f32 / last, f32::from(*r) / 255.0, f32::from(*g) / 255.0, f32::from(*b) / 255.0));
+    axes.set_palette(PaletteType::Custom(palette.collect()));
+    axes.set_cb_log(heatmap.log.then_some(10.0));
+    axes.set_cb_range(Fix(min), Fix(max));
+    axes.set_cb_ticks(Some((AutoOption::Auto, 0)), &[], &[TextColor(foreground)]);
+    if !heatmap.label.is_empty() {
+        axes.set_cb_label(&heatmap.label, &[TextColor(foreground)]);
+    }
+    axes.image(pixels, ys.len(), xs.len(), Some((x_first, y_first, x_last, y_last)), &[]);
+}
+
+// The centres when they are evenly spaced, otherwise evenly spaced ones over the same span as
+// close as the closest of them (no more than a thousand)
+fn evenly_spaced(centres: &[f64]) -> Vec<f64> {
+    let steps: Vec<f64> = centres.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    let (Some(&first), Some(&last), Some(step)) = (centres.first(), centres.last(), steps.iter().map(|step| step.abs()).reduce(f64::min)) else {
+        return centres.to_vec();
+    };
+    if step == 0.0 || steps.iter().all(|other| (other - steps[0]).abs() <= 1e-6 * step) {
+        return centres.to_vec();
+    }
+    let count = (((last - first).abs() / step).round() as usize + 1).min(1000);
+    (0..count).map(|index| first + (last - first) * index as f64 / (count - 1) as f64).collect()
+}
+
+// Write the commands drawing the chart into `image_path` as a gnuplot script, the curves being
+// read from a text file instead of the binary data sent to gnuplot
+pub fn write_script(chart: &Chart, style: &PlotStyle, image_path: &Path, script_path: &Path, data_path: &Path) -> Result<()> {
+    let mut commands = Vec::new();
+    figure(chart, style, image_path).echo(&mut commands);
+    let (script, data) = split_data(&commands, &data_path.to_string_lossy());
+    write_file(script_path, |file| file.write_all(script.as_bytes()))?;
+    write_file(data_path, |file| file.write_all(data.as_bytes()))
+}
+
+// Split the commands echoed by a figure into the script, where each inline record or image of
+// a plot command becomes a block of `data_file` (read with `index`), and the text of those
+// blocks: one row per point, blocks separated by two blank lines. The pixels of images become
+// "x y value" rows, a blank line between each of their rows.
+fn split_data(commands: &[u8], data_file: &str) -> (String, String) {
+    const INLINE: &str = "\"-\" binary endian=little ";
+    let (mut script, mut data) = (String::new(), String::new());
+    let mut block = 0;
+    let mut rest = commands;
+    while !rest.is_empty() {
+        let end = rest.iter().position(|&byte| byte == b'\n').map_or(rest.len(), |i| i + 1);
+        let line = String::from_utf8_lossy(&rest[..end]);
+        rest = &rest[end..];
+
+        // `"-" binary endian=little record=<rows> format="%float64" using 1:2 with lines ...`, or
+        // `"-" binary endian=little array=(<columns>,<rows>) format="%float64" origin=(<x>,<y>)
+        // dx=<dx> dy=<dy> with image`
+        let mut blocks = Vec::new();
+        let mut parts = line.split(INLINE);
+        script.push_str(parts.next().unwrap_or_default());
+        for part in parts {
+            let (source, arguments) = part.split_once(' ').unwrap_or((part, ""));
+            let mut arguments = arguments.strip_prefix("format=\"%float64\" ").unwrap_or(arguments).to_string();
+            let inline = match source.strip_prefix("array=(").and_then(|size| size.strip_suffix(')')) {
+                Some(size) => {
+                    let mut image = Image::default();
+                    let (columns, rows) = size.split_once(',').unwrap_or((size, "1"));
+                    (image.columns, image.rows) = (columns.parse().unwrap_or(0), rows.parse().unwrap_or(0));
+                    while let Some((argument, others)) = arguments.split_once(' ').filter(|(argument, _)| argument.contains('=')) {
+                        let (key, value) = argument.split_once('=').unwrap_or_default();
+                        let value = value.trim_matches(|c| c == '(' || c == ')');
+                        match key {
+                            "origin" => {
+                                let (x, y) = value.split_once(',').unwrap_or((value, "0"));
+                                image.origin = (x.parse().unwrap_or(0.0), y.parse().unwrap_or(0.0));
+                            }
+                            "dx" => image.step.0 = value.parse().unwrap_or(1.0),
+                            "dy" => image.step.1 = value.parse().unwrap_or(1.0),
+                            _ => {}
+                        }
+                        arguments = others.to_string();
+                    }
+                    arguments = format!("using 1:2:3 {}", arguments.trim_start());
+                    Inline::Image(image)
+                }
+                None => {
+                    let rows = source.strip_prefix("record=").and_then(|rows| rows.parse().ok()).unwrap_or(0);
+                    let columns = arguments.split_whitespace().nth(1).map_or(1, |using| using.split(':').count());
+                    Inline::Records(rows, columns)
+                }
+            };
+            blocks.push(inline);
+            script.push_str(&format!("'{}' index {} {}", data_file.replace('\'', "''"), block, arguments));
+            block += 1;
+        }
+
+        // The data of the blocks follow the plot command, as little-endian doubles
+        for inline in blocks {
+            let (count, columns) = match &inline {
+                Inline::Records(rows, columns) => (rows * columns, *columns),
+                Inline::Image(image) => (image.rows * image.columns, image.columns),
+            };
+            let len = (8 * count).min(rest.len());
+            let values: Vec<f64> = rest[..len].chunks_exact(8).map(|bytes| f64::from_le_bytes(bytes.try_into().unwrap_or_default())).collect();
+            rest = &rest[len..];
+            for (row, values) in values.chunks(columns.max(1)).enumerate() {
+                match &inline {
+                    Inline::Records(..) => {
+                        let fields: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                        data.push_str(&fields.join("\t"));
+                        data.push('\n');
+                    }
+                    Inline::Image(image) => {
+                        if row > 0 {
+                            data.push('\n');
+                        }
+                        let y = image.origin.1 + row as f64 * image.step.1;
+                        for (column, value) in values.iter().enumerate() {
+                            data.push_str(&format!("{}\t{}\t{}\n", image.origin.0 + column as f64 * image.step.0, y, value));
+                        }
+                    }
+                }
+            }
+            data.push_str("\n\n");
+        }
+    }
+    (script, data)
+}
+
+// Data sent inline to gnuplot: records of rows of columns, or an image
+enum Inline {
+    Records(usize, usize),
+    Image(Image),
+}
+
+// Pixels of an image, from `origin` (x, y) at the bottom left by `step` (dx, dy)
+#[derive(Default)]
+struct Image {
+    columns: usize,
+    rows: usize,
+    origin: (f64, f64),
+    step: (f64, f64),
+}
@@ -1,31 +1,355 @@
-// This module is responsible for plotting data using gnuplot.
-extern crate gnuplot;
-use gnuplot::{Figure, AxesCommon, Caption, Color, Fix};
-
-pub fn plot(energies: &Vec<f64>, stopping_powers: &Vec<f64>, 
-    caption: &str, title: &str){
-// --- Plotting using gnuplot ---
-let mut fg = Figure::new();
-{
-    let axes = fg.axes2d();
-
-    // Set logarithmic scales for both axes
-    axes.set_x_log(Some(2.0));
-    axes.set_y_log(Some(2.0));
-    
-    // Set axis ranges
-    axes.set_x_range(Fix(10.0), Fix(10500.0));
-    axes.set_y_range(Fix(1e-30),Fix(1e-27));
-    
-    // Set titles and labels
-    axes.set_title(title, &[]);
-    axes.set_x_label("Energía (MeV)", &[]);
-    axes.set_y_label("Poder de frenado (MeV/cm)", &[]);
-    
+// This module builds the figures of the tables, labelled in the language of the output, and
+// draws them with the backend of the output.
+use super::chart::{Annotation, Area, Axis, Chart, Heatmap, Panel, Series, Style};
+use crate::error::Result;
+use crate::i18n::fill;
+use crate::output::OutputConfig;
+
+// Abscissa label (kinetic energy, momentum or βγ) and stopping-power unit of `plot` and `plot_band`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoppingLabels<'a> {
+    pub x_label: &'a str,
+    pub unit: &'a str,
+}
+
+// Colors of the curves of figures with several of them, in turn
+const COLORS: [&str; 6] = ["blue", "red", "dark-green", "orange", "purple", "black"];
+
+// `minimum` marks the minimum ionizing point (energy, dE/dx) when given
+pub fn plot(energies: &[f64], stopping_powers: &[f64],
+    caption: &str, title: &str, labels: StoppingLabels, minimum: Option<(f64, f64)>, output: &OutputConfig) -> Result<()> {
+    let mut panel = stopping_panel(title, labels, output);
+
+    // Plot the data in blue with a label
+    panel.series.push(Series::lines(caption, energies, stopping_powers, "blue"));
+
+    // Mark the minimum ionizing point in black
+    if let Some((energy, de_dx)) = minimum {
+        panel.annotations.push(Annotation::new(energy, de_dx, output.lang.labels().minimum_ionizing, "black"));
+    }
+    show(&Chart::single(panel), output)
+}
+
+// Same as `plot` with the (lower, upper) bounds of `band` shaded around the curve
+pub fn plot_band(energies: &[f64], stopping_powers: &[f64], band: (&[f64], &[f64]),
+    caption: &str, title: &str, labels: StoppingLabels, output: &OutputConfig) -> Result<()> {
+    let mut panel = stopping_panel(title, labels, output);
+
+    // Shade the band in light blue under the curve in blue
+    panel.series.push(Series::band(Some(output.lang.labels().uncertainty), energies, band.0, band.1, "light-blue", 0.5));
+    panel.series.push(Series::lines(caption, energies, stopping_powers, "blue"));
+    show(&Chart::single(panel), output)
+}
+
+// Same as `plot` with several labelled curves, e.g. of each variant of the corrections or of
+// several projectiles or materials, drawn in their own colors and styles. Curves without a
+// color take those of the other figures in turn.
+pub fn plot_multi(series: &[Series], title: &str, labels: StoppingLabels, output: &OutputConfig) -> Result<()> {
+    let mut panel = stopping_panel(title, labels, output);
+
+    for (index, series) in series.iter().enumerate() {
+        let color = if series.color.is_empty() { COLORS[index % COLORS.len()].to_string() } else { series.color.clone() };
+        panel.series.push(Series { color, ..series.clone() });
+    }
+    show(&Chart::single(panel), output)
+}
+
+// Stopping-power curves on a grid of panels `columns` wide, e.g. the four variants of the
+// corrections on a 2×2 grid, each titled with its caption
+pub fn plot_summary(curves: &[(&str, &[f64], &[f64])], columns: usize, title: &str, labels: StoppingLabels, output: &OutputConfig) -> Result<()> {
+    let columns = columns.max(1);
+    let rows = curves.len().div_ceil(columns);
+    let panels = curves.iter().enumerate().map(|(index, (caption, energies, stopping_powers))| {
+        let mut panel = stopping_panel(caption, labels, output);
+        panel.area = Some(Area::cell(index / columns, index % columns, rows, columns));
+        panel.series.push(Series::new(None, energies, stopping_powers, Style::Lines, "blue"));
+        panel
+    });
+    show(&Chart { title: title.to_string(), panels: panels.collect() }, output)
+}
+
+// Logarithmic axes of the stopping-power figures, spanning the curves of any unit
+fn stopping_panel(title: &str, labels: StoppingLabels, output: &OutputConfig) -> Panel {
+    let y_label = fill(output.lang.labels().stopping_power, &[labels.unit]);
+    Panel { x_axis: Axis::log(10.0), y_axis: Axis::log(10.0), ..Panel::new(title, labels.x_label, &y_label) }
+}
+
+// `peak` marks the Bragg peak (depth, dose) when given
+pub fn plot_bragg(depths: &[f64], doses: &[f64], caption: &str, title: &str, peak: Option<(f64, f64)>, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let mut panel = Panel::new(title, labels.depth, labels.dose);
+
+    // Plot the data in red with a label
+    panel.series.push(Series::lines(caption, depths, doses, "red"));
+
+    // Mark the Bragg peak in black
+    if let Some((depth, dose)) = peak {
+        panel.annotations.push(Annotation::new(depth, dose, labels.bragg_peak, "black"));
+    }
+    show(&Chart::single(panel), output)
+}
+
+// CSDA range against the initial kinetic energy on logarithmic axes, with the range straggling
+// σ (cm) of each energy shaded around it when given
+pub fn plot_range(energies: &[f64], ranges: &[f64], straggling: Option<&[f64]>, caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let mut panel = Panel { x_axis: Axis::log(10.0), y_axis: Axis::log(10.0), ..Panel::new(title, labels.energy, labels.csda_range) };
+
+    // Shade ±σ in light blue under the curve
+    if let Some(sigmas) = straggling {
+        panel.series.push(Series::error_band(Some(labels.range_straggling), energies, ranges, sigmas, "light-blue", 0.5));
+    }
+
     // Plot the data in blue with a label
-    axes.lines(energies, stopping_powers, &[Caption(caption), Color("blue")]);
+    panel.series.push(Series::lines(caption, energies, ranges, "blue"));
+    show(&Chart::single(panel), output)
+}
+
+// Collision and radiative stopping powers of electrons on logarithmic axes
+pub fn plot_losses(energies: &[f64], collision: &[f64], radiative: &[f64], caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let mut panel = Panel { x_axis: Axis::log(10.0), y_axis: Axis::log(10.0), ..Panel::new(title, labels.energy, &fill(labels.stopping_power, &["MeV/cm"])) };
+
+    // Collision losses in blue and radiative losses in red
+    panel.series.push(Series::lines(&format!("{} {}", caption, labels.collision), energies, collision, "blue"));
+    panel.series.push(Series::lines(&format!("{} {}", caption, labels.radiative), energies, radiative, "red"));
+    show(&Chart::single(panel), output)
+}
+
+// Ratio of the mass stopping powers of two materials against energy
+pub fn plot_ratio(energies: &[f64], ratios: &[f64], caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    // Logarithmic energy axis only, the ratio varies slowly
+    let labels = output.lang.labels();
+    let mut panel = Panel { x_axis: Axis::log(10.0), ..Panel::new(title, labels.energy, labels.mass_ratio) };
+
+    // Plot the data in green with a label
+    panel.series.push(Series::lines(caption, energies, ratios, "dark-green"));
+    show(&Chart::single(panel), output)
+}
+
+// Scintillation light per unit length with and without Birks quenching
+pub fn plot_light(energies: &[f64], unquenched: &[f64], quenched: &[f64], caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let mut panel = Panel { x_axis: Axis::log(10.0), y_axis: Axis::log(10.0), ..Panel::new(title, labels.energy, labels.light) };
+
+    // Unquenched light in blue and Birks light in red
+    panel.series.push(Series::lines(&format!("{} {}", caption, labels.unquenched), energies, unquenched, "blue"));
+    panel.series.push(Series::lines(&format!("{} Birks", caption), energies, quenched, "red"));
+    show(&Chart::single(panel), output)
+}
+
+// ΔE-E correlation of several species, one series of (E, ΔE) points per species
+pub fn plot_telescope(series: &[(&str, &[f64], &[f64])], title: &str, output: &OutputConfig) -> Result<()> {
+    let mut panel = Panel::new(title, output.lang.labels().residual_energy, "ΔE (MeV)");
+
+    // One color per species
+    for (index, (caption, residual, delta)) in series.iter().enumerate() {
+        panel.series.push(Series::lines(caption, residual, delta, COLORS[index % COLORS.len()]));
+    }
+    show(&Chart::single(panel), output)
+}
+
+// Several curves on shared logarithmic axes, one (caption, x, y) series per curve
+pub fn plot_overlay(series: &[(&str, &[f64], &[f64])], x_label: &str, y_label: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let mut panel = Panel { x_axis: Axis::log(10.0), y_axis: Axis::log(10.0), ..Panel::new(title, x_label, y_label) };
+
+    // One color per curve
+    for (index, (caption, xs, ys)) in series.iter().enumerate() {
+        panel.series.push(Series::lines(caption, xs, ys, COLORS[index % COLORS.len()]));
+    }
+    show(&Chart::single(panel), output)
+}
+
+// Columns of a table against its first one, one (caption, y) curve per column, on a logarithmic
+// x axis with `x_log` and a logarithmic y axis when the values are positive and span more than
+// two decades
+pub fn plot_columns(xs: &[f64], columns: &[(&str, &[f64])], x_label: &str, y_label: &str, x_log: bool, title: &str, output: &OutputConfig) -> Result<()> {
+    let values = columns.iter().flat_map(|(_, ys)| ys.iter().copied()).filter(|y| y.is_finite());
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), y| (min.min(y), max.max(y)));
+    let x_axis = if x_log { Axis::log(10.0) } else { Axis::default() };
+    let y_axis = if min > 0.0 && max / min > 100.0 { Axis::log(10.0) } else { Axis::default() };
+    let mut panel = Panel { x_axis, y_axis, ..Panel::new(title, x_label, y_label) };
+
+    // One color per column
+    for (index, (caption, ys)) in columns.iter().enumerate() {
+        panel.series.push(Series::lines(caption, xs, ys, COLORS[index % COLORS.len()]));
+    }
+    show(&Chart::single(panel), output)
+}
+
+// Track-averaged and dose-averaged LET against depth
+pub fn plot_let(depths: &[f64], track: &[f64], dose: &[f64], caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let mut panel = Panel::new(title, output.lang.labels().depth, "LET (keV/µm)");
+
+    // LET_track in blue and LET_dose in red
+    panel.series.push(Series::lines(&format!("{} LET_t", caption), depths, track, "blue"));
+    panel.series.push(Series::lines(&format!("{} LET_d", caption), depths, dose, "red"));
+    show(&Chart::single(panel), output)
+}
+
+// Physical and RBE-weighted dose against depth
+pub fn plot_rbe(depths: &[f64], doses: &[f64], weighted: &[f64], caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let mut panel = Panel::new(title, labels.depth, labels.dose_gy);
+
+    // Physical dose in red and RBE-weighted dose in black
+    panel.series.push(Series::lines(&format!("{} {}", caption, labels.physical), depths, doses, "red"));
+    panel.series.push(Series::lines(&format!("{} {}", caption, labels.rbe_weighted), depths, weighted, "black"));
+    show(&Chart::single(panel), output)
+}
+
+// Lateral beam spread against depth
+pub fn plot_spread(depths: &[f64], sigmas: &[f64], caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let mut panel = Panel::new(title, output.lang.labels().depth, "σx (cm)");
+
+    // Plot the data in blue with a label
+    panel.series.push(Series::lines(caption, depths, sigmas, "blue"));
+    show(&Chart::single(panel), output)
+}
+
+// Tornado chart: the fractional change (%) for a decrease and an increase of each parameter
+pub fn plot_tornado(labels: &[&str], minus: &[f64], plus: &[f64], title: &str, output: &OutputConfig) -> Result<()> {
+    let positions: Vec<f64> = (0..labels.len()).map(|i| i as f64).collect();
+
+    // One tick per parameter
+    let mut panel = Panel::new(title, output.lang.labels().parameter, output.lang.labels().change);
+    panel.x_ticks = labels.iter().enumerate().map(|(i, label)| (i as f64, label.to_string())).collect();
+
+    // Decreased parameter in blue and increased parameter in red
+    panel.series.push(Series::new(Some("-"), &positions, minus, Style::Boxes { alpha: 0.6 }, "blue"));
+    panel.series.push(Series::new(Some("+"), &positions, plus, Style::Boxes { alpha: 0.6 }, "red"));
+    show(&Chart::single(panel), output)
+}
+
+// Relative residuals (%) of several curves against a reference table
+pub fn plot_residuals(series: &[(&str, &[f64], &[f64])], title: &str, output: &OutputConfig) -> Result<()> {
+    // Logarithmic energy axis only, residuals change sign
+    let labels = output.lang.labels();
+    let mut panel = Panel { x_axis: Axis::log(10.0), ..Panel::new(title, labels.energy, labels.relative_residual) };
+
+    // Zero line and one color per curve
+    if let Some((_, energies, _)) = series.first() {
+        let zeros = vec![0.0; energies.len()];
+        panel.series.push(Series::new(None, energies, &zeros, Style::Lines, "black"));
+    }
+    let colors = ["blue", "red", "dark-green", "orange", "purple", "grey"];
+    for (index, (caption, xs, ys)) in series.iter().enumerate() {
+        panel.series.push(Series::new(Some(caption), xs, ys, Style::LinesPoints, colors[index % colors.len()]));
+    }
+    show(&Chart::single(panel), output)
+}
+
+// Color map of dE/dx over the energies (columns) and a scanned parameter (rows), e.g. the
+// atomic number of the target or the thickness crossed before, on a logarithmic energy axis
+// with colors following the logarithm of dE/dx
+pub fn plot_heatmap(energies: &[f64], parameters: &[f64], stopping_powers: &[Vec<f64>],
+    y_label: &str, unit: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let mut panel = Panel { x_axis: Axis::log(10.0), ..Panel::new(title, labels.energy, y_label) };
+    panel.heatmap = Some(Heatmap::new(energies, parameters, stopping_powers, &fill(labels.stopping_power, &[unit]), true));
+    show(&Chart::single(panel), output)
+}
+
+// Label, energies, values and ratios computed/reference of one external data set
+pub type ReferenceSeries<'a> = (&'a str, &'a [f64], &'a [f64], &'a [f64]);
+
+// Two-panel comparison: the computed stopping power as a line with the external data sets
+// overlaid as points on top, and the ratio computed/reference of each data set below
+pub fn plot_compare(energies: &[f64], stopping_powers: &[f64], caption: &str,
+    references: &[ReferenceSeries], title: &str, unit: &str, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let colors = ["red", "dark-green", "orange", "purple", "black", "grey"];
+    let x_min = energies.first().copied().unwrap_or(1.0);
+    let x_max = energies.last().copied().unwrap_or(1000.0);
+    let x_axis = Axis { log_base: Some(10.0), range: Some((x_min, x_max)) };
+
+    // Upper panel on the top 60% of the figure, sharing the energy axis with the lower one
+    let mut upper = Panel {
+        x_axis,
+        y_axis: Axis::log(10.0),
+        x_tick_labels: false,
+        area: Some(Area { left: 0.1, right: 0.95, bottom: 0.38, top: 0.92 }),
+        ..Panel::new(title, "", &fill(labels.stopping_power, &[unit]))
+    };
+
+    // Computed curve in blue and one color per data set
+    upper.series.push(Series::lines(caption, energies, stopping_powers, "blue"));
+    for (index, (label, xs, ys, _)) in references.iter().enumerate() {
+        upper.series.push(Series::points(label, xs, ys, colors[index % colors.len()]));
+    }
+
+    // Lower panel with the ratio to each data set around 1
+    let mut lower = Panel {
+        x_axis,
+        area: Some(Area { left: 0.1, right: 0.95, bottom: 0.1, top: 0.36 }),
+        ..Panel::new("", labels.energy, labels.computed_over_reference)
+    };
+    lower.series.push(Series::new(None, &[x_min, x_max], &[1.0, 1.0], Style::Lines, "black"));
+    for (index, (_, xs, _, ratios)) in references.iter().enumerate() {
+        lower.series.push(Series::new(None, xs, ratios, Style::Points, colors[index % colors.len()]));
+    }
+    show(&Chart { title: title.to_string(), panels: vec![upper, lower] }, output)
+}
+
+// Draw the chart with the backend of the output once the output directory exists, on the ranges
+// and with the axis labels and style of the output, and write it as a gnuplot script when asked to
+fn show(chart: &Chart, output: &OutputConfig) -> Result<()> {
+    // The ranges, palette and logarithmic base asked for replace those of every panel, and the
+    // labels those of the panels labelling their axes; the marks asked for join their own, black
+    // ones taking the text color of the theme
+    let style = &output.style;
+    let mut chart = chart.clone();
+    for panel in &mut chart.panels {
+        panel.x_axis.range = output.x_range.or(panel.x_axis.range);
+        panel.y_axis.range = output.y_range.or(panel.y_axis.range);
+        if let Some(label) = output.x_label.as_ref().filter(|_| !panel.x_label.is_empty()) {
+            panel.x_label = label.clone();
+        }
+        if let Some(label) = output.y_label.as_ref().filter(|_| !panel.y_label.is_empty()) {
+            panel.y_label = label.clone();
+        }
+        panel.annotations.extend(output.annotations.iter().cloned());
+        panel.guides.extend(output.guides.iter().cloned());
+        for annotation in &mut panel.annotations {
+            annotation.color = style.mark_color(&annotation.color);
+        }
+        for guide in &mut panel.guides {
+            guide.color = style.mark_color(&guide.color);
+        }
+        for axis in [&mut panel.x_axis, &mut panel.y_axis] {
+            axis.log_base = axis.log_base.map(|_| style.log_base);
+        }
+        for series in &mut panel.series {
+            series.color = style.color(&series.color);
+        }
+    }
+    let chart = &chart;
+
+    // Files named after a template may have been written by an earlier run; plots drawn on the
+    // standard output have none
+    output.check_new_files(output.plot_files(&chart.title))?;
+    if output.scripts {
+        write_script(chart, output)?;
+    }
+    // Without plots no backend is started
+    if !output.plots {
+        return Ok(());
+    }
+    if output.backend.writes_images() {
+        output.create()?;
+    }
+    output.backend.renderer().draw(chart, style, &output.plot_file(&chart.title, output.plot_format.extension()))
+}
+
+// Scripts are those gnuplot would run, so they need a build with it
+#[cfg(feature = "gnuplot")]
+fn write_script(chart: &Chart, output: &OutputConfig) -> Result<()> {
+    let plot_file = |extension| output.plot_file(&chart.title, extension);
+    super::gnuplot::write_script(chart, &output.style, &plot_file(output.plot_format.extension()), &plot_file("plt"), &plot_file("dat"))
+}
+
+#[cfg(not(feature = "gnuplot"))]
+fn write_script(chart: &Chart, output: &OutputConfig) -> Result<()> {
+    let path = output.plot_file(&chart.title, "plt");
+    Err(crate::error::Error::Draw { path, message: String::from("gnuplot scripts need a build with --features gnuplot") })
 }
-// Set terminal to PNG (size 1000x600) and display the plot
-fg.set_terminal("pngcairo size 1000,600", &format!("./output/{}.png",title));
-fg.show().expect("Unable to show plot");
-}
\ No newline at end of file
@@ -0,0 +1,426 @@
+// This module draws charts with the plotters crate, into PNG images or SVG, PDF and EPS
+// documents, without any program besides this one
+use std::path::Path;
+
+use ::plotters::coord::ranged1d::{AsRangedCoord, ValueFormatter};
+use ::plotters::coord::Shift;
+use ::plotters::element::DashedPathElement;
+use ::plotters::prelude::*;
+use ::plotters::style::text_anchor::{HPos, Pos, VPos};
+
+use super::backend::{PlotBackend, PlotFormat};
+use super::chart::{Chart, Direction, Heatmap, Panel, Series, Style};
+use super::style::{colormap, Legend, PlotStyle};
+use super::vector::{Document, VectorBackend};
+use crate::error::{Error, Result};
+
+// Room (pixels at 96 dpi) for the title above the axes, the tick labels and label of the
+// abscissa below them and those of the ordinate on their left, and around figures of a single
+// panel
+const CAPTION_SIZE: u32 = 40;
+const X_LABEL_AREA: u32 = 50;
+const Y_LABEL_AREA: u32 = 80;
+const MARGIN: u32 = 15;
+// Width (pixels at 96 dpi) of the color bar right of the axes of a color map, with its labels
+const COLOR_BAR: u32 = 130;
+const COLOR_BAR_STRIP: u32 = 20;
+
+// Backend drawing the charts in this process
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Plotters;
+
+impl PlotBackend for Plotters {
+    fn draw(&self, chart: &Chart, style: &PlotStyle, path: &Path) -> Result<()> {
+        // Vector documents are at 96 dpi
+        let format = PlotFormat::of(path);
+        let sizes = Sizes::new(style, if format == PlotFormat::Png { style.scale() } else { 1.0 });
+        let background = rgb(style.theme.background());
+        let (size, background) = ((sizes.width, sizes.height), (background.0, background.1, background.2));
+        let drawn = match format {
+            PlotFormat::Png => draw_chart(&BitMapBackend::new(path, size).into_drawing_area(), chart, &sizes),
+            PlotFormat::Svg => draw_chart(&SVGBackend::new(path, size).into_drawing_area(), chart, &sizes),
+            PlotFormat::Pdf => draw_chart(&VectorBackend::new(path, Document::Pdf, size, background).into_drawing_area(), chart, &sizes),
+            PlotFormat::Eps => draw_chart(&VectorBackend::new(path, Document::Eps, size, background).into_drawing_area(), chart, &sizes),
+        };
+        drawn.map_err(|message| Error::Draw { path: path.to_path_buf(), message })
+    }
+}
+
+type Drawn = std::result::Result<(), String>;
+
+// Style of a chart with the sizes (pixels) of the image and of its elements, vector documents
+// being at 96 dpi
+struct Sizes<'a> {
+    style: &'a PlotStyle,
+    width: u32,
+    height: u32,
+    caption: u32,
+    x_label_area: u32,
+    y_label_area: u32,
+    margin: u32,
+    color_bar: u32,
+    color_bar_strip: u32,
+    font: u32,
+    title_font: u32,
+    line: u32,
+    point: u32,
+}
+
+impl<'a> Sizes<'a> {
+    fn new(style: &'a PlotStyle, scale: f64) -> Self {
+        let pixels = |size: f64| (size * scale).round().max(1.0) as u32;
+        Sizes {
+            style,
+            width: pixels(f64::from(style.width)),
+            height: pixels(f64::from(style.height)),
+            caption: pixels(f64::from(CAPTION_SIZE)),
+            x_label_area: pixels(f64::from(X_LABEL_AREA)),
+            y_label_area: pixels(f64::from(Y_LABEL_AREA)),
+            margin: pixels(f64::from(MARGIN)),
+            color_bar: pixels(f64::from(COLOR_BAR)),
+            color_bar_strip: pixels(f64::from(COLOR_BAR_STRIP)),
+            font: pixels(f64::from(style.font_size)),
+            title_font: pixels(f64::from(style.font_size + 6)),
+            line: pixels(2.0 * style.line_width),
+            point: pixels(4.0),
+        }
+    }
+}
+
+fn draw_chart<DB: DrawingBackend>(root: &DrawingArea<DB, Shift>, chart: &Chart, sizes: &Sizes) -> Drawn {
+    root.fill(&rgb(sizes.style.theme.background())).map_err(|e| e.to_string())?;
+    for panel in &chart.panels {
+        let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+        let (x_range, y_range) = (x_min..x_max, y_min..y_max);
+        match (panel.x_axis.log_base.is_some(), panel.y_axis.log_base.is_some()) {
+            (false, false) => draw_panel(root, panel, sizes, x_range, y_range)?,
+            (true, false) => draw_panel(root, panel, sizes, x_range.log_scale(), y_range)?,
+            (false, true) => draw_panel(root, panel, sizes, x_range, y_range.log_scale())?,
+            (true, true) => draw_panel(root, panel, sizes, x_range.log_scale(), y_range.log_scale())?,
+        }
+    }
+    root.present().map_err(|e| e.to_string())
+}
+
+// Draw the axes and series of a panel on their part of `root`, which is widened to hold the
+// title and labels around the axes when the panel has an area, and narrowed by the color bar
+// of its color map
+fn draw_panel<DB, X, Y>(root: &DrawingArea<DB, Shift>, panel: &Panel, sizes: &Sizes, x: X, y: Y) -> Drawn
+where
+    DB: DrawingBackend,
+    X: AsRangedCoord<Value = f64>,
+    Y: AsRangedCoord<Value = f64>,
+    X::CoordDescType: ValueFormatter<f64>,
+    Y::CoordDescType: ValueFormatter<f64>,
+{
+    let font = sizes.style.font.as_str();
+    let (background, foreground) = (rgb(sizes.style.theme.background()), rgb(sizes.style.theme.foreground()));
+    let caption = if panel.title.is_empty() { 0 } else { sizes.caption };
+    let area = match panel.area {
+        Some(area) => {
+            let (width, height) = (f64::from(sizes.width), f64::from(sizes.height));
+            let left = (area.left * width - f64::from(sizes.y_label_area)).max(0.0);
+            let top = ((1.0 - area.top) * height - f64::from(caption)).max(0.0);
+            let right = (area.right * width).min(width);
+            let bottom = ((1.0 - area.bottom) * height + f64::from(sizes.x_label_area)).min(height);
+            root.clone().shrink((left as u32, top as u32), ((right - left) as u32, (bottom - top) as u32))
+        }
+        None => root.margin(sizes.margin, sizes.margin, sizes.margin, sizes.margin),
+    };
+    let (area, bar) = match &panel.heatmap {
+        Some(heatmap) => {
+            let (area, bar) = area.split_horizontally(area.dim_in_pixel().0.saturating_sub(sizes.color_bar));
+            (area, Some((bar, heatmap)))
+        }
+        None => (area, None),
+    };
+
+    let mut builder = ChartBuilder::on(&area);
+    builder.x_label_area_size(sizes.x_label_area).y_label_area_size(sizes.y_label_area);
+    if !panel.title.is_empty() {
+        builder.caption(&panel.title, (font, sizes.title_font).into_font().color(&foreground));
+    }
+    let mut chart = builder.build_cartesian_2d(x, y).map_err(|e| e.to_string())?;
+
+    // Labelled ticks are drawn below the axes after them
+    let hide_x_labels = !panel.x_tick_labels || !panel.x_ticks.is_empty();
+    let no_label = |_: &f64| String::new();
+    let mut mesh = chart.configure_mesh();
+    mesh.x_desc(panel.x_label.as_str())
+        .y_desc(panel.y_label.as_str())
+        .label_style((font, sizes.font).into_font().color(&foreground))
+        .axis_style(foreground);
+    if sizes.style.grid {
+        mesh.bold_line_style(foreground.mix(0.2)).light_line_style(TRANSPARENT);
+    } else {
+        mesh.disable_mesh();
+    }
+    if hide_x_labels {
+        mesh.x_label_formatter(&no_label);
+    }
+    mesh.draw().map_err(|e| e.to_string())?;
+
+    if let Some(heatmap) = &panel.heatmap {
+        draw_heatmap(&mut chart, heatmap, panel)?;
+    }
+    for series in &panel.series {
+        draw_series(&mut chart, series, panel, sizes)?;
+    }
+    draw_marks(&mut chart, panel, sizes)?;
+    let position = match sizes.style.legend {
+        Legend::TopRight => Some(SeriesLabelPosition::UpperRight),
+        Legend::TopLeft => Some(SeriesLabelPosition::UpperLeft),
+        Legend::BottomRight => Some(SeriesLabelPosition::LowerRight),
+        Legend::BottomLeft => Some(SeriesLabelPosition::LowerLeft),
+        Legend::None => None,
+    };
+    if let Some(position) = position.filter(|_| panel.series.iter().any(|series| series.caption.is_some())) {
+        chart
+            .configure_series_labels()
+            .position(position)
+            .background_style(background.mix(0.8))
+            .border_style(foreground)
+            .label_font((font, sizes.font).into_font().color(&foreground))
+            .draw()
+            .map_err(|e| e.to_string())?;
+    }
+
+    let (y_min, _) = panel.y_range();
+    for (x, label) in &panel.x_ticks {
+        let (column, row) = chart.backend_coord(&(*x, y_min));
+        let style = (font, sizes.font).into_font().color(&foreground).pos(Pos::new(HPos::Center, VPos::Top));
+        root.draw(&Text::new(label.as_str(), (column, row + sizes.font as i32 / 2), style)).map_err(|e| e.to_string())?;
+    }
+
+    if let Some((bar, heatmap)) = bar {
+        let (min, max) = heatmap.limits();
+        if heatmap.log {
+            draw_color_bar(&bar, heatmap, sizes, caption, (min..max).log_scale())?;
+        } else {
+            draw_color_bar(&bar, heatmap, sizes, caption, min..max)?;
+        }
+    }
+    Ok(())
+}
+
+// Cells of a color map inside the ranges of the panel, cut at their limits
+fn draw_heatmap<DB, X, Y>(chart: &mut ChartContext<DB, Cartesian2d<X, Y>>, heatmap: &Heatmap, panel: &Panel) -> Drawn
+where
+    DB: DrawingBackend,
+    X: Ranged<ValueType = f64>,
+    Y: Ranged<ValueType = f64>,
+{
+    let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+    let x_edges = heatmap.x_edges(panel.x_axis.log_base.is_some());
+    let y_edges = heatmap.y_edges(panel.y_axis.log_base.is_some());
+    let limits = heatmap.limits();
+    let mut cells = Vec::new();
+    for (row, values) in heatmap.values.iter().enumerate().take(y_edges.len().saturating_sub(1)) {
+        let (bottom, top) = (y_edges[row].clamp(y_min, y_max), y_edges[row + 1].clamp(y_min, y_max));
+        for (column, value) in values.iter().enumerate().take(x_edges.len().saturating_sub(1)) {
+            let (left, right) = (x_edges[column].clamp(x_min, x_max), x_edges[column + 1].clamp(x_min, x_max));
+            if let Some(fraction) = heatmap.fraction(*value, limits).filter(|_| left != right && bottom != top) {
+                let (r, g, b) = colormap(fraction);
+                cells.push(Rectangle::new([(left, bottom), (right, top)], RGBColor(r, g, b).filled()));
+            }
+        }
+    }
+    chart.draw_series(cells).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Strip of the colors of a color map from its lowest value at the bottom to its highest at
+// the top, level with the axes beside it, with the values and label on its right
+fn draw_color_bar<DB, Y>(bar: &DrawingArea<DB, Shift>, heatmap: &Heatmap, sizes: &Sizes, caption: u32, y: Y) -> Drawn
+where
+    DB: DrawingBackend,
+    Y: AsRangedCoord<Value = f64>,
+    Y::CoordDescType: ValueFormatter<f64>,
+{
+    let font = sizes.style.font.as_str();
+    let foreground = rgb(sizes.style.theme.foreground());
+    let (width, _) = bar.dim_in_pixel();
+    let mut builder = ChartBuilder::on(bar);
+    builder
+        .margin_top(caption)
+        .margin_left(sizes.margin)
+        .x_label_area_size(sizes.x_label_area)
+        .right_y_label_area_size(width.saturating_sub(sizes.margin + sizes.color_bar_strip));
+    let mut chart = builder.build_cartesian_2d(0.0..1.0, y).map_err(|e| e.to_string())?;
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .disable_x_axis()
+        .y_desc(heatmap.label.as_str())
+        .label_style((font, sizes.font).into_font().color(&foreground))
+        .axis_style(foreground)
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    // Enough steps to look continuous
+    const STEPS: usize = 100;
+    let limits = heatmap.limits();
+    let steps = (0..STEPS).map(|step| {
+        let (from, to) = (step as f64 / STEPS as f64, (step + 1) as f64 / STEPS as f64);
+        let (r, g, b) = colormap((from + to) / 2.0);
+        Rectangle::new([(0.0, heatmap.value(from, limits)), (1.0, heatmap.value(to, limits))], RGBColor(r, g, b).filled())
+    });
+    chart.draw_series(steps).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn draw_series<DB, X, Y>(chart: &mut ChartContext<DB, Cartesian2d<X, Y>>, series: &Series, panel: &Panel, sizes: &Sizes) -> Drawn
+where
+    DB: DrawingBackend,
+    X: Ranged<ValueType = f64>,
+    Y: Ranged<ValueType = f64>,
+{
+    let color = rgb(&series.color);
+    let (line, point) = (sizes.line, sizes.point);
+    // Points outside logarithmic axes cannot be drawn, and plotters would move those outside
+    // the ranges to their limits
+    let drawable = |(x, y): &(f64, f64)| (panel.x_axis.log_base.is_none() || *x > 0.0) && (panel.y_axis.log_base.is_none() || *y > 0.0);
+    let points: Vec<(f64, f64)> = series.xs.iter().copied().zip(series.ys.iter().copied()).filter(drawable).collect();
+    let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+    let inside = |(x, y): &&(f64, f64)| (x_min..=x_max).contains(x) && (y_min..=y_max).contains(y);
+
+    // Series without a caption are left out of the legend
+    let caption = series.caption.as_deref();
+    match &series.style {
+        Style::Lines | Style::LinesPoints => {
+            if series.style == Style::LinesPoints {
+                chart.draw_series(points.iter().filter(inside).map(|point| Circle::new(*point, sizes.point, color.stroke_width(1)))).map_err(|e| e.to_string())?;
+            }
+            let pieces = clip(&points, panel);
+            let annotation = chart.draw_series(pieces.into_iter().map(|piece| PathElement::new(piece, color.stroke_width(sizes.line)))).map_err(|e| e.to_string())?;
+            if let Some(caption) = caption {
+                annotation.label(caption).legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(line)));
+            }
+        }
+        Style::Points => {
+            let annotation = chart.draw_series(points.iter().filter(inside).map(|point| Circle::new(*point, sizes.point, color.stroke_width(1)))).map_err(|e| e.to_string())?;
+            if let Some(caption) = caption {
+                annotation.label(caption).legend(move |(x, y)| Circle::new((x + 10, y), point, color.stroke_width(1)));
+            }
+        }
+        Style::Boxes { alpha } => {
+            let fill = color.mix(*alpha).filled();
+            let annotation = chart.draw_series(points.iter().map(|(x, y)| Rectangle::new([(x - 0.4, 0.0), (x + 0.4, *y)], fill))).map_err(|e| e.to_string())?;
+            if let Some(caption) = caption {
+                annotation.label(caption).legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], fill));
+            }
+        }
+        Style::Band { upper, alpha } => {
+            let fill = color.mix(*alpha).filled();
+            let upper = series.xs.iter().copied().zip(upper.iter().copied()).filter(drawable).rev();
+            let outline: Vec<(f64, f64)> = points.into_iter().chain(upper).collect();
+            let annotation = chart.draw_series(std::iter::once(Polygon::new(outline, fill))).map_err(|e| e.to_string())?;
+            if let Some(caption) = caption {
+                annotation.label(caption).legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], fill));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Guides across the axes and the marked points, each labelled in its color, leaving out those
+// outside the ranges
+fn draw_marks<DB, X, Y>(chart: &mut ChartContext<DB, Cartesian2d<X, Y>>, panel: &Panel, sizes: &Sizes) -> Drawn
+where
+    DB: DrawingBackend,
+    X: Ranged<ValueType = f64>,
+    Y: Ranged<ValueType = f64>,
+{
+    let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+    let font = (sizes.style.font.as_str(), sizes.font).into_font();
+    let gap = sizes.font as i32 / 3;
+    for guide in &panel.guides {
+        let color = rgb(&guide.color);
+        // Vertical guides are labelled right of their top, horizontal ones above their right end
+        let (ends, offset, position) = match guide.direction {
+            Direction::Vertical if (x_min..=x_max).contains(&guide.value) => {
+                ([(guide.value, y_min), (guide.value, y_max)], (gap, gap), Pos::new(HPos::Left, VPos::Top))
+            }
+            Direction::Horizontal if (y_min..=y_max).contains(&guide.value) => {
+                ([(x_min, guide.value), (x_max, guide.value)], (-gap, -gap), Pos::new(HPos::Right, VPos::Bottom))
+            }
+            _ => continue,
+        };
+        let line = DashedPathElement::new(ends, 3 * sizes.line, 2 * sizes.line, color.stroke_width(sizes.line.div_ceil(2)));
+        chart.draw_series(std::iter::once(line)).map_err(|e| e.to_string())?;
+        let label = EmptyElement::at(ends[1]) + Text::new(guide.label.clone(), offset, font.clone().color(&color).pos(position));
+        chart.draw_series(std::iter::once(label)).map_err(|e| e.to_string())?;
+    }
+
+    let inside = |x: f64, y: f64| (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y);
+    for annotation in panel.annotations.iter().filter(|annotation| inside(annotation.x, annotation.y)) {
+        let color = rgb(&annotation.color);
+        let offset = sizes.point as i32 + gap;
+        let style = font.clone().color(&color).pos(Pos::new(HPos::Left, VPos::Bottom));
+        let mark = EmptyElement::at((annotation.x, annotation.y))
+            + Circle::new((0, 0), sizes.point, color.filled())
+            + Text::new(annotation.label.clone(), (offset, -offset), style);
+        chart.draw_series(std::iter::once(mark)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Pieces of the line through `points` inside the ranges of the panel, cut where it crosses
+// their limits (straight on the scales of the axes, as it is drawn)
+pub(super) fn clip(points: &[(f64, f64)], panel: &Panel) -> Vec<Vec<(f64, f64)>> {
+    let (x_log, y_log) = (panel.x_axis.log_base.is_some(), panel.y_axis.log_base.is_some());
+    let scale = |value: f64, log: bool| if log { value.log10() } else { value };
+    let unscale = |value: f64, log: bool| if log { 10f64.powf(value) } else { value };
+    let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+    let (x_min, x_max, y_min, y_max) = (scale(x_min, x_log), scale(x_max, x_log), scale(y_min, y_log), scale(y_max, y_log));
+
+    let mut pieces: Vec<Vec<(f64, f64)>> = Vec::new();
+    let mut joined = false;
+    for pair in points.windows(2) {
+        let (x0, y0) = (scale(pair[0].0, x_log), scale(pair[0].1, y_log));
+        let (dx, dy) = (scale(pair[1].0, x_log) - x0, scale(pair[1].1, y_log) - y0);
+
+        // Liang-Barsky: the part t0..t1 of the segment between every limit
+        let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+        let limits = [(-dx, x0 - x_min), (dx, x_max - x0), (-dy, y0 - y_min), (dy, y_max - y0)];
+        let visible = limits.iter().all(|&(p, q)| {
+            if p == 0.0 {
+                return q >= 0.0;
+            }
+            let t = q / p;
+            if p < 0.0 { t0 = t0.max(t) } else { t1 = t1.min(t) }
+            t0 <= t1
+        });
+        if !visible {
+            joined = false;
+            continue;
+        }
+
+        let point = |t: f64| (unscale(x0 + t * dx, x_log), unscale(y0 + t * dy, y_log));
+        match pieces.last_mut().filter(|_| joined && t0 == 0.0) {
+            Some(piece) => piece.push(point(t1)),
+            None => pieces.push(vec![point(t0), point(t1)]),
+        }
+        joined = t1 == 1.0;
+    }
+    pieces
+}
+
+// RGB of the gnuplot color names used here, or of a "#rrggbb" color; black otherwise
+pub(super) fn rgb(color: &str) -> RGBColor {
+    match color {
+        "blue" => RGBColor(0, 0, 255),
+        "red" => RGBColor(255, 0, 0),
+        "dark-green" => RGBColor(0, 100, 0),
+        "light-blue" => RGBColor(173, 216, 230),
+        "orange" => RGBColor(255, 165, 0),
+        "purple" => RGBColor(192, 128, 255),
+        "grey" | "gray" => RGBColor(192, 192, 192),
+        "white" => RGBColor(255, 255, 255),
+        _ => {
+            let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6);
+            let channel = |i: usize| hex.and_then(|hex| u8::from_str_radix(&hex[i..i + 2], 16).ok()).unwrap_or(0);
+            RGBColor(channel(0), channel(2), channel(4))
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// This module defines the style every chart is drawn with: size and resolution of the images,
+// fonts, theme, colors and widths of the curves, colors of the color maps, legend, grid and base
+// of the logarithmic axes
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+// Colors the figures give their curves, in turn; a palette replaces them in the same order
+pub const DEFAULT_PALETTE: [&str; 6] = ["blue", "red", "dark-green", "orange", "purple", "black"];
+
+// Colors of the curves on dark backgrounds, lighter ones in the order of `DEFAULT_PALETTE`
+pub const DARK_PALETTE: [&str; 6] = ["#5fa8ff", "#ff6b6b", "#6fd36f", "#ffb347", "#d4a5ff", "#e6e6e6"];
+
+// Colors of the color maps (viridis) from their lowest values to their highest, evenly spaced
+pub const COLORMAP: [(u8, u8, u8); 9] = [
+    (68, 1, 84),
+    (71, 45, 123),
+    (59, 82, 139),
+    (44, 114, 142),
+    (33, 145, 140),
+    (40, 174, 128),
+    (94, 201, 98),
+    (173, 220, 48),
+    (253, 231, 37),
+];
+
+// Resolution (dots per inch) the sizes of the images, fonts and lines are given at
+pub const BASE_DPI: u32 = 96;
+
+// Style of the charts. `width` and `height` are pixels at 96 dpi, the images having `dpi / 96`
+// times as many with fonts and lines as much thicker; `terminal` replaces the gnuplot terminal
+// of PNG images (pngcairo). The palette is that of the theme unless another one is given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlotStyle {
+    pub width: u32,
+    pub height: u32,
+    pub dpi: u32,
+    pub terminal: Option<String>,
+    pub theme: Theme,
+    pub palette: Vec<String>,
+    pub line_width: f64,
+    pub font: String,
+    pub font_size: u32,
+    pub legend: Legend,
+    pub grid: bool,
+    pub log_base: f64,
+}
+
+impl Default for PlotStyle {
+    fn default() -> Self {
+        PlotStyle {
+            width: 1000,
+            height: 600,
+            dpi: BASE_DPI,
+            terminal: None,
+            theme: Theme::default(),
+            palette: Theme::default().palette(),
+            line_width: 1.0,
+            font: String::from("sans-serif"),
+            font_size: 14,
+            legend: Legend::default(),
+            grid: false,
+            log_base: 10.0,
+        }
+    }
+}
+
+impl PlotStyle {
+    // Factor from the sizes at 96 dpi to those of the images
+    pub fn scale(&self) -> f64 {
+        f64::from(self.dpi) / f64::from(BASE_DPI)
+    }
+
+    // Size of the images in pixels
+    pub fn pixels(&self) -> (u32, u32) {
+        let scale = self.scale();
+        ((f64::from(self.width) * scale).round() as u32, (f64::from(self.height) * scale).round() as u32)
+    }
+
+    // Color of the palette taking the place of a default color, which other colors keep
+    pub fn color(&self, color: &str) -> String {
+        match DEFAULT_PALETTE.iter().position(|default| *default == color) {
+            Some(index) if !self.palette.is_empty() => self.palette[index % self.palette.len()].clone(),
+            _ => color.to_string(),
+        }
+    }
+
+    // Color of a marked point or guide: the foreground of the theme in place of black, as the
+    // text around it
+    pub fn mark_color(&self, color: &str) -> String {
+        if color == "black" { self.theme.foreground().to_string() } else { color.to_string() }
+    }
+}
+
+// Colors of the figures: dark text and axes on white for papers, or light ones on a dark
+// background for slides
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Theme {
+    // Color of the background
+    pub fn background(&self) -> &'static str {
+        match self {
+            Theme::Light => "white",
+            Theme::Dark => "#1e1e1e",
+        }
+    }
+
+    // Color of the titles, labels, axes, ticks and legend frame
+    pub fn foreground(&self) -> &'static str {
+        match self {
+            Theme::Light => "black",
+            Theme::Dark => "#e6e6e6",
+        }
+    }
+
+    // Colors the curves take in turn
+    pub fn palette(&self) -> Vec<String> {
+        let palette = match self {
+            Theme::Light => DEFAULT_PALETTE,
+            Theme::Dark => DARK_PALETTE,
+        };
+        palette.iter().map(|color| color.to_string()).collect()
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Theme::Light => write!(f, "light"),
+            Theme::Dark => write!(f, "dark"),
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "light" => Ok(Theme::Light),
+            "dark" => Ok(Theme::Dark),
+            _ => Err(format!("unknown theme '{}' (expected light or dark)", s)),
+        }
+    }
+}
+
+// Color of the color maps at a fraction between 0 (lowest values) and 1 (highest), between
+// those of `COLORMAP`
+pub fn colormap(fraction: f64) -> (u8, u8, u8) {
+    let position = fraction.clamp(0.0, 1.0) * (COLORMAP.len() - 1) as f64;
+    let index = (position.floor() as usize).min(COLORMAP.len() - 2);
+    let t = position - index as f64;
+    let (from, to) = (COLORMAP[index], COLORMAP[index + 1]);
+    let channel = |from: u8, to: u8| (f64::from(from) + t * (f64::from(to) - f64::from(from))).round() as u8;
+    (channel(from.0, to.0), channel(from.1, to.1), channel(from.2, to.2))
+}
+
+// Corner of the axes holding the legend, or none
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Legend {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+    None,
+}
+
+impl fmt::Display for Legend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Legend::TopRight => write!(f, "top-right"),
+            Legend::TopLeft => write!(f, "top-left"),
+            Legend::BottomRight => write!(f, "bottom-right"),
+            Legend::BottomLeft => write!(f, "bottom-left"),
+            Legend::None => write!(f, "none"),
+        }
+    }
+}
+
+impl FromStr for Legend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "top-right" => Ok(Legend::TopRight),
+            "top-left" => Ok(Legend::TopLeft),
+            "bottom-right" => Ok(Legend::BottomRight),
+            "bottom-left" => Ok(Legend::BottomLeft),
+            "none" => Ok(Legend::None),
+            _ => Err(format!("unknown legend position '{}' (expected top-right, top-left, bottom-right, bottom-left or none)", s)),
+        }
+    }
+}
@@ -0,0 +1,428 @@
+// This module draws charts as text on the standard output, in Unicode braille dots (two across
+// and four down per character) colored when it is a terminal, to look at them over a remote
+// shell without a display or copying any image
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use super::backend::PlotBackend;
+use super::chart::{Chart, Direction, Heatmap, Panel, Series, Style};
+use super::plotters::{clip, rgb};
+use super::style::{colormap, Legend, PlotStyle};
+use crate::error::{Error, Result};
+
+// Characters across the charts, unless COLUMNS gives the width of the terminal, and lines of
+// the axes of each panel
+const COLUMNS: usize = 80;
+const ROWS: usize = 20;
+
+// Characters of the cells of color maps without colors, from their lowest values to their highest
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
+
+// Backend writing the charts to the standard output instead of image files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Terminal;
+
+impl PlotBackend for Terminal {
+    // Nothing is written to `path`, the file the chart would be drawn into by the others
+    fn draw(&self, chart: &Chart, style: &PlotStyle, _path: &Path) -> Result<()> {
+        let stdout = io::stdout();
+        // Colors are left out of pipes, and when NO_COLOR is set
+        let colored = stdout.is_terminal() && env::var_os("NO_COLOR").is_none();
+        let width = env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()).unwrap_or(COLUMNS);
+        let text = render(chart, style, width, colored);
+        stdout.lock().write_all(text.as_bytes()).map_err(Error::Output)
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+// Text of a chart `width` characters wide: its title, then each panel with its own
+fn render(chart: &Chart, style: &PlotStyle, width: usize, colored: bool) -> String {
+    let mut text = format!("{}\n", centered(&chart.title, width));
+    for panel in &chart.panels {
+        text.push('\n');
+        if chart.panels.len() > 1 {
+            text.push_str(&format!("{}\n", centered(&panel.title, width)));
+        }
+        render_panel(&mut text, panel, style, width, colored);
+    }
+    text.push('\n');
+    text
+}
+
+// Axes of a panel: the label of the ordinate above them, its values at the top, middle and
+// bottom lines on their left, those of the abscissa and its label below, then the legend and
+// the color bar of a color map
+fn render_panel(text: &mut String, panel: &Panel, style: &PlotStyle, width: usize, colored: bool) {
+    let scale = Scale::new(panel);
+    let y_values: Vec<String> = [1.0, 0.5, 0.0].iter().map(|&fraction| number(scale.y_value(fraction))).collect();
+    let margin = y_values.iter().map(|value| value.chars().count()).max().unwrap_or(0) + 1;
+    let columns = width.saturating_sub(margin + 2).max(20);
+
+    let mut canvas = Canvas::new(columns, ROWS);
+    if let Some(heatmap) = &panel.heatmap {
+        draw_heatmap(&mut canvas, heatmap, panel, &scale);
+    }
+    for series in &panel.series {
+        draw_series(&mut canvas, series, panel, &scale);
+    }
+    draw_marks(&mut canvas, panel, &scale);
+
+    if !panel.y_label.is_empty() {
+        text.push_str(&format!("{}\n", panel.y_label));
+    }
+    for (row, line) in canvas.lines(colored).iter().enumerate() {
+        let value = match row {
+            0 => Some(&y_values[0]),
+            row if row == ROWS / 2 => Some(&y_values[1]),
+            row if row == ROWS - 1 => Some(&y_values[2]),
+            _ => None,
+        };
+        let line = match value {
+            Some(value) => format!("{:>margin$} ┤{}", value, line, margin = margin),
+            None => format!("{:>margin$} │{}", "", line, margin = margin),
+        };
+        text.push_str(&format!("{}\n", line.trim_end()));
+    }
+    text.push_str(&format!("{:>margin$} └{}\n", "", "─".repeat(columns), margin = margin));
+
+    // Labelled ticks where they fit, or the values of the abscissa at its ends and middle
+    let mut ticks = vec![' '; columns + margin + 2];
+    let mut place = |column: usize, label: &str, from: usize| -> usize {
+        let label: Vec<char> = label.chars().collect();
+        if label.len() > ticks.len() {
+            return from;
+        }
+        let start = (margin + 2 + column).saturating_sub(label.len() / 2).max(from).min(ticks.len().saturating_sub(label.len()));
+        if start < from {
+            return from;
+        }
+        ticks[start..start + label.len()].copy_from_slice(&label);
+        start + label.len() + 1
+    };
+    if panel.x_ticks.is_empty() {
+        let mut from = 0;
+        for fraction in [0.0, 0.5, 1.0] {
+            from = place(scale.column(fraction, columns), &number(scale.x_value(fraction)), from);
+        }
+    } else {
+        let mut from = 0;
+        for (x, label) in &panel.x_ticks {
+            let fraction = scale.x_fraction(*x);
+            if (0.0..=1.0).contains(&fraction) {
+                from = place(scale.column(fraction, columns), label, from);
+            }
+        }
+    }
+    text.push_str(&format!("{}\n", ticks.iter().collect::<String>().trim_end()));
+    if !panel.x_label.is_empty() {
+        text.push_str(&format!("{:margin$}{}\n", "", centered(&panel.x_label, columns + 2), margin = margin));
+    }
+
+    // Series without a caption are left out of the legend
+    if style.legend != Legend::None {
+        for series in panel.series.iter().filter(|series| series.caption.is_some()) {
+            let mark = match series.style {
+                Style::Points => "  o ",
+                Style::Boxes { .. } | Style::Band { .. } => " ⣿⣿ ",
+                _ => " ⠤⠤ ",
+            };
+            let mark = if colored { paint(mark, rgb_of(&series.color)) } else { mark.to_string() };
+            text.push_str(&format!("{:margin$}{} {}\n", "", mark, series.caption.as_deref().unwrap_or_default(), margin = margin));
+        }
+    }
+    if let Some(heatmap) = &panel.heatmap {
+        let (min, max) = heatmap.limits();
+        let bar: String = (0..20).map(|step| shade(step as f64 / 19.0, colored)).collect();
+        text.push_str(&format!("{:margin$}  {} {} {}  {}\n", "", number(min), bar, number(max), heatmap.label, margin = margin));
+    }
+}
+
+// Each character of the axes shaded by the cell of the color map under its middle
+fn draw_heatmap(canvas: &mut Canvas, heatmap: &Heatmap, panel: &Panel, scale: &Scale) {
+    let (x_log, y_log) = (panel.x_axis.log_base.is_some(), panel.y_axis.log_base.is_some());
+    let limits = heatmap.limits();
+    for row in 0..canvas.rows {
+        let y = scale.y_value(1.0 - (row as f64 + 0.5) / canvas.rows as f64);
+        for column in 0..canvas.columns {
+            let x = scale.x_value((column as f64 + 0.5) / canvas.columns as f64);
+            if let Some(fraction) = heatmap.cell(x, y, x_log, y_log).and_then(|value| heatmap.fraction(value, limits)) {
+                canvas.shades[row * canvas.columns + column] = Some(fraction);
+            }
+        }
+    }
+}
+
+fn draw_series(canvas: &mut Canvas, series: &Series, panel: &Panel, scale: &Scale) {
+    let color = rgb_of(&series.color);
+    let (columns, rows) = (canvas.dot_columns(), canvas.dot_rows());
+    let dot = |(x, y): (f64, f64)| (scale.x_fraction(x) * columns, scale.y_fraction(y) * rows);
+    let drawable = |(x, y): &(f64, f64)| (panel.x_axis.log_base.is_none() || *x > 0.0) && (panel.y_axis.log_base.is_none() || *y > 0.0);
+    let points: Vec<(f64, f64)> = series.xs.iter().copied().zip(series.ys.iter().copied()).filter(drawable).collect();
+
+    let lines = |canvas: &mut Canvas, points: &[(f64, f64)]| {
+        for piece in clip(points, panel) {
+            for pair in piece.windows(2) {
+                canvas.line(dot(pair[0]), dot(pair[1]), color);
+            }
+        }
+    };
+    match &series.style {
+        Style::Lines => lines(canvas, &points),
+        Style::Points | Style::LinesPoints => {
+            if series.style == Style::LinesPoints {
+                lines(canvas, &points);
+            }
+            for point in points.iter().filter(|(x, y)| (0.0..=1.0).contains(&scale.x_fraction(*x)) && (0.0..=1.0).contains(&scale.y_fraction(*y))) {
+                let (x, y) = dot(*point);
+                canvas.set(x.round() as i64, y.round() as i64, color);
+            }
+        }
+        // Boxes are filled from zero (the bottom of logarithmic axes) to their value
+        Style::Boxes { .. } => {
+            let base = if panel.y_axis.log_base.is_some() { 0.0 } else { scale.y_fraction(0.0).clamp(0.0, 1.0) * rows };
+            for &(x, y) in &points {
+                let (left, right) = (dot((x - 0.4, y)).0.max(0.0), dot((x + 0.4, y)).0.min(columns));
+                let top = (scale.y_fraction(y).clamp(0.0, 1.0) * rows).round() as i64;
+                let (low, high) = (top.min(base.round() as i64), top.max(base.round() as i64));
+                for column in left.round() as i64..=right.round() as i64 {
+                    for row in low..=high {
+                        canvas.set(column, row, color);
+                    }
+                }
+            }
+        }
+        // Bands are drawn by their bounds
+        Style::Band { upper, .. } => {
+            lines(canvas, &points);
+            let upper: Vec<(f64, f64)> = series.xs.iter().copied().zip(upper.iter().copied()).filter(drawable).collect();
+            lines(canvas, &upper);
+        }
+    }
+}
+
+// Guides across the axes as dotted lines and the marked points as bullets, labelled in their
+// colors: vertical guides right of their top, horizontal ones above their right end
+fn draw_marks(canvas: &mut Canvas, panel: &Panel, scale: &Scale) {
+    let (columns, rows) = (canvas.dot_columns(), canvas.dot_rows());
+    let inside = |fraction: f64| (0.0..=1.0).contains(&fraction);
+    for guide in &panel.guides {
+        let color = rgb_of(&guide.color);
+        match guide.direction {
+            Direction::Vertical if inside(scale.x_fraction(guide.value)) => {
+                let x = scale.x_fraction(guide.value) * columns;
+                for y in (0..=rows as i64).step_by(2) {
+                    canvas.set(x.round() as i64, y, color);
+                }
+                canvas.write(x + 2.0, rows, &guide.label, color);
+            }
+            Direction::Horizontal if inside(scale.y_fraction(guide.value)) => {
+                let y = scale.y_fraction(guide.value) * rows;
+                for x in (0..=columns as i64).step_by(2) {
+                    canvas.set(x, y.round() as i64, color);
+                }
+                canvas.write(columns, y + 4.0, &guide.label, color);
+            }
+            _ => {}
+        }
+    }
+    for annotation in &panel.annotations {
+        let (x, y) = (scale.x_fraction(annotation.x), scale.y_fraction(annotation.y));
+        if inside(x) && inside(y) {
+            let color = rgb_of(&annotation.color);
+            canvas.write(x * columns, y * rows, "●", color);
+            canvas.write(x * columns + 4.0, y * rows + 4.0, &annotation.label, color);
+        }
+    }
+}
+
+// Ranges of the axes of a panel, on their scales (of the logarithms on logarithmic ones)
+struct Scale {
+    x: (f64, f64),
+    y: (f64, f64),
+    x_log: Option<f64>,
+    y_log: Option<f64>,
+}
+
+impl Scale {
+    fn new(panel: &Panel) -> Self {
+        let (x_log, y_log) = (panel.x_axis.log_base, panel.y_axis.log_base);
+        let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+        Scale {
+            x: (scaled(x_min, x_log), scaled(x_max, x_log)),
+            y: (scaled(y_min, y_log), scaled(y_max, y_log)),
+            x_log,
+            y_log,
+        }
+    }
+
+    // Fraction of the width or height of the axes at which a value is
+    fn x_fraction(&self, x: f64) -> f64 {
+        (scaled(x, self.x_log) - self.x.0) / (self.x.1 - self.x.0)
+    }
+
+    fn y_fraction(&self, y: f64) -> f64 {
+        (scaled(y, self.y_log) - self.y.0) / (self.y.1 - self.y.0)
+    }
+
+    // Value at a fraction of the width or height of the axes
+    fn x_value(&self, fraction: f64) -> f64 {
+        unscaled(self.x.0 + fraction * (self.x.1 - self.x.0), self.x_log)
+    }
+
+    fn y_value(&self, fraction: f64) -> f64 {
+        unscaled(self.y.0 + fraction * (self.y.1 - self.y.0), self.y_log)
+    }
+
+    // Character of `columns` at a fraction of the width
+    fn column(&self, fraction: f64, columns: usize) -> usize {
+        (fraction * (columns - 1) as f64).round() as usize
+    }
+}
+
+pub(super) fn scaled(value: f64, log_base: Option<f64>) -> f64 {
+    match log_base {
+        Some(base) => value.log(base),
+        None => value,
+    }
+}
+
+pub(super) fn unscaled(value: f64, log_base: Option<f64>) -> f64 {
+    match log_base {
+        Some(base) => base.powf(value),
+        None => value,
+    }
+}
+
+// Characters of braille dots, each in the color of the last series drawn over it, or of the
+// labels written over them, over the shade of a color map (its fraction of the color bar) when
+// there is one. Dots are counted from the bottom left.
+struct Canvas {
+    columns: usize,
+    rows: usize,
+    dots: Vec<u8>,
+    colors: Vec<Option<Rgb>>,
+    text: Vec<Option<char>>,
+    shades: Vec<Option<f64>>,
+}
+
+impl Canvas {
+    fn new(columns: usize, rows: usize) -> Self {
+        let cells = columns * rows;
+        Canvas { columns, rows, dots: vec![0; cells], colors: vec![None; cells], text: vec![None; cells], shades: vec![None; cells] }
+    }
+
+    // Largest dot coordinates
+    fn dot_columns(&self) -> f64 {
+        (2 * self.columns - 1) as f64
+    }
+
+    fn dot_rows(&self) -> f64 {
+        (4 * self.rows - 1) as f64
+    }
+
+    // Set a dot, when it is inside the axes
+    fn set(&mut self, x: i64, y: i64, color: Rgb) {
+        let (width, height) = (2 * self.columns as i64, 4 * self.rows as i64);
+        if !(0..width).contains(&x) || !(0..height).contains(&y) {
+            return;
+        }
+        let (x, y) = (x as usize, (height - 1 - y) as usize);
+        // Bits of the dots of a braille character, by column and line from the top
+        const BITS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        let cell = (y / 4) * self.columns + x / 2;
+        self.dots[cell] |= BITS[x % 2][y % 4];
+        self.colors[cell] = Some(color);
+    }
+
+    // Dots along the segment between two points (in dots)
+    fn line(&mut self, from: (f64, f64), to: (f64, f64), color: Rgb) {
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let (x, y) = (from.0 + t * (to.0 - from.0), from.1 + t * (to.1 - from.1));
+            self.set(x.round() as i64, y.round() as i64, color);
+        }
+    }
+
+    // Write `text` over the characters from that of a dot on, or ending at the right of the axes
+    // when it would not fit
+    fn write(&mut self, x: f64, y: f64, text: &str, color: Rgb) {
+        let (column, row) = ((x.max(0.0) / 2.0) as usize, self.rows - 1 - (y.clamp(0.0, self.dot_rows()) / 4.0) as usize);
+        let characters: Vec<char> = text.chars().take(self.columns).collect();
+        let start = column.min(self.columns - characters.len());
+        for (index, character) in characters.into_iter().enumerate() {
+            let cell = row * self.columns + start + index;
+            self.text[cell] = Some(character);
+            self.colors[cell] = Some(color);
+        }
+    }
+
+    // Lines of characters from the top, in the colors of their dots when `colored`, on those of
+    // the color map
+    fn lines(&self, colored: bool) -> Vec<String> {
+        (0..self.rows)
+            .map(|row| {
+                let cells = row * self.columns..(row + 1) * self.columns;
+                cells
+                    .map(|cell| match (self.text[cell], self.dots[cell], self.colors[cell], self.shades[cell]) {
+                        (None, 0, _, None) => String::from(" "),
+                        (None, 0, _, Some(fraction)) => shade(fraction, colored),
+                        (text, dots, color, fraction) => {
+                            let character = text.unwrap_or_else(|| char::from_u32(0x2800 + u32::from(dots)).unwrap_or(' ')).to_string();
+                            let character = match color.filter(|_| colored) {
+                                Some(color) => paint(&character, color),
+                                None => character,
+                            };
+                            match fraction.filter(|_| colored).map(colormap) {
+                                Some((r, g, b)) => format!("\x1b[48;2;{};{};{}m{}\x1b[0m", r, g, b, character),
+                                None => character,
+                            }
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn rgb_of(color: &str) -> Rgb {
+    let color = rgb(color);
+    (color.0, color.1, color.2)
+}
+
+// Text in a 24-bit ANSI color
+fn paint(text: &str, (r, g, b): Rgb) -> String {
+    format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text)
+}
+
+// A character of a color map at a fraction of its color bar: a space on its color, or a shade
+// as dark as the fraction is high
+fn shade(fraction: f64, colored: bool) -> String {
+    if colored {
+        let (r, g, b) = colormap(fraction);
+        return format!("\x1b[48;2;{};{};{}m \x1b[0m", r, g, b);
+    }
+    let index = (fraction * SHADES.len() as f64).floor() as usize;
+    SHADES[index.min(SHADES.len() - 1)].to_string()
+}
+
+fn centered(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(text.chars().count()) / 2;
+    format!("{:padding$}{}", "", text, padding = padding)
+}
+
+// A value with four significant digits, in scientific notation when very large or small
+pub(super) fn number(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude == 0.0 || !magnitude.is_finite() {
+        return format!("{}", value);
+    }
+    if !(1e-3..1e5).contains(&magnitude) {
+        return format!("{:.2e}", value);
+    }
+    let decimals = (3 - magnitude.log10().floor() as i32).clamp(0, 6) as usize;
+    let text = format!("{:.*}", decimals, value);
+    if text.contains('.') { text.trim_end_matches('0').trim_end_matches('.').to_string() } else { text }
+}
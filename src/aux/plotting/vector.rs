@@ -0,0 +1,348 @@
+// This module writes the charts drawn with plotters as PDF or EPS documents: a drawing backend
+// collecting the paths, circles and text of the page as operators shared by PDF and (through a
+// prolog defining them) PostScript, the text being set in the standard font closest to that of
+// the style (Helvetica, Times or Courier)
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use plotters_backend::text_anchor::{HPos, VPos};
+use plotters_backend::{BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingBackend, DrawingErrorKind, FontStyle, FontTransform};
+
+// Points (1/72 in) per pixel at 96 dpi
+const POINTS_PER_PIXEL: f64 = 0.75;
+
+// Ratio of the em of the fonts to the size plotters gives them, as in its SVG images
+const EM: f64 = 1.0 / 1.24;
+
+// Kind of vector document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Document {
+    Pdf,
+    Eps,
+}
+
+// Backend writing a single page of `size` pixels at 96 dpi into `path` when presented, on a
+// background of the color `background`
+pub struct VectorBackend {
+    path: PathBuf,
+    document: Document,
+    size: (u32, u32),
+    background: (u8, u8, u8),
+    page: String,
+    // Standard fonts (/F1, /F2, ...) and opacities (/GS1, ... of PDF pages) used on the page
+    fonts: Vec<&'static str>,
+    alphas: Vec<String>,
+    saved: bool,
+}
+
+impl VectorBackend {
+    pub fn new(path: &Path, document: Document, size: (u32, u32), background: (u8, u8, u8)) -> Self {
+        // Pixels (from the upper left corner) to points (from the lower left one)
+        let height = f64::from(size.1) * POINTS_PER_PIXEL;
+        let page = format!("{} 0 0 {} 0 {} cm 1 J 1 j\n", POINTS_PER_PIXEL, -POINTS_PER_PIXEL, number(height));
+        VectorBackend { path: path.to_path_buf(), document, size, background, page, fonts: Vec::new(), alphas: Vec::new(), saved: false }
+    }
+
+    // Set the color of the strokes or fills that follow. EPS documents have no transparency,
+    // their translucent colors are mixed with the background instead.
+    fn color(&mut self, color: BackendColor, stroke: bool) {
+        let (r, g, b) = color.rgb;
+        let operator = if stroke { "RG" } else { "rg" };
+        match self.document {
+            Document::Pdf => {
+                let [r, g, b] = [r, g, b].map(|c| f64::from(c) / 255.0);
+                let _ = writeln!(self.page, "{} {} {} {}", number(r), number(g), number(b), operator);
+                if color.alpha < 1.0 {
+                    let alpha = number(color.alpha);
+                    let index = self.alphas.iter().position(|a| *a == alpha).unwrap_or_else(|| {
+                        self.alphas.push(alpha);
+                        self.alphas.len() - 1
+                    });
+                    let _ = writeln!(self.page, "/GS{} gs", index + 1);
+                }
+            }
+            Document::Eps => {
+                let (r0, g0, b0) = self.background;
+                let mix = |c: u8, background: u8| (f64::from(c) * color.alpha + f64::from(background) * (1.0 - color.alpha)) / 255.0;
+                let [r, g, b] = [mix(r, r0), mix(g, g0), mix(b, b0)];
+                let _ = writeln!(self.page, "{} {} {} {}", number(r), number(g), number(b), operator);
+            }
+        }
+    }
+
+    // Append a stroked or filled path through `points`, closed when filled
+    fn path<S: BackendStyle>(&mut self, points: impl IntoIterator<Item = BackendCoord>, style: &S, fill: bool) {
+        if style.color().alpha == 0.0 {
+            return;
+        }
+        self.page.push_str("q\n");
+        self.color(style.color(), !fill);
+        if !fill {
+            let _ = writeln!(self.page, "{} w", style.stroke_width().max(1));
+        }
+        for (index, (x, y)) in points.into_iter().enumerate() {
+            let _ = writeln!(self.page, "{} {} {}", x, y, if index == 0 { "m" } else { "l" });
+        }
+        self.page.push_str(if fill { "h f\nQ\n" } else { "S\nQ\n" });
+    }
+
+    // Name (/F1, /F2, ...) of the standard font closest to a family and style
+    fn font(&mut self, family: &str, style: FontStyle) -> String {
+        let family = family.to_ascii_lowercase();
+        let bold = matches!(style, FontStyle::Bold);
+        let font = if family.contains("mono") || family.contains("courier") {
+            if bold { "Courier-Bold" } else { "Courier" }
+        } else if family.contains("serif") && !family.contains("sans") || family.contains("times") {
+            if bold { "Times-Bold" } else { "Times-Roman" }
+        } else if bold {
+            "Helvetica-Bold"
+        } else {
+            "Helvetica"
+        };
+        let index = self.fonts.iter().position(|f| *f == font).unwrap_or_else(|| {
+            self.fonts.push(font);
+            self.fonts.len() - 1
+        });
+        format!("/F{}", index + 1)
+    }
+
+    fn write(&self) -> io::Result<()> {
+        let (width, height) = (f64::from(self.size.0) * POINTS_PER_PIXEL, f64::from(self.size.1) * POINTS_PER_PIXEL);
+        let bytes = match self.document {
+            Document::Pdf => self.pdf(width, height),
+            Document::Eps => self.eps(width, height).into_bytes(),
+        };
+        fs::write(&self.path, bytes)
+    }
+
+    // A PDF document of the page, its fonts in the Windows encoding and its opacities
+    fn pdf(&self, width: f64, height: f64) -> Vec<u8> {
+        let first_font = 5;
+        let first_alpha = first_font + self.fonts.len();
+        let fonts: String = (0..self.fonts.len()).map(|i| format!("/F{} {} 0 R ", i + 1, first_font + i)).collect();
+        let alphas: String = (0..self.alphas.len()).map(|i| format!("/GS{} {} 0 R ", i + 1, first_alpha + i)).collect();
+        let mut objects = vec![
+            String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+            String::from("<< /Type /Pages /Kids [3 0 R] /Count 1 >>"),
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {}>> /ExtGState << {}>> >> /Contents 4 0 R >>",
+                number(width),
+                number(height),
+                fonts,
+                alphas
+            ),
+            format!("<< /Length {} >>\nstream\n{}endstream", self.page.len(), self.page),
+        ];
+        for font in &self.fonts {
+            objects.push(format!("<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>", font));
+        }
+        for alpha in &self.alphas {
+            objects.push(format!("<< /Type /ExtGState /ca {} /CA {} >>", alpha, alpha));
+        }
+
+        let mut pdf = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", index + 1, object);
+        }
+        let xref = pdf.len();
+        let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(pdf, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref);
+        pdf.into_bytes()
+    }
+
+    // An encapsulated PostScript document of the page, defining the PDF operators it uses and
+    // its fonts in the Latin-1 encoding
+    fn eps(&self, width: f64, height: f64) -> String {
+        let mut eps = String::from("%!PS-Adobe-3.0 EPSF-3.0\n");
+        let _ = writeln!(eps, "%%BoundingBox: 0 0 {} {}", width.ceil(), height.ceil());
+        let _ = writeln!(eps, "%%HiResBoundingBox: 0 0 {} {}", number(width), number(height));
+        eps.push_str("%%LanguageLevel: 2\n%%Pages: 1\n%%EndComments\n%%BeginProlog\n");
+        eps.push_str(
+            "/q {gsave} bind def /Q {grestore} bind def /cm {6 array astore concat} bind def\n\
+             /J {setlinecap} bind def /j {setlinejoin} bind def /w {setlinewidth} bind def\n\
+             /RG {setrgbcolor} bind def /rg {setrgbcolor} bind def\n\
+             /m {moveto} bind def /l {lineto} bind def /c {curveto} bind def /h {closepath} bind def\n\
+             /S {stroke} bind def /f {fill} bind def\n\
+             /BT {gsave} bind def /ET {grestore} bind def /Tf {selectfont} bind def\n\
+             /Tm {6 array astore concat 0 0 moveto} bind def /Tj {show} bind def\n",
+        );
+        for (index, font) in self.fonts.iter().enumerate() {
+            let _ = writeln!(
+                eps,
+                "/F{} /{} findfont dup length dict begin {{1 index /FID ne {{def}} {{pop pop}} ifelse}} forall \
+                 /Encoding ISOLatin1Encoding def currentdict end definefont pop",
+                index + 1,
+                font
+            );
+        }
+        eps.push_str("%%EndProlog\n%%Page: 1 1\ngsave\n");
+        eps.push_str(&self.page);
+        eps.push_str("grestore\nshowpage\n%%EOF\n");
+        eps
+    }
+}
+
+impl DrawingBackend for VectorBackend {
+    type ErrorType = io::Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<io::Error>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<io::Error>> {
+        if !self.saved {
+            self.write().map_err(DrawingErrorKind::DrawingError)?;
+            self.saved = true;
+        }
+        Ok(())
+    }
+
+    fn draw_pixel(&mut self, (x, y): BackendCoord, color: BackendColor) -> Result<(), DrawingErrorKind<io::Error>> {
+        self.path([(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)], &color, true);
+        Ok(())
+    }
+
+    fn draw_line<S: BackendStyle>(&mut self, from: BackendCoord, to: BackendCoord, style: &S) -> Result<(), DrawingErrorKind<io::Error>> {
+        self.path([from, to], style, false);
+        Ok(())
+    }
+
+    fn draw_rect<S: BackendStyle>(&mut self, upper_left: BackendCoord, bottom_right: BackendCoord, style: &S, fill: bool) -> Result<(), DrawingErrorKind<io::Error>> {
+        let ((left, top), (right, bottom)) = (upper_left, bottom_right);
+        let corners = [(left, top), (right, top), (right, bottom), (left, bottom), (left, top)];
+        self.path(corners, style, fill);
+        Ok(())
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(&mut self, path: I, style: &S) -> Result<(), DrawingErrorKind<io::Error>> {
+        self.path(path, style, false);
+        Ok(())
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(&mut self, vertices: I, style: &S) -> Result<(), DrawingErrorKind<io::Error>> {
+        self.path(vertices, style, true);
+        Ok(())
+    }
+
+    fn draw_circle<S: BackendStyle>(&mut self, (x, y): BackendCoord, radius: u32, style: &S, fill: bool) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 {
+            return Ok(());
+        }
+        self.page.push_str("q\n");
+        self.color(style.color(), !fill);
+        if !fill {
+            let _ = writeln!(self.page, "{} w", style.stroke_width().max(1));
+        }
+        // Four Bézier quarters
+        let (x, y, r) = (f64::from(x), f64::from(y), f64::from(radius));
+        let k = 0.5523 * r;
+        let _ = writeln!(self.page, "{} {} m", number(x + r), number(y));
+        let quarters = [
+            [x + r, y + k, x + k, y + r, x, y + r],
+            [x - k, y + r, x - r, y + k, x - r, y],
+            [x - r, y - k, x - k, y - r, x, y - r],
+            [x + k, y - r, x + r, y - k, x + r, y],
+        ];
+        for quarter in quarters {
+            let _ = writeln!(self.page, "{} c", quarter.map(number).join(" "));
+        }
+        self.page.push_str(if fill { "h f\nQ\n" } else { "h S\nQ\n" });
+        Ok(())
+    }
+
+    fn draw_text<S: BackendTextStyle>(&mut self, text: &str, style: &S, (x, y): BackendCoord) -> Result<(), DrawingErrorKind<io::Error>> {
+        if style.color().alpha == 0.0 || text.is_empty() {
+            return Ok(());
+        }
+        let width = match style.layout_box(text) {
+            Ok(((left, _), (right, _))) => f64::from(right - left),
+            Err(error) => return Err(DrawingErrorKind::FontError(Box::new(error))),
+        };
+        let size = style.size() * EM;
+
+        // Directions (pixels) of the writing and of the descent of the text, and the shift of
+        // its start from the anchor along them
+        let ((ux, uy), (wx, wy)) = match style.transform() {
+            FontTransform::Rotate90 => ((0.0, 1.0), (-1.0, 0.0)),
+            FontTransform::Rotate180 => ((-1.0, 0.0), (0.0, -1.0)),
+            FontTransform::Rotate270 => ((0.0, -1.0), (1.0, 0.0)),
+            _ => ((1.0, 0.0), (0.0, 1.0)),
+        };
+        let along = match style.anchor().h_pos {
+            HPos::Left => 0.0,
+            HPos::Center => -width / 2.0,
+            HPos::Right => -width,
+        };
+        let down = match style.anchor().v_pos {
+            VPos::Top => 0.76 * size,
+            VPos::Center => 0.25 * size,
+            VPos::Bottom => -0.25 * size,
+        };
+        let (x, y) = (f64::from(x) + along * ux + down * wx, f64::from(y) + along * uy + down * wy);
+
+        let font = self.font(style.family().as_str(), style.style());
+        self.page.push_str("q\n");
+        self.color(style.color(), false);
+        let _ = writeln!(
+            self.page,
+            "BT {} {} Tf {} {} {} {} {} {} Tm ({}) Tj ET\nQ",
+            font,
+            number(size),
+            number(ux),
+            number(uy),
+            number(-wx),
+            number(-wy),
+            number(x),
+            number(y),
+            latin1(text)
+        );
+        Ok(())
+    }
+}
+
+// The document is written when the backend is dropped without having been presented
+impl Drop for VectorBackend {
+    fn drop(&mut self) {
+        if !self.saved {
+            let _ = self.present();
+        }
+    }
+}
+
+// A number with at most three decimals, without trailing zeros
+fn number(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { String::from("0") } else { text.to_string() }
+}
+
+// The text of a string literal in Latin-1 (escaped in octal beyond ASCII), characters outside
+// it becoming question marks
+fn latin1(text: &str) -> String {
+    let mut literal = String::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                literal.push('\\');
+                literal.push(c);
+            }
+            ' '..='~' => literal.push(c),
+            c if (c as u32) >= 0xA0 && (c as u32) <= 0xFF => {
+                let _ = write!(literal, "\\{:03o}", c as u32);
+            }
+            _ => literal.push('?'),
+        }
+    }
+    literal
+}
@@ -0,0 +1,224 @@
+// This module shows charts in windows, where they are zoomed by scrolling (or dragging a box
+// with the right button) and panned by dragging, double clicks going back to the whole curves;
+// the values of the curve point nearest the cursor, or those of the cursor itself, are shown
+// beside it, with the value of the cell of a color map under the cursor. egui plots have linear
+// axes only, so logarithmic ones hold the logarithms of the values, their ticks and the readout
+// being labelled with the values.
+use std::path::Path;
+
+use eframe::egui::{self, Color32};
+use egui_plot::{Bar, BarChart, CoordinatesFormatter, Corner, FilledArea, HLine, HoverPosition, Line, LineStyle, MarkerShape, Plot, PlotPoint, PlotPoints, PlotUi, Points, Polygon, Text, VLine};
+
+use super::backend::PlotBackend;
+use super::chart::{Chart, Direction, Heatmap, Panel, Series, Style};
+use super::plotters::rgb;
+use super::style::{colormap, Legend, PlotStyle, Theme};
+use super::terminal::{number, scaled, unscaled};
+use crate::error::{Error, Result};
+
+// Backend showing each chart in a window of its own, the run going on once it is closed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Viewer;
+
+impl PlotBackend for Viewer {
+    // Nothing is written to `path`, the file the chart would be drawn into by the others
+    fn draw(&self, chart: &Chart, style: &PlotStyle, _path: &Path) -> Result<()> {
+        let viewport = egui::ViewportBuilder::default()
+            .with_title(&chart.title)
+            .with_inner_size([style.width as f32, style.height as f32]);
+        let options = eframe::NativeOptions { viewport, ..Default::default() };
+        let window = Window { chart: chart.clone(), style: style.clone(), panel: 0 };
+        let theme = match style.theme {
+            Theme::Light => egui::Theme::Light,
+            Theme::Dark => egui::Theme::Dark,
+        };
+        eframe::run_native(
+            "system_rs",
+            options,
+            Box::new(move |context| {
+                // Curves are colored for the background of the theme, as in the images
+                context.egui_ctx.set_theme(theme);
+                Ok(Box::new(window))
+            }),
+        )
+        .map_err(|e| Error::View { title: chart.title.clone(), message: e.to_string() })
+    }
+}
+
+// A chart shown one panel at a time, chosen above it
+struct Window {
+    chart: Chart,
+    style: PlotStyle,
+    panel: usize,
+}
+
+impl eframe::App for Window {
+    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ui, |ui| {
+            ui.vertical_centered(|ui| ui.heading(&self.chart.title));
+            if self.chart.panels.len() > 1 {
+                ui.horizontal(|ui| {
+                    for (index, panel) in self.chart.panels.iter().enumerate() {
+                        ui.selectable_value(&mut self.panel, index, &panel.title);
+                    }
+                });
+            }
+            if let Some(panel) = self.chart.panels.get(self.panel) {
+                show_panel(ui, panel, &self.style, self.panel);
+            }
+        });
+    }
+}
+
+fn show_panel(ui: &mut egui::Ui, panel: &Panel, style: &PlotStyle, index: usize) {
+    let (x_log, y_log) = (panel.x_axis.log_base, panel.y_axis.log_base);
+    let ((x_min, x_max), (y_min, y_max)) = (panel.x_range(), panel.y_range());
+    let (x_label, y_label) = (label(&panel.x_label, "x"), label(&panel.y_label, "y"));
+    let heatmap = panel.heatmap.clone();
+    let readout = move |x: f64, y: f64| {
+        let (x, y) = (unscaled(x, x_log), unscaled(y, y_log));
+        let mut text = format!("{} = {}\n{} = {}", x_label, number(x), y_label, number(y));
+        if let Some(heatmap) = &heatmap {
+            if let Some(value) = heatmap.cell(x, y, x_log.is_some(), y_log.is_some()) {
+                text.push_str(&format!("\n{} = {}", heatmap.label, number(value)));
+            }
+        }
+        text
+    };
+    let cursor = readout.clone();
+
+    let mut plot = Plot::new(("panel", index))
+        .x_axis_label(panel.x_label.as_str())
+        .y_axis_label(panel.y_label.as_str())
+        .default_x_bounds(scaled(x_min, x_log), scaled(x_max, x_log))
+        .default_y_bounds(scaled(y_min, y_log), scaled(y_max, y_log))
+        .show_grid(style.grid)
+        .x_axis_formatter(move |mark, _| number(unscaled(mark.value, x_log)))
+        .y_axis_formatter(move |mark, _| number(unscaled(mark.value, y_log)))
+        .label_formatter(move |position| match position {
+            HoverPosition::NearDataPoint { plot_name, position, .. } if !plot_name.is_empty() => {
+                Some(format!("{}\n{}", plot_name, readout(position.x, position.y)))
+            }
+            HoverPosition::NearDataPoint { position, .. } | HoverPosition::Elsewhere { position } => Some(readout(position.x, position.y)),
+        })
+        .coordinates_formatter(Corner::LeftBottom, CoordinatesFormatter::new(move |point, _| cursor(point.x, point.y)));
+    let corner = match style.legend {
+        Legend::TopRight => Some(Corner::RightTop),
+        Legend::TopLeft => Some(Corner::LeftTop),
+        Legend::BottomRight => Some(Corner::RightBottom),
+        Legend::BottomLeft => Some(Corner::LeftBottom),
+        Legend::None => None,
+    };
+    if let Some(corner) = corner {
+        plot = plot.legend(egui_plot::Legend::default().position(corner));
+    }
+    plot.show(ui, |plot| {
+        if let Some(heatmap) = &panel.heatmap {
+            show_heatmap(plot, heatmap, panel);
+        }
+        for series in &panel.series {
+            show_series(plot, series, panel, style);
+        }
+        show_marks(plot, panel, style);
+    });
+}
+
+// Name of an axis in the readout: its label, or `axis` when it has none
+fn label(label: &str, axis: &str) -> String {
+    if label.is_empty() { axis.to_string() } else { label.to_string() }
+}
+
+// Points of the series on the scales of the axes, without those outside logarithmic ones
+fn show_series(plot: &mut PlotUi, series: &Series, panel: &Panel, style: &PlotStyle) {
+    let (x_log, y_log) = (panel.x_axis.log_base, panel.y_axis.log_base);
+    let drawable = |(x, y): &(f64, f64)| (x_log.is_none() || *x > 0.0) && (y_log.is_none() || *y > 0.0);
+    let scale = |(x, y): (f64, f64)| [scaled(x, x_log), scaled(y, y_log)];
+    let points = |ys: &[f64]| -> Vec<[f64; 2]> { series.xs.iter().copied().zip(ys.iter().copied()).filter(drawable).map(scale).collect() };
+
+    let (r, g, b) = channels(&series.color);
+    let color = Color32::from_rgb(r, g, b);
+    let fill = |alpha: f64| Color32::from_rgba_unmultiplied(r, g, b, (alpha.clamp(0.0, 1.0) * 255.0).round() as u8);
+    // Series without a caption are left out of the legend
+    let name = series.caption.clone().unwrap_or_default();
+    let width = (2.0 * style.line_width) as f32;
+
+    match &series.style {
+        Style::Lines => plot.line(Line::new(name, PlotPoints::from(points(&series.ys))).color(color).width(width)),
+        Style::Points => plot.points(Points::new(name, PlotPoints::from(points(&series.ys))).color(color).shape(MarkerShape::Circle).radius(4.0)),
+        Style::LinesPoints => {
+            plot.line(Line::new(name.clone(), PlotPoints::from(points(&series.ys))).color(color).width(width));
+            plot.points(Points::new(name, PlotPoints::from(points(&series.ys))).color(color).shape(MarkerShape::Circle).radius(4.0));
+        }
+        Style::Boxes { alpha } => {
+            let bars = points(&series.ys).into_iter().map(|[x, y]| Bar::new(x, y).width(0.8)).collect();
+            plot.bar_chart(BarChart::new(name, bars).color(fill(*alpha)));
+        }
+        Style::Band { upper, alpha } => {
+            let bounds: Vec<(f64, f64, f64)> = series.xs.iter().zip(&series.ys).zip(upper)
+                .filter(|((x, lower), upper)| drawable(&(**x, **lower)) && drawable(&(**x, **upper)))
+                .map(|((x, lower), upper)| (scaled(*x, x_log), scaled(*lower, y_log), scaled(*upper, y_log)))
+                .collect();
+            let xs: Vec<f64> = bounds.iter().map(|bound| bound.0).collect();
+            let lower: Vec<f64> = bounds.iter().map(|bound| bound.1).collect();
+            let upper: Vec<f64> = bounds.iter().map(|bound| bound.2).collect();
+            plot.add(FilledArea::new(name, &xs, &lower, &upper).fill_color(fill(*alpha)));
+        }
+    }
+}
+
+// Cells of a color map as rectangles on the scales of the axes, without those outside
+// logarithmic ones
+fn show_heatmap(plot: &mut PlotUi, heatmap: &Heatmap, panel: &Panel) {
+    let (x_log, y_log) = (panel.x_axis.log_base, panel.y_axis.log_base);
+    let x_edges: Vec<f64> = heatmap.x_edges(x_log.is_some()).into_iter().map(|x| scaled(x, x_log)).collect();
+    let y_edges: Vec<f64> = heatmap.y_edges(y_log.is_some()).into_iter().map(|y| scaled(y, y_log)).collect();
+    let limits = heatmap.limits();
+    for (row, values) in heatmap.values.iter().enumerate().take(y_edges.len().saturating_sub(1)) {
+        let (bottom, top) = (y_edges[row], y_edges[row + 1]);
+        for (column, value) in values.iter().enumerate().take(x_edges.len().saturating_sub(1)) {
+            let (left, right) = (x_edges[column], x_edges[column + 1]);
+            let fraction = heatmap.fraction(*value, limits).filter(|_| [left, right, bottom, top].iter().all(|edge| edge.is_finite()));
+            if let Some(fraction) = fraction {
+                let (r, g, b) = colormap(fraction);
+                let corners = vec![[left, bottom], [right, bottom], [right, top], [left, top]];
+                plot.polygon(Polygon::new("", corners).fill_color(Color32::from_rgb(r, g, b)).stroke(egui::Stroke::NONE).allow_hover(false));
+            }
+        }
+    }
+}
+
+// Guides across the axes, labelled at the top or right of the view, and the marked points
+fn show_marks(plot: &mut PlotUi, panel: &Panel, style: &PlotStyle) {
+    let (x_log, y_log) = (panel.x_axis.log_base, panel.y_axis.log_base);
+    let bounds = plot.plot_bounds();
+    let width = style.line_width as f32;
+    for guide in &panel.guides {
+        let (r, g, b) = channels(&guide.color);
+        let color = Color32::from_rgb(r, g, b);
+        match guide.direction {
+            Direction::Vertical if x_log.is_none() || guide.value > 0.0 => {
+                let x = scaled(guide.value, x_log);
+                plot.vline(VLine::new("", x).color(color).width(width).style(LineStyle::dashed_loose()));
+                plot.text(Text::new("", PlotPoint::new(x, bounds.max()[1]), format!(" {}", guide.label)).color(color).anchor(egui::Align2::LEFT_TOP));
+            }
+            Direction::Horizontal if y_log.is_none() || guide.value > 0.0 => {
+                let y = scaled(guide.value, y_log);
+                plot.hline(HLine::new("", y).color(color).width(width).style(LineStyle::dashed_loose()));
+                plot.text(Text::new("", PlotPoint::new(bounds.max()[0], y), format!("{} ", guide.label)).color(color).anchor(egui::Align2::RIGHT_BOTTOM));
+            }
+            _ => {}
+        }
+    }
+    for annotation in panel.annotations.iter().filter(|annotation| (x_log.is_none() || annotation.x > 0.0) && (y_log.is_none() || annotation.y > 0.0)) {
+        let (r, g, b) = channels(&annotation.color);
+        let color = Color32::from_rgb(r, g, b);
+        let point = [scaled(annotation.x, x_log), scaled(annotation.y, y_log)];
+        plot.points(Points::new("", vec![point]).color(color).shape(MarkerShape::Circle).filled(true).radius(4.0));
+        plot.text(Text::new("", PlotPoint::new(point[0], point[1]), format!("  {}", annotation.label)).color(color).anchor(egui::Align2::LEFT_BOTTOM));
+    }
+}
+
+fn channels(color: &str) -> (u8, u8, u8) {
+    let color = rgb(color);
+    (color.0, color.1, color.2)
+}
@@ -0,0 +1,88 @@
+// This module appends runs to an SQLite database, so that the tables of earlier runs can be
+// queried and compared, e.g. the stopping power of every run in water:
+//   SELECT t.run, p.value FROM tables t JOIN points p ON p.table_id = t.id
+//   WHERE json_extract(t.metadata, '$.material') = 'water' AND p.quantity LIKE 'dE/dx%'
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::output::create_dir;
+
+// Runs (the command line and its Unix time), the tables each of them wrote (the name of the
+// data file and the metadata as a JSON object) and every value of their columns, the quantity
+// being the column header and the point the row number
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    started INTEGER NOT NULL,
+    command TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS tables (
+    id INTEGER PRIMARY KEY,
+    run INTEGER NOT NULL REFERENCES runs (id),
+    name TEXT NOT NULL,
+    metadata TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS points (
+    table_id INTEGER NOT NULL REFERENCES tables (id),
+    point INTEGER NOT NULL,
+    quantity TEXT NOT NULL,
+    value REAL
+);
+CREATE INDEX IF NOT EXISTS points_by_table ON points (table_id);";
+
+// Database file and id of the run the tables are added to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Store {
+    pub path: PathBuf,
+    pub run: i64,
+}
+
+impl Store {
+    // Open the database, creating the file and its tables when missing, and add a run of
+    // `command`
+    pub fn open<P: AsRef<Path>>(path: P, command: &str) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let started = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs() as i64);
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            create_dir(dir)?;
+        }
+        let run = Connection::open(&path)
+            .and_then(|connection| {
+                connection.execute_batch(SCHEMA)?;
+                connection.execute("INSERT INTO runs (started, command) VALUES (?1, ?2)", params![started, command])?;
+                Ok(connection.last_insert_rowid())
+            })
+            .map_err(|source| Error::Store { path: path.clone(), source })?;
+        Ok(Store { path, run })
+    }
+
+    // Add the table `name` of the run with its metadata and the values of the columns named in
+    // `header`, one row per record
+    pub fn add_table(&self, name: &str, metadata: &[(&str, Value)], header: &[String], rows: &[Vec<f64>]) -> Result<()> {
+        let metadata: Map<String, Value> = metadata.iter().map(|(key, value)| (key.to_string(), value.clone())).collect();
+        let insert = || -> rusqlite::Result<()> {
+            let mut connection = Connection::open(&self.path)?;
+            let transaction = connection.transaction()?;
+            transaction.execute(
+                "INSERT INTO tables (run, name, metadata) VALUES (?1, ?2, ?3)",
+                params![self.run, name, Value::Object(metadata).to_string()],
+            )?;
+            let table = transaction.last_insert_rowid();
+            {
+                let mut point = transaction.prepare("INSERT INTO points (table_id, point, quantity, value) VALUES (?1, ?2, ?3, ?4)")?;
+                for (index, row) in rows.iter().enumerate() {
+                    for (quantity, value) in header.iter().zip(row) {
+                        point.execute(params![table, index as i64, quantity, value])?;
+                    }
+                }
+            }
+            transaction.commit()
+        };
+        insert().map_err(|source| Error::Store { path: self.path.clone(), source })
+    }
+}
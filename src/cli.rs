@@ -0,0 +1,1160 @@
+// This module declares the command-line interface: the target and output options shared by
+// every command, the tables, plots and Bragg curve computed when no subcommand is given, and
+// one argument struct per subcommand
+use std::path::PathBuf;
+
+use std::ffi::OsStr;
+
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use system_rs::export::{Markup, DEFAULT_DIGITS};
+use system_rs::i18n::Lang;
+use system_rs::output::{Format, Notation};
+use system_rs::plotting::backend::{Backend, PlotFormat};
+use system_rs::plotting::chart::{Annotation, Guide};
+use system_rs::plotting::style::{Legend, PlotStyle, Theme};
+use system_rs::config::GridConfig;
+use system_rs::RunConfig;
+use system_rs::bethe_bloch::run::{self, TableSettings};
+use system_rs::bethe_bloch::{materials, projectile, range, Axis, Corrections, EnergyGrid, Material, Projectile, RbeModel, Scan, Spacing, Unit};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Stopping power of charged particles in matter with the Bethe-Bloch formula")]
+pub struct Cli {
+    #[command(flatten)]
+    pub target: TargetArgs,
+
+    /// Directory receiving the data files and plots, created when missing
+    #[arg(long, global = true, value_name = "DIR", default_value = "output")]
+    pub output_dir: PathBuf,
+
+    /// Never prompt on standard input, taking the defaults instead (implied when it is not a terminal)
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Skip the plots and write only the data files
+    #[arg(long, global = true)]
+    pub no_plot: bool,
+
+    /// Program drawing the plots: plotters, terminal (text on the standard output, e.g. over SSH), gnuplot in builds with the gnuplot feature (the default), or viewer (windows with zoom, pan and the values under the cursor) in builds with the viewer feature
+    #[arg(long, global = true, visible_alias = "plot", value_name = "BACKEND", default_value = "plotters")]
+    pub plot_backend: Backend,
+
+    /// Format of the plots: png, or svg, pdf and eps vector graphics
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "png")]
+    pub plot_format: PlotFormat,
+
+    /// Fixed range of the abscissa of every plot, e.g. 10,1000; that of the data by default
+    #[arg(long, global = true, value_name = "MIN,MAX", value_parser = parse_range, allow_hyphen_values = true)]
+    pub x_range: Option<(f64, f64)>,
+
+    /// Fixed range of the ordinate of every plot; that of the data by default
+    #[arg(long, global = true, value_name = "MIN,MAX", value_parser = parse_range, allow_hyphen_values = true)]
+    pub y_range: Option<(f64, f64)>,
+
+    /// Point marked and labelled on every plot, e.g. 150,5.4,"My point"; repeatable
+    #[arg(long = "annotate", global = true, value_name = "X,Y,LABEL", value_parser = parse_annotation, allow_hyphen_values = true)]
+    pub annotations: Vec<Annotation>,
+
+    /// Dashed vertical line across every plot at an abscissa, optionally labelled, e.g. 480,Cherenkov; repeatable
+    #[arg(long = "vline", global = true, value_name = "X[,LABEL]", value_parser = parse_vline, allow_hyphen_values = true)]
+    pub vlines: Vec<Guide>,
+
+    /// Dashed horizontal line across every plot at an ordinate, optionally labelled; repeatable
+    #[arg(long = "hline", global = true, value_name = "Y[,LABEL]", value_parser = parse_hline, allow_hyphen_values = true)]
+    pub hlines: Vec<Guide>,
+
+    #[command(flatten)]
+    pub style: StyleArgs,
+
+    /// Language of the labels of the plots and of the prompts: es (Spanish) or en (English)
+    #[arg(long, global = true, value_name = "LANG", default_value = "es")]
+    pub lang: Lang,
+
+    /// Decimal places of every number of the text tables, instead of those of each column
+    #[arg(long, global = true)]
+    pub precision: Option<usize>,
+
+    /// Notation of every number of the text tables, fixed or scientific, instead of that of each column
+    #[arg(long, global = true)]
+    pub notation: Option<Notation>,
+
+    /// Column delimiter of the text tables: tab, space, comma or any other string
+    #[arg(long, global = true, default_value = "tab", value_parser = parse_delimiter)]
+    pub delimiter: String,
+
+    /// Also write each figure as a gnuplot script (.plt) reading its data (.dat), next to the image; the
+    /// scripts are written with --no-plot too
+    #[arg(long, global = true)]
+    pub scripts: bool,
+
+    /// Name the plot files after a template such as {particle}_{material}_{corrections}_{date}, followed by
+    /// the {title} of the plot unless it is placed; plots are named after their title by default
+    #[arg(long, global = true, value_name = "TEMPLATE")]
+    pub plot_name: Option<String>,
+
+    /// Replace the plot files of earlier runs instead of stopping
+    #[arg(long, global = true)]
+    pub force: bool,
+
+    /// Format of the stopping-power, range and Bragg-curve tables: text, csv (with a header row and the run as
+    /// comments), json or, in builds with the features of the same names, hdf5 (every table in output/tables.h5),
+    /// parquet or root
+    #[arg(long, global = true, default_value = "text")]
+    pub format: Format,
+
+    /// Append the run, the parameters and the stopping-power, range and Bragg-curve tables to an SQLite
+    /// database, created when missing
+    #[arg(long, global = true, value_name = "DB")]
+    pub store: Option<PathBuf>,
+
+    /// Also write the stopping-power, range and Bragg-curve tables as latex (a tabular) or markdown tables
+    #[arg(long, global = true, value_name = "MARKUP")]
+    pub export: Option<Markup>,
+
+    /// Comma separated energies (MeV, or depths in cm of the Bragg-curve tables) whose nearest rows are
+    /// exported; every row by default
+    #[arg(long, global = true, value_name = "VALUES", value_delimiter = ',', requires = "export")]
+    pub export_at: Vec<f64>,
+
+    /// Significant figures of the exported values
+    #[arg(long, global = true, default_value_t = DEFAULT_DIGITS as u8, value_parser = clap::value_parser!(u8).range(1..=17))]
+    pub digits: u8,
+
+    /// Print every point of the stopping-power tables on standard output
+    #[arg(long, global = true)]
+    pub dump_stdout: bool,
+
+    /// Only report errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Report more detail, e.g. -v for every point of the stopping-power tables
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Threads computing the tables, scans and Monte Carlo histories; one per core by default
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    pub threads: Option<u32>,
+
+    /// TOML or YAML file with the options of the run; options on the command line take precedence
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Run again whenever the --config file changes, until interrupted
+    #[arg(long, global = true, requires = "config")]
+    pub watch: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub tables: TableArgs,
+}
+
+impl Cli {
+    // Options of the stopping-power tables of the command, if it computes them
+    pub fn stopping_mut(&mut self) -> Option<&mut StoppingArgs> {
+        match &mut self.command {
+            None => Some(&mut self.tables.stopping),
+            Some(Command::Stopping(args) | Command::Plot(PlotArgs { stopping: args, .. })) => Some(args),
+            _ => None,
+        }
+    }
+
+    // Corrections chosen with --corrections for the command, if it takes them
+    pub fn corrections(&self) -> Option<Corrections> {
+        match &self.command {
+            None => self.tables.stopping.corrections,
+            Some(Command::Stopping(args) | Command::Plot(PlotArgs { stopping: args, .. })) => args.corrections,
+            Some(Command::Bragg(args)) => args.corrections,
+            Some(Command::Pipe(args)) => args.corrections,
+            _ => None,
+        }
+    }
+
+    // Options of the tables given before a subcommand would be left unused, as the subcommand
+    // takes its own after its name, so they are refused as clap refuses conflicting options
+    pub fn check_placement(matches: &ArgMatches) -> Result<(), clap::Error> {
+        let Some((subcommand, _)) = matches.subcommand() else { return Ok(()) };
+        let misplaced: Vec<String> = TableArgs::augment_args(clap::Command::new("tables"))
+            .get_arguments()
+            .filter(|arg| given(matches, arg.get_id().as_str()))
+            .map(|arg| arg.get_long().map_or_else(|| arg.get_id().to_string(), |long| format!("--{}", long)))
+            .collect();
+        if misplaced.is_empty() {
+            return Ok(());
+        }
+        let message = format!("the subcommand '{}' cannot be used with {} before it; give the options of a subcommand after its name", subcommand, misplaced.join(", "));
+        Err(Cli::command().error(ErrorKind::ArgumentConflict, message))
+    }
+
+    // Take the options of `config` that were not given on the command line (`matches` is what
+    // the command line was parsed from). Optional arguments only need filling in when unset,
+    // those with a default value are checked against `matches`.
+    pub fn apply_config(&mut self, config: &RunConfig, matches: &ArgMatches) -> Result<(), String> {
+        let subcommand = matches.subcommand().map(|(_, matches)| matches);
+        // Global options may be given before or after the subcommand
+        let global = |id: &str| given(matches, id) || subcommand.is_some_and(|matches| given(matches, id));
+
+        let target = &mut self.target;
+        if let Some(name) = config.material.as_deref().filter(|_| !global("material")) {
+            target.material = parse_material(name).map_err(|e| format!("material: {}", e))?;
+        }
+        if let Some(name) = config.particle.as_deref().filter(|_| !global("particle")) {
+            target.particle = parse_particle(name).map_err(|e| format!("particle: {}", e))?;
+        }
+        target.temperature = target.temperature.or(config.temperature);
+        target.pressure = target.pressure.or(config.pressure);
+        target.shell_table = target.shell_table.take().or_else(|| config.shell_table.clone());
+        if let Some(dir) = config.output.dir.clone().filter(|_| !global("output_dir")) {
+            self.output_dir = dir;
+        }
+        if let Some(format) = config.output.format.as_deref().filter(|_| !global("format")) {
+            self.format = format.parse().map_err(|e| format!("output.format: {}", e))?;
+        }
+        if let Some(backend) = config.output.backend.as_deref().filter(|_| !global("plot_backend")) {
+            self.plot_backend = backend.parse().map_err(|e| format!("output.backend: {}", e))?;
+        }
+        if let Some(format) = config.output.plot_format.as_deref().filter(|_| !global("plot_format")) {
+            self.plot_format = format.parse().map_err(|e| format!("output.plot_format: {}", e))?;
+        }
+        if let Some(range) = config.output.x_range.filter(|_| self.x_range.is_none()) {
+            self.x_range = Some(checked_range(range).map_err(|e| format!("output.x_range: {}", e))?);
+        }
+        if let Some(range) = config.output.y_range.filter(|_| self.y_range.is_none()) {
+            self.y_range = Some(checked_range(range).map_err(|e| format!("output.y_range: {}", e))?);
+        }
+        if self.annotations.is_empty() {
+            for annotation in config.output.annotations.iter().flatten() {
+                self.annotations.push(parse_annotation(annotation).map_err(|e| format!("output.annotations: {}", e))?);
+            }
+        }
+        if self.vlines.is_empty() {
+            for guide in config.output.vlines.iter().flatten() {
+                self.vlines.push(parse_vline(guide).map_err(|e| format!("output.vlines: {}", e))?);
+            }
+        }
+        if self.hlines.is_empty() {
+            for guide in config.output.hlines.iter().flatten() {
+                self.hlines.push(parse_hline(guide).map_err(|e| format!("output.hlines: {}", e))?);
+            }
+        }
+        self.style.apply_config(config)?;
+        if let Some(lang) = config.output.lang.as_deref().filter(|_| !global("lang")) {
+            self.lang = lang.parse().map_err(|e| format!("output.lang: {}", e))?;
+        }
+        self.precision = self.precision.or(config.output.precision);
+        if let Some(notation) = config.output.notation.as_deref().filter(|_| self.notation.is_none()) {
+            self.notation = Some(notation.parse().map_err(|e| format!("output.notation: {}", e))?);
+        }
+        if let Some(delimiter) = config.output.delimiter.as_deref().filter(|_| !global("delimiter")) {
+            self.delimiter = parse_delimiter(delimiter).map_err(|e| format!("output.delimiter: {}", e))?;
+        }
+        self.store = self.store.take().or_else(|| config.output.store.clone());
+        if let Some(markup) = config.output.export.as_deref().filter(|_| self.export.is_none()) {
+            self.export = Some(markup.parse().map_err(|e| format!("output.export: {}", e))?);
+        }
+        if let Some(values) = config.output.export_at.clone().filter(|_| self.export_at.is_empty()) {
+            self.export_at = values;
+        }
+        if let Some(digits) = config.output.digits.filter(|_| !global("digits")) {
+            self.digits = digits;
+        }
+        if let Some(threads) = config.threads.filter(|_| self.threads.is_none()) {
+            self.threads = Some(if threads > 0 { threads } else { return Err(String::from("threads: must be positive")) });
+        }
+        self.non_interactive |= config.interactive == Some(false);
+        self.no_plot |= config.output.plots == Some(false);
+        self.scripts |= config.output.scripts == Some(true);
+        self.plot_name = self.plot_name.take().or_else(|| config.output.plot_name.clone());
+        self.force |= config.output.force == Some(true);
+
+        match (&mut self.command, subcommand) {
+            (None, _) => {
+                self.tables.stopping.apply_config(config, matches)?;
+                self.tables.bragg = self.tables.bragg.or(config.bragg);
+            }
+            (Some(Command::Stopping(args) | Command::Plot(PlotArgs { stopping: args, .. })), Some(matches)) => args.apply_config(config, matches)?,
+            (Some(Command::Bragg(args)), _) => {
+                args.corrections = args.corrections.or(config_corrections(config)?);
+                args.radiative |= config.radiative.unwrap_or_default();
+                args.density.apply_config(config);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// Projectile and target shared by every command
+#[derive(Debug, Args)]
+pub struct TargetArgs {
+    /// Target material
+    #[arg(long, global = true, default_value = "water", value_parser = material_names())]
+    pub material: Material,
+
+    /// Projectile
+    #[arg(long, global = true, default_value = "proton", value_parser = particle_names())]
+    pub particle: Projectile,
+
+    /// Temperature of a gas target (K)
+    #[arg(long, global = true, value_parser = positive)]
+    pub temperature: Option<f64>,
+
+    /// Pressure of a gas target (atm)
+    #[arg(long, global = true, value_parser = positive)]
+    pub pressure: Option<f64>,
+
+    /// Table of βγ and C/Z columns with the shell corrections of the material, e.g. Bichsel's, used by the shell-table correction
+    #[arg(long, global = true, value_name = "FILE")]
+    pub shell_table: Option<PathBuf>,
+}
+
+// Style of every plot, defaulting to that of `PlotStyle`
+#[derive(Debug, Args)]
+pub struct StyleArgs {
+    /// Size of the images in pixels at 96 dpi, e.g. 1000x600
+    #[arg(long, global = true, value_name = "WIDTHxHEIGHT", value_parser = parse_size)]
+    pub plot_size: Option<(u32, u32)>,
+
+    /// Resolution of the PNG images, scaling their size, fonts and lines (96 by default)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    pub dpi: Option<u32>,
+
+    /// Gnuplot terminal of the PNG images, e.g. png; pngcairo by default
+    #[arg(long, global = true)]
+    pub terminal: Option<String>,
+
+    /// Colors of the figures: light (dark text on white, for papers) or dark (for slides)
+    #[arg(long, global = true)]
+    pub theme: Option<Theme>,
+
+    /// Comma separated colors (gnuplot names or #rrggbb) the curves take in turn, those of the theme by default
+    #[arg(long, global = true, value_name = "COLORS", value_delimiter = ',')]
+    pub palette: Vec<String>,
+
+    /// Width of the lines, relative to the default one
+    #[arg(long, global = true, value_parser = positive)]
+    pub line_width: Option<f64>,
+
+    /// Font family of the titles, labels and legends
+    #[arg(long, global = true)]
+    pub font: Option<String>,
+
+    /// Font size (points) of the labels and legends, the titles being 6 points larger
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    pub font_size: Option<u32>,
+
+    /// Corner of the legend: top-right, top-left, bottom-right, bottom-left or none
+    #[arg(long, global = true)]
+    pub legend: Option<Legend>,
+
+    /// Draw a grid at the major ticks
+    #[arg(long, global = true)]
+    pub grid: bool,
+
+    /// Base of the logarithmic axes (10 by default)
+    #[arg(long, global = true, value_parser = log_base)]
+    pub log_base: Option<f64>,
+}
+
+impl StyleArgs {
+    fn apply_config(&mut self, config: &RunConfig) -> Result<(), String> {
+        let plot = &config.plot;
+        if let Some((width, height)) = plot.size.filter(|_| self.plot_size.is_none()) {
+            self.plot_size = Some(checked_size(width, height).map_err(|e| format!("plot.size: {}", e))?);
+        }
+        if let Some(dpi) = plot.dpi.filter(|_| self.dpi.is_none()) {
+            self.dpi = Some(if dpi > 0 { dpi } else { return Err(String::from("plot.dpi: must be positive")) });
+        }
+        self.terminal = self.terminal.take().or_else(|| plot.terminal.clone());
+        if let Some(theme) = plot.theme.as_deref().filter(|_| self.theme.is_none()) {
+            self.theme = Some(theme.parse().map_err(|e| format!("plot.theme: {}", e))?);
+        }
+        if let Some(palette) = plot.palette.clone().filter(|_| self.palette.is_empty()) {
+            self.palette = palette;
+        }
+        if let Some(width) = plot.line_width.filter(|_| self.line_width.is_none()) {
+            self.line_width = Some(positive(&width.to_string()).map_err(|e| format!("plot.line_width: {}", e))?);
+        }
+        self.font = self.font.take().or_else(|| plot.font.clone());
+        if let Some(size) = plot.font_size.filter(|_| self.font_size.is_none()) {
+            self.font_size = Some(if size > 0 { size } else { return Err(String::from("plot.font_size: must be positive")) });
+        }
+        if let Some(legend) = plot.legend.as_deref().filter(|_| self.legend.is_none()) {
+            self.legend = Some(legend.parse().map_err(|e| format!("plot.legend: {}", e))?);
+        }
+        self.grid |= plot.grid == Some(true);
+        if let Some(base) = plot.log_base.filter(|_| self.log_base.is_none()) {
+            self.log_base = Some(log_base(&base.to_string()).map_err(|e| format!("plot.log_base: {}", e))?);
+        }
+        Ok(())
+    }
+
+    // Style given by the options, the default one for those missing
+    pub fn style(&self) -> PlotStyle {
+        let default = PlotStyle::default();
+        let (width, height) = self.plot_size.unwrap_or((default.width, default.height));
+        let theme = self.theme.unwrap_or(default.theme);
+        PlotStyle {
+            width,
+            height,
+            dpi: self.dpi.unwrap_or(default.dpi),
+            terminal: self.terminal.clone(),
+            theme,
+            palette: if self.palette.is_empty() { theme.palette() } else { self.palette.clone() },
+            line_width: self.line_width.unwrap_or(default.line_width),
+            font: self.font.clone().unwrap_or(default.font),
+            font_size: self.font_size.unwrap_or(default.font_size),
+            legend: self.legend.unwrap_or(default.legend),
+            grid: self.grid,
+            log_base: self.log_base.unwrap_or(default.log_base),
+        }
+    }
+}
+
+// Every stopping-power table and plot, and the Bragg curve with --bragg
+#[derive(Debug, Args)]
+pub struct TableArgs {
+    #[command(flatten)]
+    pub stopping: StoppingArgs,
+
+    /// Initial energy of a Bragg curve (MeV)
+    #[arg(long, value_name = "MEV", value_parser = positive)]
+    pub bragg: Option<f64>,
+
+    #[command(flatten)]
+    pub bragg_options: BraggOptions,
+}
+
+// Stopping-power tables with and without corrections
+#[derive(Debug, Args)]
+pub struct StoppingArgs {
+    /// Lowest kinetic energy of the grid (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 10.0)]
+    pub emin: f64,
+
+    /// Highest kinetic energy of the grid (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 10000.0)]
+    pub emax: f64,
+
+    /// Number of grid points
+    #[arg(long, value_parser = clap::value_parser!(u64).range(2..).map(|points| points as usize), default_value_t = 1000)]
+    pub points: usize,
+
+    /// Grid spacing: linear or log
+    #[arg(long, default_value = "linear")]
+    pub spacing: Spacing,
+
+    /// Comma separated corrections of a single table (density, shell, shell-table, barkas, bloch or none)
+    /// instead of the four standard variants
+    #[arg(long, value_name = "LIST")]
+    pub corrections: Option<Corrections>,
+
+    /// Comma separated projectiles of a batch run over every projectile and material
+    #[arg(long, value_delimiter = ',', value_parser = particle_names())]
+    pub particles: Vec<Projectile>,
+
+    /// Comma separated materials of a batch run over every projectile and material
+    #[arg(long, value_delimiter = ',', value_parser = material_names())]
+    pub materials: Vec<Material>,
+
+    #[command(flatten)]
+    pub density: DensityArgs,
+
+    /// Output unit: MeV/cm, keV/um or "MeV cm2/g"
+    #[arg(long)]
+    pub unit: Option<Unit>,
+
+    /// Report mass stopping powers (shorthand for --unit "MeV cm2/g")
+    #[arg(long)]
+    pub mass: bool,
+
+    /// Abscissa of the tables: energy, momentum or betagamma
+    #[arg(long)]
+    pub axis: Option<Axis>,
+
+    /// Add the radiative losses of muons and electrons
+    #[arg(long)]
+    pub radiative: bool,
+
+    /// Uncertainty of the mean excitation energy (eV), drawn as a band around each curve
+    #[arg(long, value_name = "EV", value_parser = positive)]
+    pub i_uncertainty: Option<f64>,
+
+    /// Also draw the curves of every table, each variant of the corrections of each projectile and
+    /// material, on a single figure
+    #[arg(long)]
+    pub overlay: bool,
+
+    /// Also draw the four variants of the corrections of each projectile and material as the panels of a
+    /// single 2×2 figure
+    #[arg(long, conflicts_with = "corrections")]
+    pub summary: bool,
+}
+
+impl StoppingArgs {
+    fn apply_config(&mut self, config: &RunConfig, matches: &ArgMatches) -> Result<(), String> {
+        // The values of the file go through the checks of the flags, and the grid they make
+        // through those of the run
+        let grid = &config.grid;
+        if let Some(emin) = grid.emin.filter(|_| !given(matches, "emin")) {
+            self.emin = positive(&emin.to_string()).map_err(|e| format!("grid.emin: {}", e))?;
+        }
+        if let Some(emax) = grid.emax.filter(|_| !given(matches, "emax")) {
+            self.emax = positive(&emax.to_string()).map_err(|e| format!("grid.emax: {}", e))?;
+        }
+        if let Some(points) = grid.points.filter(|_| !given(matches, "points")) {
+            self.points = if points >= 2 { points } else { return Err(String::from("grid.points: must be at least 2")) };
+        }
+        if let Some(spacing) = grid.spacing.as_deref().filter(|_| !given(matches, "spacing")) {
+            self.spacing = spacing.parse().map_err(|e| format!("grid.spacing: {}", e))?;
+        }
+        if *grid != GridConfig::default() {
+            EnergyGrid { min: self.emin, max: self.emax, points: self.points, spacing: self.spacing }.check().map_err(|e| format!("grid: {}", e))?;
+        }
+        self.corrections = self.corrections.or(config_corrections(config)?);
+        if let Some(unit) = config.unit.as_deref().filter(|_| self.unit.is_none()) {
+            self.unit = Some(unit.parse().map_err(|e| format!("unit: {}", e))?);
+        }
+        if let Some(axis) = config.axis.as_deref().filter(|_| self.axis.is_none()) {
+            self.axis = Some(axis.parse().map_err(|e| format!("axis: {}", e))?);
+        }
+        self.radiative |= config.radiative.unwrap_or_default();
+        self.overlay |= config.overlay == Some(true);
+        self.summary |= config.summary == Some(true) && self.corrections.is_none();
+        self.density.apply_config(config);
+        Ok(())
+    }
+}
+
+// RBE weighting and nuclear attenuation of a Bragg curve
+#[derive(Debug, Args)]
+pub struct BraggOptions {
+    /// RBE weighting of the Bragg curve: a constant RBE or mcnamara
+    #[arg(long, value_parser = parse_rbe)]
+    pub rbe: Option<RbeModel>,
+
+    /// Photon (α/β) of the McNamara model (Gy)
+    #[arg(long, value_parser = positive, requires = "rbe")]
+    pub alpha_beta: Option<f64>,
+
+    /// Physical dose at the Bragg peak for the RBE weighting (Gy)
+    #[arg(long, value_parser = positive, default_value_t = 2.0)]
+    pub dose: f64,
+
+    /// Attenuate the primary fluence of the Bragg curve by nuclear interactions
+    #[arg(long)]
+    pub nuclear: bool,
+
+    /// Nonelastic cross section (barn) for the attenuation, instead of the interaction length
+    #[arg(long, value_parser = positive)]
+    pub cross_section: Option<f64>,
+}
+
+// Sternheimer density-effect coefficients, defaulting to those of the material
+#[derive(Debug, Args)]
+pub struct DensityArgs {
+    /// Sternheimer coefficient a
+    #[arg(long)]
+    pub a: Option<f64>,
+
+    /// Sternheimer coefficient x0
+    #[arg(long)]
+    pub x0: Option<f64>,
+
+    /// Sternheimer coefficient x1
+    #[arg(long)]
+    pub x1: Option<f64>,
+
+    /// Sternheimer coefficient C (-C in the tables)
+    #[arg(long)]
+    pub c: Option<f64>,
+
+    /// Sternheimer coefficient m
+    #[arg(long)]
+    pub m: Option<f64>,
+
+    /// Density correction of conductors below x0 (δ0)
+    #[arg(long)]
+    pub delta0: Option<f64>,
+}
+
+impl StoppingArgs {
+    // Energy grid of the stopping-power tables
+    pub fn grid(&self) -> EnergyGrid {
+        EnergyGrid { min: self.emin, max: self.emax, points: self.points, spacing: self.spacing }
+    }
+
+    // How the tables are computed: --mass stands for MeV cm²/g
+    pub fn settings(&self) -> TableSettings {
+        TableSettings {
+            grid: self.grid(),
+            corrections: self.corrections,
+            radiative: self.radiative,
+            unit: if self.mass { Unit::MevCm2PerG } else { self.unit.unwrap_or_default() },
+            axis: self.axis.unwrap_or_default(),
+            i_uncertainty: self.i_uncertainty,
+        }
+    }
+
+    // Every projectile and material of a batch run, --particles and --materials defaulting to
+    // --particle and --material
+    pub fn targets(&self, projectile: &Projectile, material: &Material) -> Vec<(Projectile, Material)> {
+        let projectiles = if self.particles.is_empty() { std::slice::from_ref(projectile) } else { &self.particles };
+        let materials = if self.materials.is_empty() { std::slice::from_ref(material) } else { &self.materials };
+        run::targets(projectiles, materials)
+    }
+}
+
+impl DensityArgs {
+    fn apply_config(&mut self, config: &RunConfig) {
+        let density = &config.density;
+        self.a = self.a.or(density.a);
+        self.x0 = self.x0.or(density.x0);
+        self.x1 = self.x1.or(density.x1);
+        self.c = self.c.or(density.c);
+        self.m = self.m.or(density.m);
+        self.delta0 = self.delta0.or(density.delta0);
+    }
+
+    // Whether a, x0, x1, C and m were all given
+    pub fn is_complete(&self) -> bool {
+        self.a.is_some() && self.x0.is_some() && self.x1.is_some() && self.c.is_some() && self.m.is_some()
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Stopping-power tables with and without corrections, written without plots
+    Stopping(StoppingArgs),
+    /// CSDA range and straggling at an energy, and the range table up to it
+    Range(RangeArgs),
+    /// Bragg curve and LET profile, optionally RBE-weighted
+    Bragg(BraggArgs),
+    /// Plots of the stopping-power tables with and without corrections, or of tables written by earlier runs
+    Plot(PlotArgs),
+    /// Read kinetic energies (MeV) from standard input, one per line, and write "energy dE/dx" lines to standard output
+    Pipe(PipeArgs),
+    /// Interactive session answering commands such as `set material aluminium`, `dedx 150` or `range 200`
+    Repl,
+    /// Compare the correction variants with a reference table (PSTAR protons in water by default)
+    Validate(ValidateArgs),
+    /// Water-equivalent thickness of a slab
+    Wet(WetArgs),
+    /// Fluence-to-dose conversion
+    Dose(DoseArgs),
+    /// Kinetic energy from a CSDA range or a dE/dx
+    Energy(EnergyArgs),
+    /// Least-squares fit of the Sternheimer coefficients to a reference table
+    FitDensity(FitDensityArgs),
+    /// Bragg-Kleeman fit of the range table
+    Fit(FitArgs),
+    /// Cherenkov threshold, and the angle and yield at an energy
+    Cherenkov(CherenkovArgs),
+    /// Overlay external (energy, dE/dx) files on the computed curve
+    Compare(CompareArgs),
+    /// Delta-ray spectrum
+    Delta(DeltaArgs),
+    /// Ion pairs and collected charge along a track
+    Ionization(IonizationArgs),
+    /// Monte Carlo transport with energy-loss straggling
+    Mc(McArgs),
+    /// dE/dx of several species against momentum
+    Pid(PidArgs),
+    /// Collision and radiative losses of electrons and the critical energy
+    Radiative,
+    /// Mass stopping-power ratio of the material to another one
+    Ratio(RatioArgs),
+    /// Color map of dE/dx over energy and the atomic number of the elements or the thickness crossed
+    Heatmap(HeatmapArgs),
+    /// Lateral spread of a pencil beam against depth
+    Scatter(ScatterArgs),
+    /// Birks-quenched scintillation light
+    Scintillation(ScintillationArgs),
+    /// Sensitivity of dE/dx to the Sternheimer coefficients
+    Sensitivity(SensitivityArgs),
+    /// Spread-out Bragg peak
+    Sobp(SobpArgs),
+    /// Transport through a stack of layers
+    Stack(StackArgs),
+    /// ΔE-E telescope scan for several species
+    Telescope(Box<TelescopeArgs>),
+    /// Shell completion script, e.g. `system_rs completions bash > /etc/bash_completion.d/system_rs`
+    #[command(hide = true)]
+    Completions(CompletionsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// Shell the script is written for
+    pub shell: Shell,
+}
+
+#[derive(Debug, Args)]
+pub struct RangeArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct BraggArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Comma separated corrections (density, shell, shell-table, barkas, bloch or none), density and shell by default
+    #[arg(long, value_name = "LIST")]
+    pub corrections: Option<Corrections>,
+    /// Add the radiative losses of muons and electrons
+    #[arg(long)]
+    pub radiative: bool,
+    #[command(flatten)]
+    pub density: DensityArgs,
+    #[command(flatten)]
+    pub options: BraggOptions,
+}
+
+#[derive(Debug, Args)]
+pub struct PlotArgs {
+    /// Text, CSV or JSON tables of earlier runs, plotted instead of computing the stopping-power tables
+    #[arg(value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+    #[command(flatten)]
+    pub stopping: StoppingArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct PipeArgs {
+    /// Comma separated corrections (density, shell, shell-table, barkas, bloch or none), density and shell by default
+    #[arg(long, value_name = "LIST")]
+    pub corrections: Option<Corrections>,
+    /// Output unit: MeV/cm, keV/um or "MeV cm2/g"
+    #[arg(long)]
+    pub unit: Option<Unit>,
+    /// Add the radiative losses of muons and electrons
+    #[arg(long)]
+    pub radiative: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Reference table instead of the embedded PSTAR data
+    #[arg(long)]
+    pub reference: Option<PathBuf>,
+    /// Column of the stopping power in the reference table
+    #[arg(long, default_value_t = 1)]
+    pub column: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct WetArgs {
+    /// Slab thickness (cm)
+    #[arg(long, value_parser = positive)]
+    pub thickness: f64,
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct EnergyArgs {
+    /// CSDA range (cm)
+    #[arg(long, value_parser = positive, required_unless_present = "dedx")]
+    pub range: Option<f64>,
+    /// Stopping power (MeV/cm)
+    #[arg(long, value_parser = positive)]
+    pub dedx: Option<f64>,
+}
+
+#[derive(Debug, Args)]
+pub struct FitDensityArgs {
+    /// Reference table, e.g. a PSTAR export
+    #[arg(long)]
+    pub reference: PathBuf,
+    /// Column of the stopping power in the reference table
+    #[arg(long, default_value_t = 1)]
+    pub column: usize,
+    /// Lowest reference energy used in the fit (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 10.0)]
+    pub emin: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct FitArgs {
+    /// Lowest energy of the fit (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 10.0)]
+    pub emin: f64,
+    /// Highest energy of the fit (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 250.0)]
+    pub emax: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct CherenkovArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: Option<f64>,
+}
+
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// Data files with the energy (MeV) and dE/dx in the first two columns
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+    /// Unit of the dE/dx in the files
+    #[arg(long)]
+    pub unit: Option<Unit>,
+    /// Column of the dE/dx in the files
+    #[arg(long, default_value_t = 1)]
+    pub column: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct DeltaArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Lowest delta-ray energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 0.01)]
+    pub threshold: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct IonizationArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Layer thickness (cm)
+    #[arg(long, value_parser = positive, default_value_t = 1.0)]
+    pub thickness: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct McArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Number of histories
+    #[arg(long, default_value_t = 1000)]
+    pub histories: usize,
+    /// Step length (cm)
+    #[arg(long, value_parser = positive, default_value_t = 0.01)]
+    pub step: f64,
+    /// Seed of the random number generator
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Args)]
+pub struct PidArgs {
+    /// Comma separated species (pion, kaon, proton, ... by default)
+    #[arg(long, value_delimiter = ',', value_parser = particle_names())]
+    pub species: Option<Vec<Projectile>>,
+    /// Lowest momentum (MeV/c)
+    #[arg(long, value_parser = positive, default_value_t = 100.0)]
+    pub pmin: f64,
+    /// Highest momentum (MeV/c)
+    #[arg(long, value_parser = positive, default_value_t = 100000.0)]
+    pub pmax: f64,
+    /// Output unit
+    #[arg(long)]
+    pub unit: Option<Unit>,
+}
+
+#[derive(Debug, Args)]
+pub struct RatioArgs {
+    /// Material in the denominator
+    #[arg(long, default_value = "air", value_parser = material_names())]
+    pub to: Material,
+    /// Lowest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 1.0)]
+    pub emin: f64,
+    /// Highest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 1000.0)]
+    pub emax: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct HeatmapArgs {
+    /// Scanned parameter: z (mass stopping power in each element) or thickness (dE/dx after crossing that much of the material)
+    #[arg(long, default_value = "z")]
+    pub scan: Scan,
+    /// Lowest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 1.0)]
+    pub emin: f64,
+    /// Highest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 1000.0)]
+    pub emax: f64,
+    /// Number of energies, logarithmically spaced
+    #[arg(long, value_parser = clap::value_parser!(u64).range(2..).map(|points| points as usize), default_value_t = 100)]
+    pub points: usize,
+    /// Largest thickness (cm) of a thickness scan, the CSDA range at the highest energy by default
+    #[arg(long, value_parser = positive)]
+    pub thickness: Option<f64>,
+    /// Number of thicknesses of a thickness scan, evenly spaced from zero
+    #[arg(long, default_value_t = 50)]
+    pub steps: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct ScatterArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Initial beam size (cm)
+    #[arg(long, default_value_t = 0.0)]
+    pub sigma0: f64,
+    /// Step length (cm)
+    #[arg(long, value_parser = positive, default_value_t = 0.01)]
+    pub step: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct ScintillationArgs {
+    /// Birks coefficient kB (cm/MeV)
+    #[arg(long)]
+    pub kb: Option<f64>,
+    /// Light yield (photons/MeV)
+    #[arg(long = "yield", value_parser = positive)]
+    pub light_yield: Option<f64>,
+    /// Kinetic energy of a stopping particle (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: Option<f64>,
+}
+
+#[derive(Debug, Args)]
+pub struct SensitivityArgs {
+    /// Perturbation of each coefficient (%)
+    #[arg(long, value_parser = positive, default_value_t = 5.0)]
+    pub percent: f64,
+    /// Lowest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 10.0)]
+    pub emin: f64,
+    /// Highest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 100000.0)]
+    pub emax: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct SobpArgs {
+    /// Proximal depth of the plateau (cm)
+    #[arg(long, value_parser = positive)]
+    pub from: f64,
+    /// Distal depth of the plateau (cm)
+    #[arg(long, value_parser = positive)]
+    pub to: f64,
+    /// Depth spacing of the pristine peaks (cm)
+    #[arg(long, value_parser = positive, default_value_t = 0.25)]
+    pub spacing: f64,
+    /// Step length (cm)
+    #[arg(long, value_parser = positive, default_value_t = 0.01)]
+    pub step: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct StackArgs {
+    /// Layer as <material>:<cm>, repeated in beam order
+    #[arg(long = "layer", required = true, value_parser = parse_layer)]
+    pub layers: Vec<Layer>,
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Step length (cm)
+    #[arg(long, value_parser = positive, default_value_t = 0.01)]
+    pub step: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct TelescopeArgs {
+    /// ΔE detector as <material>:<cm>
+    #[arg(long, default_value = "silicon:0.005", value_parser = parse_layer)]
+    pub delta: Layer,
+    /// E detector as <material>:<cm>
+    #[arg(long, default_value = "silicon:1.0", value_parser = parse_layer)]
+    pub stop: Layer,
+    /// Comma separated species
+    #[arg(long, value_delimiter = ',', value_parser = particle_names(), default_values = ["proton", "deuteron", "alpha"])]
+    pub species: Vec<Projectile>,
+    /// Lowest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = range::DEFAULT_CUTOFF)]
+    pub emin: f64,
+    /// Highest energy (MeV)
+    #[arg(long, value_parser = positive, default_value_t = 200.0)]
+    pub emax: f64,
+}
+
+#[derive(Debug, Args)]
+pub struct DoseArgs {
+    /// Kinetic energy (MeV)
+    #[arg(long, value_parser = positive)]
+    pub energy: f64,
+    /// Fluence (1/cm²)
+    #[arg(long, value_parser = positive, conflicts_with_all = ["particles", "current"])]
+    pub fluence: Option<f64>,
+    /// Number of particles over the area
+    #[arg(long, value_parser = positive, conflicts_with = "current")]
+    pub particles: Option<f64>,
+    /// Beam current (nA)
+    #[arg(long, value_parser = positive)]
+    pub current: Option<f64>,
+    /// Irradiation time with the current (s)
+    #[arg(long, value_parser = positive, default_value_t = 1.0)]
+    pub time: f64,
+    /// Field area (cm²)
+    #[arg(long, value_parser = positive, default_value_t = 1.0)]
+    pub area: f64,
+    /// Dose to deliver (Gy)
+    #[arg(long, value_parser = positive)]
+    pub dose: Option<f64>,
+}
+
+// A "<material>:<cm>" layer
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub material: Material,
+    pub thickness: f64,
+}
+
+// Whether the argument `id` was given on the command line rather than defaulted
+fn given(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+// The correction list of a configuration file, if any
+fn config_corrections(config: &RunConfig) -> Result<Option<Corrections>, String> {
+    config.corrections.as_ref().map(|names| names.join(",").parse().map_err(|e| format!("corrections: {}", e))).transpose()
+}
+
+// Parser of a preset name that lists the presets as possible values, for the help and the
+// shell completions, while still accepting the aliases of `by_name`
+#[derive(Clone)]
+struct Names<T> {
+    names: &'static [&'static str],
+    parse: fn(&str) -> Result<T, String>,
+}
+
+impl<T: Clone + Send + Sync + 'static> TypedValueParser for Names<T> {
+    type Value = T;
+
+    fn parse_ref(&self, cmd: &clap::Command, arg: Option<&Arg>, value: &OsStr) -> Result<T, clap::Error> {
+        self.parse.parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(self.names.iter().map(PossibleValue::new)))
+    }
+}
+
+fn material_names() -> Names<Material> {
+    Names { names: &materials::NAMES, parse: parse_material }
+}
+
+fn particle_names() -> Names<Projectile> {
+    Names { names: &projectile::NAMES, parse: parse_particle }
+}
+
+fn parse_material(name: &str) -> Result<Material, String> {
+    materials::by_name(name).ok_or_else(|| format!("unknown material (available: {})", materials::NAMES.join(", ")))
+}
+
+fn parse_particle(name: &str) -> Result<Projectile, String> {
+    projectile::by_name(name).ok_or_else(|| format!("unknown particle (available: {})", projectile::NAMES.join(", ")))
+}
+
+fn parse_layer(layer: &str) -> Result<Layer, String> {
+    let (name, thickness) = layer.split_once(':').ok_or("expected <material>:<cm>")?;
+    Ok(Layer { material: parse_material(name)?, thickness: positive(thickness)? })
+}
+
+// A constant RBE, or the McNamara model with (α/β) = 2 Gy unless --alpha-beta is given
+fn parse_rbe(model: &str) -> Result<RbeModel, String> {
+    match model.parse::<f64>() {
+        Ok(rbe) if rbe > 0.0 => Ok(RbeModel::Constant(rbe)),
+        Ok(_) => Err(String::from("the RBE must be positive")),
+        Err(_) if model.eq_ignore_ascii_case("mcnamara") => Ok(RbeModel::McNamara { alpha_beta: 2.0 }),
+        Err(_) => Err(String::from("expected a constant RBE or mcnamara")),
+    }
+}
+
+// Lower and upper limits of an axis, "<min>,<max>"
+fn parse_range(range: &str) -> Result<(f64, f64), String> {
+    let (min, max) = range.split_once(',').ok_or("expected <min>,<max>")?;
+    let parse = |value: &str| value.trim().parse::<f64>().map_err(|e| e.to_string());
+    checked_range((parse(min)?, parse(max)?))
+}
+
+fn checked_range((min, max): (f64, f64)) -> Result<(f64, f64), String> {
+    if min < max && min.is_finite() && max.is_finite() { Ok((min, max)) } else { Err(String::from("the lower limit must be below the upper one")) }
+}
+
+// A point marked in black, the label taking the rest of the text after x and y
+fn parse_annotation(annotation: &str) -> Result<Annotation, String> {
+    let mut parts = annotation.splitn(3, ',');
+    let (x, y) = (parts.next().unwrap_or_default(), parts.next().ok_or("expected <x>,<y>,<label>")?);
+    let parse = |value: &str| match value.trim().parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        Ok(_) => Err(String::from("the coordinates must be finite")),
+        Err(error) => Err(error.to_string()),
+    };
+    Ok(Annotation::new(parse(x)?, parse(y)?, parts.next().unwrap_or_default().trim(), "black"))
+}
+
+// Guide lines in black, labelled with the text after their value when there is any
+fn parse_vline(guide: &str) -> Result<Guide, String> {
+    let (value, label) = parse_guide(guide)?;
+    Ok(Guide::vertical(value, label, "black"))
+}
+
+fn parse_hline(guide: &str) -> Result<Guide, String> {
+    let (value, label) = parse_guide(guide)?;
+    Ok(Guide::horizontal(value, label, "black"))
+}
+
+fn parse_guide(guide: &str) -> Result<(f64, &str), String> {
+    let (value, label) = guide.split_once(',').unwrap_or((guide, ""));
+    let value = value.trim().parse::<f64>().map_err(|e| e.to_string())?;
+    if value.is_finite() { Ok((value, label.trim())) } else { Err(String::from("the value must be finite")) }
+}
+
+// Names of the usual delimiters, or the delimiter itself
+fn parse_delimiter(delimiter: &str) -> Result<String, String> {
+    match delimiter {
+        "" => Err(String::from("the delimiter cannot be empty")),
+        "tab" | "\\t" => Ok(String::from("\t")),
+        "space" => Ok(String::from(" ")),
+        "comma" => Ok(String::from(",")),
+        delimiter => Ok(delimiter.to_string()),
+    }
+}
+
+// Image size as <width>x<height>
+fn parse_size(size: &str) -> Result<(u32, u32), String> {
+    let (width, height) = size.split_once(['x', 'X']).ok_or("expected <width>x<height>")?;
+    let parse = |value: &str| value.trim().parse::<u32>().map_err(|e| e.to_string());
+    checked_size(parse(width)?, parse(height)?)
+}
+
+fn checked_size(width: u32, height: u32) -> Result<(u32, u32), String> {
+    if width > 0 && height > 0 { Ok((width, height)) } else { Err(String::from("the width and height must be positive")) }
+}
+
+fn log_base(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(value) if value > 1.0 && value.is_finite() => Ok(value),
+        Ok(_) => Err(String::from("must be a number above 1")),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+fn positive(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(value) if value > 0.0 && value.is_finite() => Ok(value),
+        Ok(_) => Err(String::from("must be a positive number")),
+        Err(error) => Err(error.to_string()),
+    }
+}
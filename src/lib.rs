@@ -0,0 +1,8 @@
+// Library crate exposing the Bethe-Bloch stopping power routines and the
+// plotting helpers, so they can be reused outside of the command line tool.
+pub mod aux;
+
+pub use aux::{bethe_bloch, config, error, export, i18n, output, plotting, store};
+pub use aux::config::RunConfig;
+pub use aux::error::{Error, Result};
+pub use aux::output::OutputConfig;
@@ -1,122 +1,1315 @@
-mod aux;
+mod cli;
+mod repl;
 
-use std::collections::HashMap;
-use std::env;
-use std::io;
-use aux::{bethe_bloch, plotting};
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use indicatif::{ProgressBar, ProgressStyle};
+use log::{debug, error, info, warn, Level, LevelFilter};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use system_rs::{bethe_bloch, error, plotting, Error, OutputConfig, Result, RunConfig};
+use system_rs::i18n::{fill, Labels};
+use system_rs::plotting::chart::{Series, Style};
+use serde_json::json;
+use system_rs::output::{grid_metadata, metadata, Metadata, Notation, PlotNaming, Table, TextFormat};
+use system_rs::export::Export;
+use system_rs::store::Store;
+use system_rs::bethe_bloch::run::{self, variants, Variant};
+use system_rs::bethe_bloch::{cherenkov, comparison, delta_rays, density_fit, dosimetry, electron, gas, ionization, monte_carlo, pid, radiative, range, ratio, rbe, scan, scattering, scintillation, sensitivity, shell, slab, sobp, telescope, validation, wet, Axis, Corrections, DensityCorrectionParams, EnergyGrid, Material, MonteCarlo, Projectile, RbeModel, ReferenceTable, Scan, Scintillator, ShellCorrectionModel, SlabOutcome, Stack, StoppingPowerBand, StoppingPowerTable, Telescope, Unit};
 
-extern crate gnuplot;
+use cli::{BraggArgs, BraggOptions, Cli, CherenkovArgs, Command, CompareArgs, CompletionsArgs, DeltaArgs, DensityArgs, DoseArgs, EnergyArgs, FitArgs, FitDensityArgs, HeatmapArgs, IonizationArgs, McArgs, PidArgs, PipeArgs, PlotArgs, RangeArgs, RatioArgs, ScatterArgs,
+    ScintillationArgs, SensitivityArgs, SobpArgs, StoppingArgs, StackArgs, TelescopeArgs, ValidateArgs, WetArgs};
 
+// How often a --watch run checks whether the configuration file has changed
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 
 fn main() {
+    if let Err(error) = run() {
+        eprintln!("Error: {}", error);
+        process::exit(1);
+    }
+}
 
-    // Default delta correction parameters
-    let a_default = 0.09116;
-    let x0_default = 0.24;
-    let x1_default = 2.8004;
-    let c_default = 3.5017;
-    let m_default = 3.4773;
-
-    // Variables for delta correction parameters (initialize with defaults)
-    let mut a = a_default;
-    let mut x0 = x0_default;
-    let mut x1 = x1_default;
-    let mut c_param = c_default;
-    let mut m_param = m_default;
-
-    // Process command-line arguments
-    let args: Vec<String> = env::args().collect();
-    if args.len() >= 6 {
-        a = args[1].parse().unwrap_or(a_default);
-        x0 = args[2].parse().unwrap_or(x0_default);
-        x1 = args[3].parse().unwrap_or(x1_default);
-        c_param = args[4].parse().unwrap_or(c_default);
-        m_param = args[5].parse().unwrap_or(m_default);
-    } else {
-        println!("Delta correction parameters were not fully provided on the command line.");
-        println!("Would you like to input them via standard input? (y/n): ");
-        let mut answer = String::new();
-        io::stdin().read_line(&mut answer).expect("Failed to read line");
-        if answer.trim().eq_ignore_ascii_case("y") {
-            a = prompt("Enter value for a", a_default);
-            x0 = prompt("Enter value for x0", x0_default);
-            x1 = prompt("Enter value for x1", x1_default);
-            c_param = prompt("Enter value for C", c_default);
-            m_param = prompt("Enter value for m", m_default);
-        } else {
-            println!("Using default delta correction parameters.");
+fn run() -> Result<()> {
+    let matches = Cli::command().get_matches();
+    Cli::check_placement(&matches).unwrap_or_else(|error| error.exit());
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    init_logging(cli.quiet, cli.verbose);
+
+    match cli.config.clone() {
+        Some(path) if cli.watch => watch(&path, &matches),
+        _ => execute(configure(cli, &matches)?),
+    }
+}
+
+// Options of a --config file fill in those not given on the command line
+fn configure(mut cli: Cli, matches: &ArgMatches) -> Result<Cli> {
+    if let Some(path) = cli.config.clone() {
+        let config = RunConfig::read(&path)?;
+        cli.apply_config(&config, matches).map_err(|message| Error::Config { path, message })?;
+    }
+    Ok(cli)
+}
+
+// Repeat the run every time the configuration file is saved. A run that fails, e.g. on a
+// half-edited file or on options that do not apply to the material, is reported and the next
+// change is waited for; every failure of a run comes back as an error for this.
+fn watch(path: &Path, matches: &ArgMatches) -> Result<()> {
+    let modified = || fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    loop {
+        let last = modified();
+        match Cli::from_arg_matches(matches) {
+            Ok(cli) => {
+                if let Err(error) = configure(cli, matches).and_then(execute) {
+                    error!("{}", error);
+                }
+            }
+            Err(error) => error!("{}", error),
+        }
+
+        info!("Watching {} for changes", path.display());
+        while modified() == last {
+            thread::sleep(WATCH_INTERVAL);
+        }
+    }
+}
+
+// Compute the tables, plots or subcommand the options ask for
+fn execute(mut cli: Cli) -> Result<()> {
+    // The pool of the parallel computations can only be built once, by the first run of --watch,
+    // and the plot windows need the main thread, so a changed thread count waits for a restart
+    let threads = cli.threads.unwrap_or_default() as usize;
+    if rayon::ThreadPoolBuilder::new().num_threads(threads).build_global().is_err() {
+        let wanted = if threads > 0 { threads } else { thread::available_parallelism().map_or(1, usize::from) };
+        if wanted != rayon::current_num_threads() {
+            warn!("Keeping the {} threads of the first run, restart to compute on {}", rayon::current_num_threads(), wanted);
         }
     }
 
-    let mut variables = HashMap::new();
-    variables.insert(String::from("a"), a);
-    variables.insert(String::from("x0"), x0);
-    variables.insert(String::from("x1"), x1);
-    variables.insert(String::from("c_param"), c_param);
-    variables.insert(String::from("m_param"), m_param);
+    let projectile = cli.target.particle.clone();
+
+    // Gases can be taken to the temperature (K) and pressure (atm) of the detector, and so
+    // can those of a batch run. The shell corrections of --shell-table belong to the target
+    // material, and to the material of the same name in a batch run.
+    let (temperature, pressure) = (cli.target.temperature, cli.target.pressure);
+    let table = cli.target.shell_table.as_ref().map(shell::read_table).transpose()?;
+    let name = cli.target.material.name.clone();
+    let with_table = |material: Material| match &table {
+        Some(table) if material.name == name => material.with_shell_corrections(table.clone()),
+        _ => material,
+    };
+    let material = gas::at_conditions(cli.target.material.clone(), temperature, pressure).map(with_table)?;
+    let mut batch = Vec::new();
+    if let Some(args) = cli.stopping_mut() {
+        args.materials = args.materials.drain(..).map(|material| gas::at_conditions(material, temperature, pressure).map(with_table)).collect::<Result<_>>()?;
+        batch = args.materials.clone();
+    }
+
+    // The shell-table correction needs the shell corrections of every material
+    if cli.corrections().is_some_and(|corrections| corrections.shell_model == ShellCorrectionModel::Tabulated) {
+        if let Some(missing) = batch.iter().chain([&material]).find(|material| material.shell_corrections.is_none()) {
+            return Err(Error::Invalid(format!("The shell-table correction needs the shell corrections of {}, give them with --shell-table", missing.name)));
+        }
+    }
+
+    // Directory receiving the data files and plots, created when first written to
+    // Database the run and its tables are appended to, and the LaTeX or Markdown tables
+    let command: Vec<String> = std::env::args().collect();
+    let store = cli.store.as_ref().map(|path| Store::open(path, &command.join(" "))).transpose()?;
+    let export = cli.export.map(|markup| Export { at: cli.export_at.clone(), digits: cli.digits.into(), ..Export::new(markup) });
+    let text = TextFormat { notation: cli.notation, precision: cli.precision, delimiter: cli.delimiter.clone() };
+    // Plot files named after a template are of the target; those of a --watch run replace the
+    // plots of the run before
+    let naming = PlotNaming { template: cli.plot_name.clone(), particle: projectile.name.clone(), material: material.name.clone(), corrections: String::new() };
+    let output = OutputConfig {
+        plots: !cli.no_plot,
+        backend: cli.plot_backend,
+        plot_format: cli.plot_format,
+        x_range: cli.x_range,
+        y_range: cli.y_range,
+        style: cli.style.style(),
+        lang: cli.lang,
+        annotations: cli.annotations.clone(),
+        guides: cli.vlines.iter().chain(&cli.hlines).cloned().collect(),
+        stdout: cli.dump_stdout,
+        format: cli.format,
+        store,
+        export,
+        scripts: cli.scripts,
+        text,
+        naming,
+        force: cli.force || cli.watch,
+        ..OutputConfig::new(cli.output_dir)
+    };
+
+    // Prompts would block batch runs, so they need a terminal
+    let interactive = !cli.non_interactive && io::stdin().is_terminal();
+
+    // SUBCOMMANDS
+
+    if let Some(command) = cli.command {
+        return match command {
+            Command::Stopping(args) => run_stopping(&args, &projectile, &material, &output, interactive),
+            Command::Range(args) => run_range(&args, &projectile, &material, &output),
+            Command::Bragg(args) => run_bragg(&args, &projectile, &material, &output, interactive),
+            Command::Plot(args) => run_plot(&args, &projectile, &material, &output, interactive),
+            Command::Pipe(args) => run_pipe(&args, &projectile, &material, &output.text),
+            Command::Repl => repl::run(repl::Session::new(projectile, material), interactive),
+            Command::Validate(args) => run_validate(&args, &projectile, &material, &output),
+            Command::Wet(args) => run_wet(&args, &projectile, &material),
+            Command::Dose(args) => run_dose(&args, &projectile, &material, &output),
+            Command::Energy(args) => run_energy(&args, &projectile, &material),
+            Command::FitDensity(args) => run_fit_density(&args, &projectile, &material, &output),
+            Command::Fit(args) => run_fit(&args, &projectile, &material),
+            Command::Cherenkov(args) => run_cherenkov(&args, &projectile, &material),
+            Command::Compare(args) => run_compare(&args, &projectile, &material, &output),
+            Command::Delta(args) => run_delta(&args, &projectile, &material, &output),
+            Command::Ionization(args) => run_ionization(&args, &projectile, &material),
+            Command::Mc(args) => run_monte_carlo(&args, &projectile, &material, &output),
+            Command::Pid(args) => run_pid(&args, &material, &output),
+            Command::Radiative => run_radiative(&projectile, &material, &output),
+            Command::Ratio(args) => run_ratio(&args, &projectile, &material, &output),
+            Command::Heatmap(args) => run_heatmap(&args, &projectile, &material, &output),
+            Command::Scatter(args) => run_scatter(&args, &projectile, &material, &output),
+            Command::Scintillation(args) => run_scintillation(&args, &projectile, &material, &output),
+            Command::Telescope(args) => run_telescope(&args, &output),
+            Command::Sensitivity(args) => run_sensitivity(&args, &projectile, &material, &output),
+            Command::Sobp(args) => run_sobp(&args, &projectile, &material, &output),
+            Command::Stack(args) => run_stack(&args, &projectile, &output),
+            Command::Completions(args) => run_completions(&args),
+        };
+    }
+
+    // Without a subcommand, every table and plot, and the Bragg curve with --bragg (of the
+    // first projectile and material of a batch run)
+    let tables = cli.tables;
+    let (projectile, material) = tables.stopping.targets(&projectile, &material).swap_remove(0);
+    let params = run_tables(&tables.stopping, &projectile, &material, &output, interactive, Tables::Both)?;
+    if let Some(energy) = tables.bragg {
+        let selection = tables.stopping.corrections.unwrap_or_else(|| Corrections::all(&params));
+        let corrections = Corrections { density_params: params, radiative: tables.stopping.radiative, ..selection };
+        bragg_curve(energy, &tables.bragg_options, &corrections, &projectile, &material, &output)?;
+    }
+    Ok(())
+}
+
+// Stopping-power tables with and without corrections: stopping [--emin <MeV>] [--emax <MeV>] ...
+fn run_stopping(args: &StoppingArgs, projectile: &Projectile, material: &Material, output: &OutputConfig, interactive: bool) -> Result<()> {
+    run_tables(args, projectile, material, output, interactive, Tables::Report).map(|_| ())
+}
+
+// Plots of the stopping-power tables: plot [--emin <MeV>] [--emax <MeV>] ..., or of the tables
+// of earlier runs without computing them again: plot <FILE>...
+fn run_plot(args: &PlotArgs, projectile: &Projectile, material: &Material, output: &OutputConfig, interactive: bool) -> Result<()> {
+    if args.files.is_empty() {
+        return run_tables(&args.stopping, projectile, material, output, interactive, Tables::Plot).map(|_| ());
+    }
+    for path in &args.files {
+        replot(path, output)?;
+    }
+    Ok(())
+}
+
+// Plot a table written by an earlier run, titled after its file. Stopping-power, range, Bragg
+// and color-map tables are drawn as when they were computed, told apart by their header; other
+// tables as their columns against the first one, on a logarithmic axis when it is an energy.
+fn replot(path: &Path, output: &OutputConfig) -> Result<()> {
+    let table = Table::read(path)?;
+    let labels = output.lang.labels();
+    let title = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+    let caption = match (table.metadata_text("projectile"), table.metadata_text("material")) {
+        (Some(projectile), Some(material)) => fill(labels.target, &[&projectile, &material]),
+        _ => title.clone(),
+    };
+    info!("Plotting {}", path.display());
 
+    let header: Vec<&str> = table.header.iter().map(String::as_str).collect();
+    let columns = &table.columns;
+    let unit = |header: &str| header.strip_prefix("dE/dx (").and_then(|unit| unit.strip_suffix(')')).map(str::to_string);
+    let axis = header.first().and_then(|header| table_axis(header));
+    if let (Some(axis), Some(unit)) = (axis, header.get(1).and_then(|header| unit(header)?.parse::<Unit>().ok())) {
+        let table = StoppingPowerTable { energies: columns[0].clone(), stopping_powers: columns[1].clone(), unit, axis, straggling: None };
+        return plot_stopping(&table, None, &caption, &title, output);
+    }
+    match header.as_slice() {
+        ["E (MeV)", parameter @ ("Z" | "t (cm)"), de_dx] => {
+            let (scan, y_label) = if *parameter == "Z" { (Scan::Z, labels.atomic_number) } else { (Scan::Thickness, labels.thickness) };
+            let map = scan::StoppingPowerMap::from_columns(scan, &columns[0], &columns[1], &columns[2]);
+            plotting::plot::plot_heatmap(&map.energies, &map.parameters, &map.stopping_powers, y_label, &unit(de_dx).unwrap_or_default(), &title, output)
+        }
+        ["E (MeV)", "R (cm)", ..] => plotting::plot::plot_range(&columns[0], &columns[1], columns.get(2).map(Vec::as_slice), &caption, &title, output),
+        ["depth (cm)", "dose (MeV/cm)"] => {
+            let peak = columns[0].iter().copied().zip(columns[1].iter().copied()).max_by(|a, b| a.1.total_cmp(&b.1));
+            plotting::plot::plot_bragg(&columns[0], &columns[1], &caption, &title, peak, output)
+        }
+        _ => {
+            let name = |index: usize| header.get(index).copied().unwrap_or_default();
+            let curves: Vec<(&str, &[f64])> = columns.iter().enumerate().skip(1)
+                .map(|(index, ys)| (if columns.len() == 2 { caption.as_str() } else { name(index) }, ys.as_slice()))
+                .collect();
+            let y_label = if columns.len() == 2 { column_label(name(1), output) } else { String::new() };
+            let x_log = table_axis(name(0)).is_some();
+            plotting::plot::plot_columns(&columns[0], &curves, &column_label(name(0), output), &y_label, x_log, &title, output)
+        }
+    }
+}
+
+// Axis label of a column header, in the language of the plots for the quantities the plots of
+// the tables label themselves
+fn column_label(header: &str, output: &OutputConfig) -> String {
+    if let Some(axis) = table_axis(header) {
+        return axis.label(output.lang).to_string();
+    }
+    let labels = output.lang.labels();
+    match header {
+        "depth (cm)" => labels.depth.to_string(),
+        "dose (MeV/cm)" => labels.dose.to_string(),
+        "dose (Gy)" => labels.dose_gy.to_string(),
+        "R (cm)" => labels.csda_range.to_string(),
+        _ => header.to_string(),
+    }
+}
 
-    // Vectors to store energies (in MeV) and stopping power values (in MeV/cm)
-    let mut energies = Vec::with_capacity(1000);
-    let mut stopping_powers = Vec::with_capacity(1000);
-    let n_points = 1000;
+// Abscissa of the stopping-power tables headed `header`
+fn table_axis(header: &str) -> Option<Axis> {
+    [Axis::KineticEnergy, Axis::Momentum, Axis::BetaGamma].into_iter().find(|axis| axis.header() == header)
+}
 
+// Whether the stopping-power tables are printed and written, plotted or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tables {
+    Report,
+    Plot,
+    Both,
+}
 
-    // BETHE-BLOCH WITHOUT CORRECTIONS
+// Compute the tables of every projectile and material of the run, with the data files and plots
+// of a batch run labelled after their target, and plot the last variant of every projectile
+// together for each material of a batch run with several projectiles. The tables of every
+// target are computed in parallel, then written and plotted in the order of the targets.
+// Returns the density parameters of the first material.
+fn run_tables(args: &StoppingArgs, projectile: &Projectile, material: &Material, output: &OutputConfig, interactive: bool, tables: Tables)
+    -> Result<DensityCorrectionParams> {
+    checked(args.grid())?;
+    let targets = args.targets(projectile, material);
+    let batch = targets.len() > 1;
+    let labels = output.lang.labels();
 
-    energies.clear();
-    stopping_powers.clear();
+    // Each material has its own default coefficients, asked for once before any table is computed
+    let mut known_params: Vec<(String, DensityCorrectionParams)> = Vec::new();
+    let mut runs: Vec<(Projectile, Material, DensityCorrectionParams)> = Vec::new();
+    for (projectile, material) in targets {
+        let params = match known_params.iter().find(|(name, _)| *name == material.name) {
+            Some((_, params)) => *params,
+            None => {
+                let params = density_params(&args.density, &material, interactive, labels)?;
+                known_params.push((material.name.clone(), params));
+                params
+            }
+        };
+        runs.push((projectile, material, params));
+    }
 
-    bethe_bloch::bb::bethe_bloch_no_corrections(&n_points, &mut energies, &mut stopping_powers);
+    // The files of every table and plot are checked before the first table is computed
+    let mut files = Vec::new();
+    for (projectile, material, params) in &runs {
+        for variant in variants(args.corrections, params, labels) {
+            let variant = if batch { variant.in_batch(projectile, material, labels) } else { variant };
+            if tables != Tables::Plot {
+                files.extend(output.table_files(&variant.file));
+                if args.i_uncertainty.is_some() {
+                    files.extend(output.table_files(&format!("{}_band", variant.file)));
+                }
+            }
+            if tables != Tables::Report {
+                files.extend(plots_of(output, projectile, material, &variant.corrections).plot_files(&variant.title));
+            }
+        }
+    }
+    output.check_new_files(files)?;
 
-    plotting::plot::plot(&energies, &stopping_powers, "Protones en Agua (Bethe-Bloch)", 
-    "Poder de Frenado en función de la energía SIN correcciones");
+    // One step per grid point of every table, the two of a band included
+    let tables_per_variant = if args.i_uncertainty.is_some() { 3 } else { 1 };
+    let variant_count: usize = runs.iter().map(|(_, _, params)| variants(args.corrections, params, labels).len()).sum();
+    let bar = progress_bar((variant_count * tables_per_variant * args.points) as u64, "Bethe-Bloch");
+    let computed: Vec<_> = runs.par_iter().map(|(projectile, material, params)| run::stopping_tables(&args.settings(), params, projectile, material, labels, || bar.inc(1))).collect();
+    bar.finish_and_clear();
 
+    let mut materials: Vec<(Material, Vec<(Projectile, StoppingPowerTable)>)> = Vec::new();
+    let mut overlay: Vec<Series> = Vec::new();
+    for ((projectile, material, _), variants) in runs.into_iter().zip(computed) {
+        let mut last = None;
+        let mut summary: Vec<(String, StoppingPowerTable)> = Vec::new();
+        for (variant, table, band) in variants {
+            let variant = if batch { variant.in_batch(&projectile, &material, labels) } else { variant };
+            if tables != Tables::Plot {
+                let corrections = Corrections { radiative: args.radiative, ..variant.corrections };
+                let metadata = grid_metadata(&projectile, &material, &corrections, &args.grid());
+                report_variant(&variant, &table, band.as_ref(), &metadata, output)?;
+            }
+            if tables != Tables::Report {
+                plot_variant(&variant, &table, band.as_ref(), &projectile, &material, output)?;
+            }
+            if args.overlay {
+                let label = variant.label(labels);
+                let caption = if batch { format!("{}: {}", target(output, &projectile.name, &material), label) } else { label.to_string() };
+                overlay.push(Series::new(Some(&caption), &table.energies, &table.stopping_powers, Style::Lines, ""));
+            }
+            if args.summary {
+                summary.push((variant.label(labels).to_string(), table.clone()));
+            }
+            last = Some(table);
+        }
 
-    // BETHE-BLOCH WITH DENSISTY 
+        // The variants of the target side by side, two per row
+        if let Some((_, table)) = summary.first().filter(|_| tables != Tables::Report) {
+            let curves: Vec<(&str, &[f64], &[f64])> = summary.iter()
+                .map(|(label, table)| (label.as_str(), table.energies.as_slice(), table.stopping_powers.as_slice()))
+                .collect();
+            let stopping_labels = plotting::plot::StoppingLabels { x_label: table.axis.label(output.lang), unit: table.unit.symbol() };
+            let title = fill(labels.summary_title, &[&projectile.name, &material.name]);
+            plotting::plot::plot_summary(&curves, 2, &title, stopping_labels, output)?;
+        }
 
-    energies.clear();
-    stopping_powers.clear();
+        let Some(table) = last else { continue };
+        match materials.iter_mut().find(|(m, _)| m.name == material.name) {
+            Some((_, curves)) => curves.push((projectile, table)),
+            None => materials.push((material, vec![(projectile, table)])),
+        }
+    }
 
-    bethe_bloch::bb::bethe_bloch_density_corrections(&n_points, &mut energies, &mut stopping_powers, &variables);
+    if tables != Tables::Report {
+        for (material, curves) in materials.iter().filter(|(_, curves)| batch && curves.len() > 1) {
+            let series: Vec<(&str, &[f64], &[f64])> = curves.iter()
+                .map(|(projectile, table)| (projectile.name.as_str(), table.energies.as_slice(), table.stopping_powers.as_slice()))
+                .collect();
+            let table = &curves[0].1;
+            plotting::plot::plot_overlay(&series, table.axis.label(output.lang), &format!("dE/dx ({})", table.unit),
+            &fill(labels.stopping_power_in, &[&material.name]), output)?;
+        }
 
-    plotting::plot::plot(&energies, &stopping_powers, "Protones en Agua (Bethe-Bloch) Correcion Densidad", 
-    "Poder de Frenado en función de la energía con correccion de densidad");
+        // Every table shares the unit and abscissa of the run
+        if let Some((_, curves)) = materials.first().filter(|_| !overlay.is_empty()) {
+            let table = &curves[0].1;
+            let stopping_labels = plotting::plot::StoppingLabels { x_label: table.axis.label(output.lang), unit: table.unit.symbol() };
+            plotting::plot::plot_multi(&overlay, labels.stopping_comparison, stopping_labels, output)?;
+        }
+    }
+    Ok(known_params.first().map_or_else(|| material.density_correction_params(), |(_, params)| *params))
+}
 
+// CSDA range and straggling, and the range table up to the energy: range --energy <MeV>
+fn run_range(args: &RangeArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (energy, cutoff) = (args.energy, range::cutoff(projectile));
+    if energy <= cutoff {
+        return Err(Error::Invalid(format!("The CSDA range of the {} is integrated from {} MeV, use a higher --energy", projectile.name, cutoff)));
+    }
+    output.check_new_files(output.table_files("range_table").into_iter().chain(output.plot_files(output.lang.labels().range_title)))?;
 
-    // BETHE-BLOCH WITH LAYER CORRECTION 
+    let corrections = Corrections::all(&material.density_correction_params());
+    let csda = range::csda_range(energy, projectile, material, &corrections);
 
-    energies.clear();
-    stopping_powers.clear();
+    info!("{} MeV {} in {}", energy, projectile.name, material.name);
+    info!("CSDA range: {:.4} cm ({:.4} g/cm²), straggling sigma: {:.4} cm", csda.length, csda.mass_thickness, csda.straggling);
 
-    bethe_bloch::bb::bethe_bloch_layer_corrections(&n_points, &mut energies, &mut stopping_powers);
+    let grid = EnergyGrid::logarithmic(cutoff, energy, 200);
+    let table = range::range_table(&grid, projectile, material, &corrections);
+    let metadata = grid_metadata(projectile, material, &corrections, &grid);
+    table.write_with(output.table_path("range_table"), output.format, &output.text, &metadata)?;
+    output.record("range_table", &metadata, || table.columns())?;
 
-    plotting::plot::plot(&energies, &stopping_powers, "Protones en Agua (Bethe-Bloch) Correcion Capa", 
-    "Poder de Frenado en función de la energía con correccion de capa");
+    plotting::plot::plot_range(&table.energies, &table.ranges, Some(&table.straggling), &target(output, &projectile.name, material),
+    output.lang.labels().range_title, output)?;
+    Ok(())
+}
 
+// Bragg curve and LET profile: bragg --energy <MeV> [--rbe <RBE | mcnamara>] [--nuclear] ...
+fn run_bragg(args: &BraggArgs, projectile: &Projectile, material: &Material, output: &OutputConfig, interactive: bool) -> Result<()> {
+    let params = density_params(&args.density, material, interactive, output.lang.labels())?;
+    let selection = args.corrections.unwrap_or_else(|| Corrections::all(&params));
+    let corrections = Corrections { density_params: params, radiative: args.radiative, ..selection };
+    bragg_curve(args.energy, &args.options, &corrections, projectile, material, output)
+}
 
-    // BETHE-BLOCH WITH ALL CORRECTIONS
+// dE/dx of the energies read from standard input, written to standard output so the tool can
+// sit in a pipeline: pipe [--corrections <list>] [--unit <unit>] [--radiative]
+fn run_pipe(args: &PipeArgs, projectile: &Projectile, material: &Material, text: &TextFormat) -> Result<()> {
+    let params = material.density_correction_params();
+    let selection = args.corrections.unwrap_or_else(|| Corrections::all(&params));
+    let corrections = Corrections { density_params: params, radiative: args.radiative, ..selection };
+    let unit: Unit = args.unit.unwrap_or_default();
 
-    energies.clear();
-    stopping_powers.clear();
+    // Standard output is line buffered, so every result is passed on as soon as it is computed
+    let mut stdout = io::stdout().lock();
+    for (number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.map_err(Error::Input)?;
 
-    bethe_bloch::bb::bethe_bloch_all_corrections(&n_points, &mut energies, &mut stopping_powers, &variables);
-    
-    plotting::plot::plot(&energies, &stopping_powers, "Protones en Agua (Bethe-Bloch) Correciones Densidad y Capa", 
-    "Poder de Frenado en función de la energía con correcciones de densidad y capa");
+        // Blank and comment lines are skipped, and only the first column of a table is read. A
+        // line without a positive energy is reported and skipped too, so that one bad record
+        // does not stop the pipeline.
+        let Some(field) = line.split(|c: char| c.is_whitespace() || c == ',').find(|f| !f.is_empty()) else { continue };
+        if field.starts_with('#') {
+            continue;
+        }
+        let energy: f64 = match field.parse() {
+            Ok(energy) if energy > 0.0 => energy,
+            _ => {
+                warn!("line {}: '{}' is not a positive energy, skipped", number + 1, field);
+                continue;
+            }
+        };
 
+        let de_dx = unit.from_mev_per_cm(bethe_bloch::bb::stopping_power(energy, projectile, material, &corrections), material);
+        match writeln!(stdout, "{}", text.join(&[text.number(energy, Notation::Fixed, None), text.scientific(de_dx)])) {
+            Ok(()) => {}
+            // The reader at the other end of the pipe has finished
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(error) => return Err(Error::Output(error)),
+        }
+    }
+    Ok(())
+}
+
+// Completion script of the command line for a shell: completions <bash|zsh|fish|...>
+fn run_completions(args: &CompletionsArgs) -> Result<()> {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(args.shell, &mut command, name, &mut script);
+    io::stdout().write_all(&script).map_err(Error::Output)
+}
+
+// Deviation of each correction variant from a reference table, by default the embedded PSTAR
+// table of protons in water: validate [--reference <file>] [--column <n>]
+fn run_validate(args: &ValidateArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (reference, source) = match &args.reference {
+        Some(path) => (ReferenceTable::read(path, args.column)?, path.display().to_string()),
+        None => {
+            if projectile.name != "proton" || material.name != "water" {
+                return Err(Error::Invalid(format!("The embedded PSTAR table is for protons in water, use --reference <file> for {} in {}", projectile.name, material.name)));
+            }
+            (ReferenceTable::pstar_water(), String::from("PSTAR"))
+        }
+    };
+    if reference.is_empty() {
+        return Err(Error::Invalid(format!("No reference points in {}", source)));
+    }
+
+    let params = material.density_correction_params();
+    let labels = output.lang.labels();
+    let (density, shell) = (capitalized(labels.density), capitalized(labels.shell));
+    let title = fill(labels.validation_title, &[&source]);
+    output.check_new_files([output.path("validation.txt")].into_iter().chain(output.plot_files(&title)))?;
+    let variants = [
+        (labels.no_corrections, Corrections::none()),
+        (density.as_str(), Corrections::density(&params)),
+        (shell.as_str(), Corrections::shell()),
+        (labels.all, Corrections::all(&params)),
+    ];
+    let validations = validation::validate(&reference, projectile, material, &variants);
+
+    info!("Deviation of {} in {} from {} ({} points)", projectile.name, material.name, source, reference.len());
+    for validation in &validations {
+        if let Some((energy, deviation)) = validation.max_deviation() {
+            info!("{}: RMS {:.3}%, max {:+.3}% at {} MeV", validation.label, 100.0 * validation.rms(), 100.0 * deviation, energy);
+        }
+    }
+    validation::write_validations(&reference, &validations, output.path("validation.txt"), &output.text)?;
+
+    let percent: Vec<Vec<f64>> = validations.iter().map(|v| v.deviations.iter().map(|d| 100.0 * d).collect()).collect();
+    let series: Vec<(&str, &[f64], &[f64])> = validations.iter().zip(&percent)
+        .map(|(validation, deviations)| (validation.label.as_str(), validation.energies.as_slice(), deviations.as_slice()))
+        .collect();
+    plotting::plot::plot_residuals(&series, &title, output)?;
+    Ok(())
+}
+
+// Water-equivalent thickness of a slab: wet --thickness <cm> --energy <MeV>
+fn run_wet(args: &WetArgs, projectile: &Projectile, material: &Material) -> Result<()> {
+    let (thickness, energy) = (args.thickness, args.energy);
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let wet = wet::water_equivalent_thickness(energy, projectile, material, thickness, &corrections);
+
+    info!("{} cm of {} for {} MeV {}", thickness, material.name, energy, projectile.name);
+    match slab::energy_after_slab(projectile, material, thickness, energy, &corrections) {
+        SlabOutcome::Exits(exit_energy) => info!("Exit energy: {:.4} MeV", exit_energy),
+        SlabOutcome::Stops(depth) => info!("The {} stops inside the slab at {:.4} cm", projectile.name, depth),
+    }
+    info!("Water-equivalent thickness: {:.4} cm", wet);
+    Ok(())
+}
+
+// Fluence to dose: dose --energy <MeV> [--fluence <1/cm2> | --particles <n> --area <cm2> | --current <nA> --time <s> --area <cm2>] [--dose <Gy>]
+fn run_dose(args: &DoseArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (energy, area) = (args.energy, args.area);
+    output.check_new_files([output.path("dose_conversion.txt")])?;
+
+    let field = match (args.fluence, args.particles, args.current) {
+        (Some(fluence), _, _) => Some(dosimetry::Field::new(fluence * area, area)),
+        (None, Some(particles), _) => Some(dosimetry::Field::new(particles, area)),
+        (None, None, Some(current)) => Some(dosimetry::Field::from_current(current, args.time, area, projectile.charge)),
+        (None, None, None) => None,
+    };
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let mass_stopping_power = bethe_bloch::bb::stopping_power(energy, projectile, material, &corrections) / material.density;
+
+    info!("{} MeV {} in {}: S/rho = {:.4} MeV cm²/g, D/Phi = {:e} Gy cm²", energy, projectile.name, material.name,
+        mass_stopping_power, dosimetry::dose_from_fluence(1.0, mass_stopping_power));
+    if let Some(field) = field {
+        info!("Fluence {:e} /cm² ({:e} particles over {} cm²): {:.6} Gy", field.fluence(), field.particles, field.area,
+            dosimetry::field_dose(energy, projectile, material, &corrections, &field));
+    }
+    if let Some(dose) = args.dose {
+        let fluence = dosimetry::fluence_for_dose(dose, mass_stopping_power);
+        info!("{} Gy needs {:e} /cm² ({:e} particles over {} cm²)", dose, fluence, fluence * area, area);
+    }
+
+    let grid = EnergyGrid::logarithmic(range::cutoff(projectile), 10000.0, 200);
+    dosimetry::write_conversion(&grid, projectile, material, &corrections, output.path("dose_conversion.txt"), &output.text)?;
+    Ok(())
+}
+
+// Inverse lookup of the kinetic energy: energy [--range <cm>] [--dedx <MeV/cm>]
+fn run_energy(args: &EnergyArgs, projectile: &Projectile, material: &Material) -> Result<()> {
+    let grid = EnergyGrid::logarithmic(range::cutoff(projectile), 10000.0, 400);
+    let corrections = Corrections::all(&material.density_correction_params());
+
+    if let Some(range) = args.range {
+        match range::range_table(&grid, projectile, material, &corrections).energy_for_range(range) {
+            Some(energy) => info!("CSDA range {} cm in {}: {:.4} MeV {}", range, material.name, energy, projectile.name),
+            None => info!("CSDA range {} cm in {} is outside the tabulated energies", range, material.name),
+        }
+    }
+    if let Some(dedx) = args.dedx {
+        match bethe_bloch::bb::bethe_bloch(projectile, material, &grid, &corrections).energy_for_dedx(dedx) {
+            Some(energy) => info!("dE/dx {} MeV/cm in {}: {:.4} MeV {}", dedx, material.name, energy, projectile.name),
+            None => info!("dE/dx {} MeV/cm in {} is outside the tabulated energies", dedx, material.name),
+        }
+    }
+    Ok(())
+}
+
+// Bragg-Kleeman fit of the range table: fit [--emin <MeV>] [--emax <MeV>]
+fn run_fit(args: &FitArgs, projectile: &Projectile, material: &Material) -> Result<()> {
+    let (emin, emax) = (args.emin, args.emax);
+
+    let grid = checked(EnergyGrid::logarithmic(emin, emax, 100))?;
+    let corrections = Corrections::all(&material.density_correction_params());
+    let table = range::range_table(&grid, projectile, material, &corrections);
+
+    match table.bragg_kleeman_fit() {
+        Some(fit) => {
+            info!("Bragg-Kleeman fit R = alpha E^p for {} in {} ({} to {} MeV)", projectile.name, material.name, emin, emax);
+            info!("alpha: {:e} cm/MeV^p", fit.alpha);
+            info!("p: {:.5}", fit.p);
+            info!("R^2: {:.6}, max relative error: {:.2}%", fit.r_squared, 100.0 * fit.max_relative_error);
+        }
+        None => {
+            return Err(Error::Invalid(format!("Not enough points above {} MeV to fit", range::cutoff(projectile))));
+        }
+    }
+    Ok(())
+}
+
+// Cherenkov threshold, and the angle and visible yield at a given energy: cherenkov [--energy <MeV>]
+fn run_cherenkov(args: &CherenkovArgs, projectile: &Projectile, material: &Material) -> Result<()> {
+    let (beta, threshold) = match (cherenkov::threshold_beta(material), cherenkov::threshold_energy(projectile, material)) {
+        (Some(beta), Some(threshold)) => (beta, threshold),
+        _ => {
+            return Err(Error::Invalid(format!("No refractive index known for {}", material.name)));
+        }
+    };
+    info!("Cherenkov threshold of {} in {}: beta = {:.6}, {:.4} MeV", projectile.name, material.name, beta, threshold);
+
+    if let Some(energy) = args.energy {
+        match cherenkov::cherenkov_angle(energy, projectile, material) {
+            Some(theta) => {
+                info!("At {} MeV: angle {:.3} deg", energy, theta.to_degrees());
+                info!("Photons from 400 to 700 nm: {:.2} per cm", cherenkov::photon_yield(energy, projectile, material, 400.0, 700.0));
+            }
+            None => info!("At {} MeV the {} is below the threshold", energy, projectile.name),
+        }
+    }
+    Ok(())
+}
+
+// Least-squares fit of the Sternheimer coefficients to a reference table:
+// fit-density --reference <file> [--column <n>] [--emin <MeV>]
+fn run_fit_density(args: &FitDensityArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (path, emin) = (args.reference.display(), args.emin);
+    output.check_new_files([output.path("density_fit_residuals.txt")].into_iter().chain(output.plot_files(output.lang.labels().density_fit_title)))?;
+    let reference = ReferenceTable::read(&args.reference, args.column)?;
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let fit = match density_fit::fit_density_params(&reference, projectile, material, &corrections, emin) {
+        Some(fit) => fit,
+        None => {
+            return Err(Error::Invalid(format!("Not enough reference points above {} MeV in {}", emin, path)));
+        }
+    };
+
+    info!("Sternheimer coefficients of {} fitted to {} ({} points, {} iterations)", material.name, path, fit.energies.len(), fit.iterations);
+    info!("a: {:.5}, x0: {:.4}, x1: {:.4}, C: {:.4}, m: {:.4}", fit.params.a, fit.params.x0, fit.params.x1, fit.params.c, fit.params.m);
+    info!("RMS relative residual: {:.4}% -> {:.4}%", 100.0 * fit.initial_rms, 100.0 * fit.rms);
+
+    fit.write(output.path("density_fit_residuals.txt"), &output.text)?;
+
+    let percent = |values: &[f64]| values.iter().map(|r| 100.0 * r).collect::<Vec<f64>>();
+    let (initial, fitted) = (percent(&fit.initial_residuals), percent(&fit.residuals));
+    let labels = output.lang.labels();
+    plotting::plot::plot_residuals(&[(labels.initial, &fit.energies, &initial), (labels.fitted, &fit.energies, &fitted)],
+    labels.density_fit_title, output)?;
+    Ok(())
+}
+
+// Overlay external (energy, dE/dx) files on the computed curve and tabulate the differences:
+// compare <file>... [--unit <unit>] [--column <n>]
+fn run_compare(args: &CompareArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let unit: Unit = args.unit.unwrap_or_default();
+
+    let label = |file: &Path| file.file_stem().map_or_else(|| file.display().to_string(), |stem| stem.to_string_lossy().into_owned());
+    let files = args.files.iter().map(|file| output.path(&format!("compare_{}.txt", label(file))));
+    output.check_new_files(files.chain(output.plot_files(output.lang.labels().external_comparison)))?;
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let mut comparisons = Vec::with_capacity(args.files.len());
+    for file in &args.files {
+        let reference = ReferenceTable::read(file, args.column)?;
+        let path = file.display().to_string();
+        let label = label(file);
+        let label = label.as_str();
+        let comparison = comparison::compare(&reference, label, projectile, material, &corrections, unit);
+        match comparison.max_difference() {
+            Some((energy, difference)) => info!("{}: {} points, mean |difference| {:.3}%, max {:+.3}% at {} MeV",
+                path, comparison.energies.len(), comparison.mean_abs_difference(), difference, energy),
+            None => {
+                return Err(Error::Invalid(format!("No data points in {}", path)));
+            }
+        }
+        comparison.write(output.path(&format!("compare_{}.txt", label)), &output.text)?;
+        comparisons.push(comparison);
+    }
+
+    // Computed curve over the energies of every data set
+    let emin = comparisons.iter().flat_map(|c| c.energies.iter().copied()).fold(f64::INFINITY, f64::min);
+    let emax = comparisons.iter().flat_map(|c| c.energies.iter().copied()).fold(0.0, f64::max);
+    let table = bethe_bloch::bb::bethe_bloch(projectile, material, &EnergyGrid::logarithmic(emin, emax, 200), &corrections)
+        .to_unit(unit, material);
+
+    let ratios: Vec<Vec<f64>> = comparisons.iter().map(|c| c.ratios()).collect();
+    let references: Vec<plotting::plot::ReferenceSeries> = comparisons.iter().zip(&ratios)
+        .map(|(c, ratios)| (c.label.as_str(), c.energies.as_slice(), c.reference.as_slice(), ratios.as_slice()))
+        .collect();
+    plotting::plot::plot_compare(&table.energies, &table.stopping_powers, &format!("{} (Bethe-Bloch)", target(output, &projectile.name, material)),
+    &references, output.lang.labels().external_comparison, unit.symbol(), output)?;
+    Ok(())
+}
+
+// Delta-ray spectrum: delta --energy <MeV> [--threshold <MeV>]
+fn run_delta(args: &DeltaArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (energy, threshold) = (args.energy, args.threshold);
+    output.check_new_files([output.path("delta_spectrum.txt")])?;
+
+    let tmax = bethe_bloch::bb::max_energy_transfer(energy, projectile);
+    info!("Delta rays from {} MeV {} in {} (Tmax = {:.4} MeV)", energy, projectile.name, material.name, tmax);
+    info!("Above {} MeV: {:.4} per cm", threshold, delta_rays::delta_rays_above(threshold, energy, projectile, material));
+
+    // Spectrum on a logarithmic grid from the threshold to Tmax
+    if threshold < tmax {
+        error::write_file(output.path("delta_spectrum.txt"), |file| {
+            let text = &output.text;
+            writeln!(file, "# {}", text.join(&["T (MeV)", "d2N/dTdx (1/(MeV cm))"]))?;
+            for kinetic in EnergyGrid::logarithmic(threshold, tmax, 200).energies() {
+                let density = delta_rays::delta_ray_spectrum(kinetic, energy, projectile, material);
+                writeln!(file, "{}", text.join(&[text.scientific(kinetic), text.scientific(density)]))?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+// Ion pairs and collected charge: ionization --energy <MeV> [--thickness <cm>]
+fn run_ionization(args: &IonizationArgs, projectile: &Projectile, material: &Material) -> Result<()> {
+    let (energy, thickness) = (args.energy, args.thickness);
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let (per_cm, track) = match (
+        ionization::ion_pairs_per_cm(energy, projectile, material, &corrections),
+        ionization::track_ionization(energy, projectile, material, thickness, &corrections),
+    ) {
+        (Some(per_cm), Some(track)) => (per_cm, track),
+        _ => {
+            return Err(Error::Invalid(format!("No W-value known for {}", material.name)));
+        }
+    };
+
+    info!("{} MeV {} in {} (W = {} eV)", energy, projectile.name, material.name, material.w_value.unwrap_or_default());
+    info!("Ion pairs per cm: {:.1}", per_cm);
+    info!("Along {} cm: {:.4} MeV deposited, {:.1} ion pairs, {:.4} fC", thickness, track.deposited, track.ion_pairs, track.charge * 1e15);
+    if track.stopped {
+        info!("The {} stops inside the layer", projectile.name);
+    }
+    Ok(())
+}
+
+// Monte Carlo transport: mc --energy <MeV> [--histories <n>] [--step <cm>] [--seed <n>]
+fn run_monte_carlo(args: &McArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (energy, histories, step) = (args.energy, args.histories, args.step);
+    let files = [output.path("mc_dose.txt"), output.path("mc_stopping.txt")];
+    output.check_new_files(files.into_iter().chain(output.plot_files(output.lang.labels().monte_carlo_bragg)))?;
+    let mut rng = match args.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let csda = range::csda_range(energy, projectile, material, &corrections);
+
+    // Run in parallel batches of about 1% of the histories, advancing the progress bar. Each
+    // batch has a generator seeded from that of the run, and they are merged in order, so that
+    // a --seed gives the same result whatever the number of threads.
+    let batch = (histories / 100).max(1);
+    let batches: Vec<(usize, u64)> = (0..histories).step_by(batch).map(|start| (batch.min(histories - start), rng.gen())).collect();
+    let bar = progress_bar(histories as u64, "Monte Carlo");
+    let runs: Vec<MonteCarlo> = batches
+        .into_par_iter()
+        .map(|(count, seed)| {
+            let run = monte_carlo::monte_carlo(energy, projectile, material, &corrections, step, count, &mut StdRng::seed_from_u64(seed));
+            bar.inc(count as u64);
+            run
+        })
+        .collect();
+    bar.finish_and_clear();
+    let mut result = MonteCarlo::default();
+    for run in &runs {
+        result.merge(run);
+    }
+
+    let (mean, sigma) = result.stopping_depth();
+
+    info!("Monte Carlo: {} histories of {} MeV {} in {}", histories, energy, projectile.name, material.name);
+    info!("Stopping depth: {:.4} ± {:.4} cm", mean, sigma);
+    info!("CSDA range: {:.4} cm, straggling sigma: {:.4} cm", csda.length, csda.straggling);
+    if let Some((depth, dose)) = result.dose.peak() {
+        info!("Bragg peak at {:.3} cm: {} MeV/cm", depth, dose);
+    }
+    result.dose.write(output.path("mc_dose.txt"), &output.text)?;
+    result.stopping.write(output.path("mc_stopping.txt"), &output.text)?;
+
+    plotting::plot::plot_bragg(&result.dose.depths, &result.dose.doses, &format!("{} (Monte Carlo)", target(output, &format!("{} {} MeV", projectile.name, energy), material)),
+    output.lang.labels().monte_carlo_bragg, result.dose.peak(), output)?;
+    Ok(())
+}
+
+// dE/dx bands of several species against momentum: pid [--species <name>,...] [--pmin <MeV/c>] [--pmax <MeV/c>] [--unit <unit>]
+fn run_pid(args: &PidArgs, material: &Material, output: &OutputConfig) -> Result<()> {
+    let projectiles = args.species.clone().unwrap_or_else(pid::default_projectiles);
+    let title = fill(output.lang.labels().particle_identification, &[&material.name]);
+    let files = projectiles.iter().map(|projectile| output.path(&format!("pid_{}.txt", projectile.name)));
+    output.check_new_files(files.chain(output.plot_files(&title)))?;
+    let (pmin, pmax) = (args.pmin, args.pmax);
+    let unit: Unit = args.unit.unwrap_or_default();
+
+    let grid = checked(EnergyGrid::logarithmic(pmin, pmax, 300))?;
+    let corrections = Corrections::all(&material.density_correction_params());
+    let curves: Vec<_> = pid::pid_curves(&grid, &projectiles, material, &corrections)
+        .into_iter()
+        .map(|curve| pid::PidCurve { table: curve.table.to_unit(unit, material), ..curve })
+        .collect();
+
+    info!("dE/dx against momentum in {} ({} to {} MeV/c)", material.name, pmin, pmax);
+    for curve in &curves {
+        if let Some((momentum, de_dx)) = curve.table.minimum_ionizing() {
+            info!("{}: minimum ionizing {:.4} {} at {:.1} MeV/c", curve.species, de_dx, curve.table.unit, momentum);
+        }
+        curve.table.write(output.path(&format!("pid_{}.txt", curve.species)), &output.text)?;
+    }
+
+    let series: Vec<(&str, &[f64], &[f64])> = curves.iter()
+        .map(|curve| (curve.species.as_str(), curve.table.energies.as_slice(), curve.table.stopping_powers.as_slice()))
+        .collect();
+    plotting::plot::plot_overlay(&series, Axis::Momentum.label(output.lang), &format!("dE/dx ({})", unit), &title, output)?;
+    Ok(())
+}
+
+// Collision vs radiative losses of electrons (or positrons) and the critical energy: radiative
+fn run_radiative(projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let projectile = if electron::is_lepton(projectile) { projectile.clone() } else { Projectile::electron() };
+    output.check_new_files([output.path("electron_losses.txt")].into_iter().chain(output.plot_files(output.lang.labels().losses_title)))?;
+    let corrections = Corrections::all(&material.density_correction_params());
+
+    let grid = EnergyGrid::logarithmic(0.01, 10000.0, 200);
+    let energies = grid.energies();
+    let collision: Vec<f64> = energies.iter().map(|&e| bethe_bloch::bb::stopping_power(e, &projectile, material, &corrections)).collect();
+    let radiative: Vec<f64> = energies.iter().map(|&e| radiative::radiative_stopping_power(e, material)).collect();
+
+    info!("{} in {} (X0 = {} g/cm²)", projectile.name, material.name, material.radiation_length());
+    match radiative::critical_energy(&projectile, material, &corrections) {
+        Some(energy) => info!("Critical energy: {:.2} MeV", energy),
+        None => info!("Critical energy outside 1 MeV to 100 GeV"),
+    }
+
+    error::write_file(output.path("electron_losses.txt"), |file| {
+        let text = &output.text;
+        writeln!(file, "# {}", text.join(&["E (MeV)", "collision (MeV/cm)", "radiative (MeV/cm)"]))?;
+        for ((&energy, &col), &rad) in energies.iter().zip(&collision).zip(&radiative) {
+            writeln!(file, "{}", text.join(&[text.scientific(energy), text.scientific(col), text.scientific(rad)]))?;
+        }
+        Ok(())
+    })?;
+
+    plotting::plot::plot_losses(&energies, &collision, &radiative, &target(output, &projectile.name, material),
+    output.lang.labels().losses_title, output)?;
+    Ok(())
+}
+
+// Mass stopping-power ratio of the material to another one: ratio [--to <material>] [--emin <MeV>] [--emax <MeV>]
+fn run_ratio(args: &RatioArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (other, emin, emax) = (&args.to, args.emin, args.emax);
+    output.check_new_files([output.path("stopping_power_ratio.txt")].into_iter().chain(output.plot_files(output.lang.labels().ratio_title)))?;
+
+    // The ratio swaps in the Sternheimer coefficients of each material
+    let grid = checked(EnergyGrid::logarithmic(emin, emax, 200))?;
+    let corrections = Corrections::all(&DensityCorrectionParams::default());
+    let ratio = ratio::stopping_power_ratio(&grid, projectile, material, other, &corrections);
+
+    info!("Mass stopping-power ratio {}/{} for {} ({} to {} MeV)", material.name, other.name, projectile.name, emin, emax);
+    for (energy, value) in ratio.iter().step_by(20) {
+        info!("{:.2} MeV: {:.4}", energy, value);
+    }
+    ratio.write(output.path("stopping_power_ratio.txt"), &output.text)?;
+
+    plotting::plot::plot_ratio(&ratio.energies, &ratio.ratios, &format!("{} {}/{}", projectile.name, material.name, other.name),
+    output.lang.labels().ratio_title, output)?;
+    Ok(())
+}
+
+// dE/dx over energy and a scanned parameter as a color map: heatmap [--scan z|thickness] [--emin <MeV>] [--emax <MeV>] [--points <n>]
+// [--thickness <cm>] [--steps <n>]
+fn run_heatmap(args: &HeatmapArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let labels = output.lang.labels();
+    let grid = checked(EnergyGrid::logarithmic(args.emin, args.emax, args.points))?;
+    let corrections = Corrections::all(&material.density_correction_params());
+
+    let title = match args.scan {
+        Scan::Z => fill(labels.elements_title, &[&projectile.name]),
+        Scan::Thickness => fill(labels.thickness_title, &[&projectile.name, &material.name]),
+    };
+    output.check_new_files([output.path(&format!("heatmap_{}.txt", args.scan))].into_iter().chain(output.plot_files(&title)))?;
+
+    let (map, y_label) = match args.scan {
+        Scan::Z => {
+            info!("Mass stopping power of {} in the elements ({} to {} MeV)", projectile.name, args.emin, args.emax);
+            (scan::element_scan(&grid, projectile, &corrections), labels.atomic_number)
+        }
+        Scan::Thickness => {
+            let thickness = args.thickness.unwrap_or_else(|| range::csda_range(args.emax, projectile, material, &corrections).length);
+            let steps = args.steps.max(2);
+            let thicknesses: Vec<f64> = (0..steps).map(|step| thickness * step as f64 / (steps - 1) as f64).collect();
+            info!("dE/dx of {} behind up to {:.4} cm of {} ({} to {} MeV)", projectile.name, thickness, material.name, args.emin, args.emax);
+            (scan::thickness_scan(&grid, projectile, material, &thicknesses, &corrections), labels.thickness)
+        }
+    };
+    map.write(output.path(&format!("heatmap_{}.txt", map.scan)), &output.text)?;
+
+    plotting::plot::plot_heatmap(&map.energies, &map.parameters, &map.stopping_powers, y_label, map.unit(), &title, output)?;
+    Ok(())
+}
+
+// Pencil-beam lateral spread: scatter --energy <MeV> [--sigma0 <cm>] [--step <cm>]
+fn run_scatter(args: &ScatterArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (energy, sigma0, step) = (args.energy, args.sigma0, args.step);
+    output.check_new_files([output.path("lateral_spread.txt")].into_iter().chain(output.plot_files(output.lang.labels().spread_title)))?;
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let spread = scattering::lateral_spread(energy, projectile, material, &corrections, sigma0, step);
+
+    info!("{} MeV {} pencil beam in {} (X0 = {:.3} cm)", energy, projectile.name, material.name, material.radiation_length_cm());
+    info!("Highland angle after 1 cm: {:.4} mrad", 1e3 * scattering::highland_angle(energy, projectile, material, 1.0));
+    if let (Some(depth), Some(sigma)) = (spread.depths.last(), spread.sigmas.last()) {
+        info!("sigma_x at the end of range ({:.3} cm): {:.4} cm", depth, sigma);
+    }
+    spread.write(output.path("lateral_spread.txt"), &output.text)?;
+
+    plotting::plot::plot_spread(&spread.depths, &spread.sigmas, &target(output, &format!("{} {} MeV", projectile.name, energy), material),
+    output.lang.labels().spread_title, output)?;
+    Ok(())
+}
+
+// Birks-quenched scintillation light: scintillation [--kb <cm/MeV>] [--yield <photons/MeV>] [--energy <MeV>]
+fn run_scintillation(args: &ScintillationArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let defaults = Scintillator::default();
+    let scintillator = Scintillator::new(args.light_yield.unwrap_or(defaults.light_yield), args.kb.unwrap_or(defaults.birks));
+    output.check_new_files([output.path("scintillation.txt")].into_iter().chain(output.plot_files(output.lang.labels().scintillation_title)))?;
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let grid = EnergyGrid::logarithmic(range::cutoff(projectile), 10000.0, 200);
+    let response = scintillation::quenched_response(&grid, projectile, material, &corrections, &scintillator);
+
+    info!("{} in {} (S = {} photons/MeV, kB = {} cm/MeV)", projectile.name, material.name, scintillator.light_yield, scintillator.birks);
+    if let Some(energy) = args.energy {
+        let light = scintillation::light_output(energy, projectile, material, &corrections, &scintillator);
+        info!("Light from a stopping {} MeV {}: {:.0} photons ({:.1}% of the unquenched yield)", energy, projectile.name, light,
+            100.0 * light / (scintillator.light_yield * energy));
+    }
+
+    error::write_file(output.path("scintillation.txt"), |file| {
+        let text = &output.text;
+        writeln!(file, "# {}", text.join(&["E (MeV)", "dE/dx (MeV/cm)", "dL/dx (photons/cm)"]))?;
+        for (energy, de_dx, light) in response.iter() {
+            writeln!(file, "{}", text.join(&[text.scientific(energy), text.scientific(de_dx), text.scientific(light)]))?;
+        }
+        Ok(())
+    })?;
+
+    let unquenched: Vec<f64> = response.stopping_powers.iter().map(|de_dx| scintillator.light_yield * de_dx).collect();
+    plotting::plot::plot_light(&response.energies, &unquenched, &response.light, &target(output, &projectile.name, material),
+    output.lang.labels().scintillation_title, output)?;
+    Ok(())
+}
+
+// Sensitivity of dE/dx to the Sternheimer coefficients: sensitivity [--percent <p>] [--emin <MeV>] [--emax <MeV>]
+fn run_sensitivity(args: &SensitivityArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (percent, emin, emax) = (args.percent, args.emin, args.emax);
+    output.check_new_files([output.path("sensitivity.txt")].into_iter().chain(output.plot_files(output.lang.labels().sensitivity_title)))?;
+
+    let grid = checked(EnergyGrid::logarithmic(emin, emax, 100))?;
+    let corrections = Corrections::all(&material.density_correction_params());
+    let mut sensitivities = sensitivity::density_sensitivity(&grid, projectile, material, &corrections, percent / 100.0);
+
+    info!("Change in dE/dx of {} in {} for ±{}% of each density-effect coefficient ({} to {} MeV)", projectile.name, material.name, percent, emin, emax);
+    for sensitivity in &sensitivities {
+        let (minus, plus) = sensitivity.extreme();
+        info!("{}: {:+.4}% / {:+.4}%", sensitivity.parameter, 100.0 * minus, 100.0 * plus);
+    }
+    sensitivity::write_sensitivities(&sensitivities, output.path("sensitivity.txt"), &output.text)?;
+
+    // Largest effect first, as in a tornado chart
+    sensitivities.sort_by(|a, b| b.magnitude().total_cmp(&a.magnitude()));
+    let labels: Vec<&str> = sensitivities.iter().map(|s| s.parameter).collect();
+    let (minus, plus): (Vec<f64>, Vec<f64>) = sensitivities.iter().map(|s| {
+        let (minus, plus) = s.extreme();
+        (100.0 * minus, 100.0 * plus)
+    }).unzip();
+    plotting::plot::plot_tornado(&labels, &minus, &plus, output.lang.labels().sensitivity_title, output)?;
+    Ok(())
+}
+
+// Spread-out Bragg peak: sobp --from <cm> --to <cm> [--spacing <cm>] [--step <cm>]
+fn run_sobp(args: &SobpArgs, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let (proximal, distal, spacing, step) = (args.from, args.to, args.spacing, args.step);
+    if distal <= proximal {
+        return Err(Error::Invalid(String::from("The distal depth --to must be beyond the proximal depth --from")));
+    }
+    output.check_new_files([output.path("sobp_curve.txt")].into_iter().chain(output.plot_files(output.lang.labels().sobp_title)))?;
+
+    let corrections = Corrections::all(&material.density_correction_params());
+    let sobp = sobp::sobp(proximal, distal, spacing, step, projectile, material, &corrections);
+
+    info!("SOBP from {} to {} cm of {} ({} layers)", proximal, distal, material.name, sobp.energies.len());
+    for (energy, weight) in sobp.energies.iter().zip(&sobp.weights) {
+        info!("{:.3} MeV: weight {:.4}", energy, weight);
+    }
+    // The deepest layer ends at the distal depth, so leave its falloff out of the plateau
+    info!("Flatness: {:.2}%", 100.0 * sobp.flatness(proximal, distal - spacing));
+    sobp.curve.write(output.path("sobp_curve.txt"), &output.text)?;
+
+    plotting::plot::plot_bragg(&sobp.curve.depths, &sobp.curve.doses, &target(output, &projectile.name, material),
+    output.lang.labels().sobp_title, None, output)?;
+    Ok(())
+}
+
+// Transport through a stack of layers: stack --layer <material>:<cm> [--layer ...] --energy <MeV> [--step <cm>]
+fn run_stack(args: &StackArgs, projectile: &Projectile, output: &OutputConfig) -> Result<()> {
+    let (energy, step) = (args.energy, args.step);
+    let stack = args.layers.iter().fold(Stack::new(), |stack, layer| stack.layer(layer.material.clone(), layer.thickness));
+    output.check_new_files([output.path("stack_curve.txt")].into_iter().chain(output.plot_files(output.lang.labels().stack_title)))?;
+
+    // The stack swaps in the Sternheimer coefficients of each layer
+    let transport = stack.transport(energy, projectile, &Corrections::all(&DensityCorrectionParams::default()), step);
+
+    info!("{} MeV {} through {:.4} cm", energy, projectile.name, stack.thickness());
+    for (layer, deposited) in stack.layers.iter().zip(&transport.deposited) {
+        info!("{} ({} cm): {:.4} MeV deposited", layer.material.name, layer.thickness, deposited);
+    }
+    match transport.outcome {
+        SlabOutcome::Exits(exit_energy) => info!("Exit energy: {:.4} MeV", exit_energy),
+        SlabOutcome::Stops(depth) => info!("The {} stops at {:.4} cm", projectile.name, depth),
+    }
+    transport.curve.write(output.path("stack_curve.txt"), &output.text)?;
+
+    plotting::plot::plot_bragg(&transport.curve.depths, &transport.curve.doses, &format!("{} {} MeV", projectile.name, energy),
+    output.lang.labels().stack_title, transport.curve.peak(), output)?;
+    Ok(())
+}
+
+// ΔE-E telescope scan for several species:
+// telescope [--delta <material>:<cm>] [--stop <material>:<cm>] [--species <name>,<name>,...] [--emin <MeV>] [--emax <MeV>]
+fn run_telescope(args: &TelescopeArgs, output: &OutputConfig) -> Result<()> {
+    let (delta, stop) = (args.delta.clone(), args.stop.clone());
+    let telescope = Telescope::new(delta.material, delta.thickness, stop.material, stop.thickness);
+    let (projectiles, emin, emax) = (&args.species, args.emin, args.emax);
+    output.check_new_files([output.path("telescope.txt")].into_iter().chain(output.plot_files(output.lang.labels().telescope_title)))?;
+
+    let grid = checked(EnergyGrid::logarithmic(emin, emax, 200))?;
+    let corrections = Corrections::all(&DensityCorrectionParams::default());
+    let loci: Vec<_> = projectiles.iter().map(|projectile| telescope.locus(&grid, projectile, &corrections)).collect();
+
+    info!("ΔE-E telescope: {} cm of {} + {} cm of {}", telescope.delta.thickness, telescope.delta.material.name,
+        telescope.stop.thickness, telescope.stop.material.name);
+    for locus in &loci {
+        match locus.energies.first() {
+            Some(threshold) => info!("{}: reaches the E detector above {:.3} MeV", locus.species, threshold),
+            None => info!("{}: stops in the ΔE detector over the whole scan", locus.species),
+        }
+    }
+    telescope::write_loci(&loci, output.path("telescope.txt"), &output.text)?;
+
+    let series: Vec<(&str, &[f64], &[f64])> = loci.iter()
+        .map(|locus| (locus.species.as_str(), locus.residual_e.as_slice(), locus.delta_e.as_slice()))
+        .collect();
+    plotting::plot::plot_telescope(&series, output.lang.labels().telescope_title, output)?;
+    Ok(())
+}
+
+// Helper function to compute the Bragg curve and LET profile at `energy`, RBE-weighted and
+// attenuated as requested, and to write and plot them.
+fn bragg_curve(energy: f64, options: &BraggOptions, corrections: &Corrections, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    // Optional RBE weighting with the photon (α/β) in Gy and the physical dose (Gy) at the Bragg peak
+    let rbe_model = match (options.rbe, options.alpha_beta) {
+        (Some(RbeModel::McNamara { .. }), Some(alpha_beta)) => Some(RbeModel::McNamara { alpha_beta }),
+        (model, _) => model,
+    };
+    let peak_dose = options.dose;
+
+    // Every table and plot is checked before the first is written
+    let plots = plots_of(output, projectile, material, corrections);
+    let labels = output.lang.labels();
+    let mut files = vec![("bragg_curve", labels.bragg_title), ("let_profile", labels.let_title)];
+    files.extend(rbe_model.map(|_| ("rbe_dose", labels.rbe_title)));
+    output.check_new_files(files.into_iter().flat_map(|(stem, title)| output.table_files(stem).into_iter().chain(plots.plot_files(title))))?;
+
+    // Step through the target in 1000 steps of the CSDA range
+    let csda = range::csda_range(energy, projectile, material, corrections);
+    let step = csda.length / 1000.0;
+    let mut curve = bethe_bloch::bragg::bragg(energy, projectile, material, corrections, step);
+
+    // Attenuate the primary fluence by nuclear interactions, optionally with a nonelastic
+    // cross section (barn) instead of the nuclear interaction length
+    if options.nuclear || options.cross_section.is_some() {
+        let coefficient = bethe_bloch::bragg::attenuation_coefficient(material, options.cross_section);
+        info!("Nuclear attenuation: {:.4} per cm", coefficient);
+        curve = curve.attenuated(coefficient);
+    }
+
+    info!("Bragg curve for {} MeV", energy);
+    info!("CSDA range: {:.4} cm ({:.4} g/cm²), straggling sigma: {:.4} cm", csda.length, csda.mass_thickness, csda.straggling);
+    if let Some((depth, dose)) = curve.peak() {
+        info!("Bragg peak at {:.3} cm: {} MeV/cm", depth, dose);
+    }
+    let mut metadata = metadata(projectile, material, corrections);
+    metadata.push(("energy_mev", json!(energy)));
+    curve.write_with(output.table_path("bragg_curve"), output.format, &output.text, &metadata)?;
+    output.record("bragg_curve", &metadata, || curve.columns())?;
+
+    plotting::plot::plot_bragg(&curve.depths, &curve.doses, &target(output, &format!("{} {} MeV", projectile.name, energy), material),
+    output.lang.labels().bragg_title, curve.peak(), &plots)?;
+
+    // Track- and dose-averaged LET of the primaries, plotted in keV/µm
+    let profile = bethe_bloch::bragg::let_profile(energy, projectile, material, corrections, step);
+    if let (Some(track), Some(dose)) = (profile.track.first(), profile.dose.first()) {
+        info!("Entrance LET: {:.4} keV/um (track), {:.4} keV/um (dose)", 0.1 * track, 0.1 * dose);
+    }
+    profile.write_with(output.table_path("let_profile"), output.format, &output.text, &metadata)?;
+    output.record("let_profile", &metadata, || profile.columns())?;
+
+    let to_kev_per_micron = |values: &[f64]| values.iter().map(|v| Unit::KevPerMicron.from_mev_per_cm(*v, material)).collect::<Vec<f64>>();
+    plotting::plot::plot_let(&profile.depths, &to_kev_per_micron(&profile.track), &to_kev_per_micron(&profile.dose),
+    &target(output, &format!("{} {} MeV", projectile.name, energy), material), output.lang.labels().let_title, &plots)?;
+
+    if let Some(model) = rbe_model {
+        let weighted = rbe::rbe_weighted_dose(&curve, &profile, &model, peak_dose);
+        if let Some(peak) = weighted.weighted.iter().copied().reduce(f64::max) {
+            info!("RBE-weighted peak dose: {:.4} Gy(RBE) for {} Gy physical ({:?})", peak, peak_dose, model);
+        }
+        weighted.write_with(output.table_path("rbe_dose"), output.format, &output.text, &metadata)?;
+        output.record("rbe_dose", &metadata, || weighted.columns())?;
+
+        plotting::plot::plot_rbe(&weighted.depths, &weighted.doses, &weighted.weighted,
+        &target(output, &format!("{} {} MeV", projectile.name, energy), material), output.lang.labels().rbe_title, &plots)?;
+    }
+    Ok(())
+}
+
+// Helper function to take the delta correction parameters from the flags given, with the
+// defaults tabulated for the material (or derived from it) for the others, offering to read
+// them from standard input when the flags do not cover a, x0, x1, C and m in an interactive run,
+// asking in the language of the labels.
+fn density_params(args: &DensityArgs, material: &Material, interactive: bool, labels: &Labels) -> Result<DensityCorrectionParams> {
+    let defaults = material.density_correction_params();
+    let mut params = DensityCorrectionParams {
+        a: args.a.unwrap_or(defaults.a),
+        x0: args.x0.unwrap_or(defaults.x0),
+        x1: args.x1.unwrap_or(defaults.x1),
+        c: args.c.unwrap_or(defaults.c),
+        m: args.m.unwrap_or(defaults.m),
+        delta0: args.delta0.unwrap_or(defaults.delta0),
+    };
+
+    if interactive && !args.is_complete() {
+        println!("{}", fill(labels.incomplete_density, &[&material.name]));
+        println!("{}", labels.ask_density);
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer).map_err(Error::Input)?;
+        let answer = answer.trim();
+        if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case(labels.yes) {
+            params.a = prompt(labels, "a", params.a)?;
+            params.x0 = prompt(labels, "x0", params.x0)?;
+            params.x1 = prompt(labels, "x1", params.x1)?;
+            params.c = prompt(labels, "C", params.c)?;
+            params.m = prompt(labels, "m", params.m)?;
+            params.delta0 = prompt(labels, "delta0", params.delta0)?;
+        } else {
+            info!("{}", labels.default_density);
+        }
+    }
+    Ok(params)
+}
+
+// Helper function to refuse a grid given by the options before anything is computed on it.
+fn checked(grid: EnergyGrid) -> Result<EnergyGrid> {
+    grid.check().map(|_| grid).map_err(|message| Error::Invalid(capitalized(&message)))
+}
+
+// Helper function to print and write a table and its band.
+fn report_variant(variant: &Variant, table: &StoppingPowerTable, band: Option<&StoppingPowerBand>, metadata: &Metadata, output: &OutputConfig) -> Result<()> {
+    report(table, &variant.heading, output, &variant.file, metadata)?;
+    if let Some(band) = band {
+        let file = format!("{}_band", variant.file);
+        band.write_with(output.table_path(&file), output.format, &output.text, metadata)?;
+        output.record(&file, metadata, || band.columns())?;
+    }
+    Ok(())
+}
+
+// Helper function to plot a table with the caption and title of its variant.
+fn plot_variant(variant: &Variant, table: &StoppingPowerTable, band: Option<&StoppingPowerBand>, projectile: &Projectile, material: &Material, output: &OutputConfig) -> Result<()> {
+    let output = plots_of(output, projectile, material, &variant.corrections);
+    plot_stopping(table, band, &format!("{} (Bethe-Bloch){}", target(&output, &projectile.name, material), variant.caption), &variant.title, &output)
+}
+
+// Helper function to name the target of a curve, e.g. "proton in water", in the language of the
+// plots.
+fn target(output: &OutputConfig, projectile: &str, material: &Material) -> String {
+    fill(output.lang.labels().target, &[projectile, &material.name])
+}
+
+// Helper function to capitalize a label, e.g. a correction naming a curve.
+fn capitalized(label: &str) -> String {
+    let mut chars = label.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+// Helper function to name the plot files of a target and its corrections, e.g. those of a
+// batch run, after them.
+fn plots_of(output: &OutputConfig, projectile: &Projectile, material: &Material, corrections: &Corrections) -> OutputConfig {
+    let names = corrections.names();
+    let corrections = if names.is_empty() { String::from("none") } else { names.join("-") };
+    OutputConfig { naming: PlotNaming { particle: projectile.name.clone(), material: material.name.clone(), corrections, ..output.naming.clone() }, ..output.clone() }
+}
+
+// Helper function to send log records to standard error: errors only with -q, information by
+// default, and debug (-v) or trace (-vv) records too. RUST_LOG overrides the level.
+fn init_logging(quiet: bool, verbose: u8) {
+    let level = match (quiet, verbose) {
+        (true, _) => LevelFilter::Error,
+        (false, 0) => LevelFilter::Info,
+        (false, 1) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .format(|buf, record| match record.level() {
+            Level::Info => writeln!(buf, "{}", record.args()),
+            level => writeln!(buf, "{}: {}", level.as_str().to_ascii_lowercase(), record.args()),
+        })
+        .init();
+}
+
+// Helper function to create a progress bar of `len` steps on standard error. It is hidden
+// with -q, and indicatif draws nothing when standard error is not a terminal.
+fn progress_bar(len: u64, message: &'static str) -> ProgressBar {
+    if !log::log_enabled!(Level::Info) {
+        return ProgressBar::hidden();
+    }
+    let style = ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len} ({eta})").unwrap_or_else(|_| ProgressStyle::default_bar());
+    ProgressBar::new(len).with_style(style.progress_chars("=> ")).with_message(message)
+}
+
+// Helper function to plot a table, shading its uncertainty band when there is one.
+fn plot_stopping(table: &StoppingPowerTable, band: Option<&StoppingPowerBand>, caption: &str, title: &str, output: &OutputConfig) -> Result<()> {
+    let labels = plotting::plot::StoppingLabels { x_label: table.axis.label(output.lang), unit: table.unit.symbol() };
+    match band {
+        Some(band) => plotting::plot::plot_band(&table.energies, &table.stopping_powers,
+            (&band.lower.stopping_powers, &band.upper.stopping_powers), caption, title, labels, output),
+        None => plotting::plot::plot(&table.energies, &table.stopping_powers, caption, title, labels,
+            table.minimum_ionizing(), output),
+    }
+}
+
+// Helper function to print a computed table and write it to `file` in the output format, below
+// `metadata` in a CSV file, to the database of --store and as the table of --export. The points go to standard output
+// with --dump-stdout and are logged at the debug level otherwise.
+fn report(table: &StoppingPowerTable, heading: &str, output: &OutputConfig, file: &str, metadata: &Metadata) -> Result<()> {
+    info!("{}", heading);
+    if output.stdout {
+        // Standard output is line buffered, which would write the points one by one
+        let mut stdout = io::BufWriter::new(io::stdout().lock());
+        for (energy, de_dx) in table.iter() {
+            writeln!(stdout, "{} (dE/dx): {} {}", abscissa(energy, table.axis), de_dx, table.unit).map_err(Error::Output)?;
+        }
+        stdout.flush().map_err(Error::Output)?;
+    } else {
+        for (energy, de_dx) in table.iter() {
+            debug!("{} (dE/dx): {} {}", abscissa(energy, table.axis), de_dx, table.unit);
+        }
+    }
+    if let Some((energy, de_dx)) = table.minimum_ionizing() {
+        info!("Minimum ionizing: {} {} at {}", de_dx, table.unit, abscissa(energy, table.axis));
+    }
+    table.write_with(output.table_path(file), output.format, &output.text, metadata)?;
+    output.record(file, metadata, || table.columns())
+}
+
+// Helper function to format a point of the table axis with its unit.
+fn abscissa(value: f64, axis: Axis) -> String {
+    match axis {
+        Axis::KineticEnergy => format!("{:.1} MeV", value),
+        Axis::Momentum => format!("{:.1} MeV/c", value),
+        Axis::BetaGamma => format!("βγ = {:.4}", value),
+    }
 }
 
-// Helper function to prompt the user for a value with a default.
-fn prompt(message: &str, default: f64) -> f64 {
-    println!("{} (default {}): ", message, default);
+// Helper function to prompt the user for the value of a parameter with a default.
+fn prompt(labels: &Labels, name: &str, default: f64) -> Result<f64> {
+    println!("{}", fill(labels.enter_value, &[name, &default.to_string()]));
     let mut input = String::new();
-    io::stdin().read_line(&mut input).expect("Failed to read line");
+    io::stdin().read_line(&mut input).map_err(Error::Input)?;
 
     let trimmed = input.trim();          
     if trimmed.is_empty() {
-       default
+       Ok(default)
     } else {
-        trimmed.parse().unwrap_or(default)
+        Ok(trimmed.parse().unwrap_or(default))
     }
 }
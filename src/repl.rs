@@ -0,0 +1,142 @@
+// This module runs the session of the repl subcommand: commands such as `set material
+// aluminium`, `dedx 150` or `range 200` are read from standard input, one per line, and
+// answered on standard output with the projectile, target and options set so far
+use std::io::{self, BufRead, Write};
+
+use log::error;
+use system_rs::bethe_bloch::{bb, materials, projectile, range, Corrections, Material, Projectile, ShellCorrectionModel, Unit};
+use system_rs::{Error, Result};
+
+const HELP: &str = "\
+set material <name>      target material
+set particle <name>      projectile
+set unit <unit>          unit of dedx: MeV/cm, keV/um or MeV cm2/g
+set corrections <list>   comma separated corrections (density, shell, shell-table, barkas, bloch or none)
+set radiative <on|off>   radiative losses of muons and electrons
+show                     current settings
+dedx <MeV>               stopping power at a kinetic energy
+range <MeV>              CSDA range and straggling at a kinetic energy
+help                     this list
+quit                     end the session";
+
+// Projectile, target and options the commands are answered with
+pub struct Session {
+    projectile: Projectile,
+    material: Material,
+    corrections: Corrections,
+    unit: Unit,
+}
+
+impl Session {
+    // Start with the density and shell corrections of the material, in MeV/cm
+    pub fn new(projectile: Projectile, material: Material) -> Self {
+        let corrections = Corrections::all(&material.density_correction_params());
+        Session { projectile, material, corrections, unit: Unit::default() }
+    }
+
+    // Answer the words of a command line, or explain why it cannot be
+    fn execute(&mut self, words: &[&str]) -> std::result::Result<String, String> {
+        match words {
+            ["help"] => Ok(String::from(HELP)),
+            ["show"] => Ok(self.settings()),
+            ["set", option, value @ ..] if !value.is_empty() => {
+                self.set(option, &value.join(" "))?;
+                Ok(self.settings())
+            }
+            ["dedx", energy] => {
+                let energy = parse_energy(energy)?;
+                let de_dx = bb::stopping_power(energy, &self.projectile, &self.material, &self.corrections);
+                Ok(format!("{:.4} {}", self.unit.from_mev_per_cm(de_dx, &self.material), self.unit))
+            }
+            ["range", energy] => {
+                let energy = parse_energy(energy)?;
+                let cutoff = range::cutoff(&self.projectile);
+                if energy <= cutoff {
+                    return Err(format!("the CSDA range is integrated from {} MeV", cutoff));
+                }
+                let csda = range::csda_range(energy, &self.projectile, &self.material, &self.corrections);
+                Ok(format!("{:.4} cm ({:.4} g/cm²), straggling sigma {:.4} cm", csda.length, csda.mass_thickness, csda.straggling))
+            }
+            _ => Err(format!("unknown command '{}', try help", words.join(" "))),
+        }
+    }
+
+    fn set(&mut self, option: &str, value: &str) -> std::result::Result<(), String> {
+        match option {
+            "material" => {
+                let material = materials::by_name(value).ok_or_else(|| format!("unknown material (available: {})", materials::NAMES.join(", ")))?;
+                check_shell_table(&self.corrections, &material)?;
+                self.corrections.density_params = material.density_correction_params();
+                self.material = material;
+            }
+            "particle" => {
+                self.projectile = projectile::by_name(value).ok_or_else(|| format!("unknown particle (available: {})", projectile::NAMES.join(", ")))?;
+            }
+            "unit" => self.unit = value.parse()?,
+            "corrections" => {
+                let selection: Corrections = value.parse()?;
+                check_shell_table(&selection, &self.material)?;
+                self.corrections = Corrections { density_params: self.corrections.density_params, radiative: self.corrections.radiative, ..selection };
+            }
+            "radiative" => {
+                self.corrections.radiative = match value {
+                    "on" | "true" | "yes" => true,
+                    "off" | "false" | "no" => false,
+                    _ => return Err(String::from("expected on or off")),
+                };
+            }
+            _ => return Err(format!("unknown setting '{}' (material, particle, unit, corrections or radiative)", option)),
+        }
+        Ok(())
+    }
+
+    fn settings(&self) -> String {
+        let names = self.corrections.names();
+        let corrections = if names.is_empty() { String::from("none") } else { names.join(",") };
+        format!("{} in {}, corrections: {}, radiative: {}, unit: {}", self.projectile.name, self.material.name, corrections,
+            if self.corrections.radiative { "on" } else { "off" }, self.unit)
+    }
+}
+
+// Read and answer commands until quit or the end of the input. The prompt is only written
+// when a person is typing; mistakes are reported and the session goes on.
+pub fn run(mut session: Session, prompt: bool) -> Result<()> {
+    let mut stdout = io::stdout();
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        if prompt {
+            write!(stdout, "> ").and_then(|_| stdout.flush()).map_err(Error::Output)?;
+        }
+        let Some(line) = lines.next() else { break };
+        let line = line.map_err(Error::Input)?;
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.first() {
+            None => continue,
+            Some(word) if word.starts_with('#') => continue,
+            Some(&("quit" | "exit")) => break,
+            Some(_) => {}
+        }
+        match session.execute(&words) {
+            Ok(answer) => writeln!(stdout, "{}", answer).map_err(Error::Output)?,
+            Err(message) => error!("{}", message),
+        }
+    }
+    Ok(())
+}
+
+// The shell-table correction only applies to a material with shell corrections, which only
+// the material of --shell-table has
+fn check_shell_table(corrections: &Corrections, material: &Material) -> std::result::Result<(), String> {
+    if corrections.shell && corrections.shell_model == ShellCorrectionModel::Tabulated && material.shell_corrections.is_none() {
+        return Err(format!("no shell corrections of {}, start the session with --shell-table", material.name));
+    }
+    Ok(())
+}
+
+fn parse_energy(value: &str) -> std::result::Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(energy) if energy > 0.0 && energy.is_finite() => Ok(energy),
+        _ => Err(format!("'{}' is not a positive energy (MeV)", value)),
+    }
+}
@@ -0,0 +1,58 @@
+// Bragg curves in liquid water: the peak sits at the end of the CSDA range and the curve
+// deposits the whole kinetic energy
+use system_rs::bethe_bloch::bragg::{bragg, let_profile};
+use system_rs::bethe_bloch::{materials, range, Corrections, Projectile, Stack};
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn proton_peak_at_the_csda_range() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+
+    for energy in [50.0, 150.0, 250.0] {
+        let csda = range::csda_range(energy, &proton, &water, &corrections()).length;
+        let step = csda / 1000.0;
+        let curve = bragg(energy, &proton, &water, &corrections(), step);
+        let (depth, _) = curve.peak().unwrap();
+        assert!((depth - csda).abs() < 0.01 * csda, "{} MeV: peak at {} cm, CSDA range {} cm", energy, depth, csda);
+
+        let deposited: f64 = curve.doses.iter().map(|dose| dose * step).sum();
+        assert!((deposited - energy).abs() < 1e-6 * energy, "{} MeV: {} MeV deposited", energy, deposited);
+    }
+}
+
+#[test]
+fn alpha_peak_within_the_residual_range() {
+    let alpha = Projectile::alpha();
+    let water = materials::water();
+
+    let csda = range::csda_range(20.0, &alpha, &water, &corrections()).length;
+    let step = csda / 1000.0;
+    let (depth, dose) = bragg(20.0, &alpha, &water, &corrections(), step).peak().unwrap();
+    let residual = range::residual_range(range::cutoff(&alpha), &alpha, &water, &corrections());
+
+    assert!(depth <= csda && depth >= csda - residual - step, "peak at {} cm, CSDA range {} cm", depth, csda);
+    // Below the 230 keV/µm of the pristine alpha peak
+    assert!(dose < 2300.0, "peak of {} MeV/cm", dose);
+}
+
+#[test]
+#[should_panic]
+fn bragg_refuses_a_zero_step() {
+    bragg(100.0, &Projectile::proton(), &materials::water(), &corrections(), 0.0);
+}
+
+#[test]
+#[should_panic]
+fn let_profile_refuses_a_negative_step() {
+    let_profile(100.0, &Projectile::proton(), &materials::water(), &corrections(), -0.1);
+}
+
+#[test]
+#[should_panic]
+fn stack_refuses_a_zero_step() {
+    Stack::new().layer(materials::water(), 10.0).transport(100.0, &Projectile::proton(), &corrections(), 0.0);
+}
@@ -0,0 +1,24 @@
+// Compounds built by Bragg additivity, and those that cannot be built
+use system_rs::bethe_bloch::{Compound, Material};
+use system_rs::Error;
+
+#[test]
+fn polyethylene_by_weight() {
+    // CH2: 14.37% hydrogen and 85.63% carbon by weight, Z/A = 0.5703 and I = 57.4 eV
+    let polyethylene = Compound::new("polyethylene", 0.94).element("H", 0.1437).unwrap().element("C", 0.8563).unwrap();
+    let material = polyethylene.build().unwrap();
+    assert!((material.z / material.a - 0.5703).abs() < 1e-3, "Z/A = {}", material.z / material.a);
+
+    let measured: Material = polyethylene.mean_excitation_energy(57.4).try_into().unwrap();
+    assert_eq!(measured.mean_excitation_energy, 57.4);
+}
+
+#[test]
+fn compounds_without_weight_are_refused() {
+    assert!(matches!(Compound::new("empty", 1.0).build(), Err(Error::Invalid(_))));
+    let weightless = Compound::new("weightless", 1.0).element("H", 0.0).unwrap().element("O", 0.0).unwrap();
+    assert!(matches!(weightless.build(), Err(Error::Invalid(_))));
+    let negative = Compound::new("negative", 1.0).element("H", -0.1).unwrap().element("O", 1.1).unwrap();
+    assert!(matches!(negative.build(), Err(Error::Invalid(_))));
+    assert!(matches!(Compound::new("unknown", 1.0).element("Xx", 1.0), Err(Error::UnknownElement(_))));
+}
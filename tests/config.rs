@@ -0,0 +1,60 @@
+// Configuration files of a run: their grid goes through the checks of the flags, and the
+// command line takes precedence over them
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("system_rs_config_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// Stopping-power run of the tool with the configuration `config`, in `dir`
+fn run(dir: &Path, config: &str, args: &[&str]) -> Output {
+    std::fs::write(dir.join("run.toml"), config).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_system_rs"))
+        .current_dir(dir)
+        .args(["--config", "run.toml", "--no-plot", "--non-interactive", "--force", "-q"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn invalid_grid_values_are_configuration_errors() {
+    let dir = dir("grid");
+    for (config, message) in [
+        ("[grid]\nemin = -5.0\n", "grid.emin"),
+        ("[grid]\nemax = 0.0\n", "grid.emax"),
+        ("[grid]\npoints = 1\n", "grid.points"),
+        ("[grid]\nemin = 500.0\nemax = 100.0\n", "grid: the lowest energy"),
+        ("[grid]\nspacing = \"cubic\"\n", "grid.spacing"),
+    ] {
+        let output = run(&dir, config, &["stopping"]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success(), "{}", config);
+        assert!(stderr.contains("run.toml") && stderr.contains(message), "{}: {}", config, stderr);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn flags_take_precedence_over_the_configuration() {
+    let dir = dir("merge");
+    let config = "material = \"aluminium\"\ncorrections = [\"density\"]\n[grid]\nemin = 10.0\nemax = 100.0\npoints = 5\n";
+    let table = || std::fs::read_to_string(dir.join("output").join("fstopping_density_corrections.csv")).unwrap();
+    let rows = |table: &str| table.lines().filter(|line| !line.starts_with('#')).count() - 1;
+
+    // The configuration fills in every option left out on the command line
+    assert!(run(&dir, config, &["--format", "csv", "stopping"]).status.success());
+    let from_config = table();
+    assert!(from_config.contains("# material: aluminium") && from_config.contains("# emin_mev: 10.0"), "{}", from_config);
+    assert_eq!(rows(&from_config), 5);
+
+    // Flags replace their own options only
+    assert!(run(&dir, config, &["--format", "csv", "--material", "water", "stopping", "--points", "7"]).status.success());
+    let merged = table();
+    assert!(merged.contains("# material: water") && merged.contains("# emin_mev: 10.0") && merged.contains("# corrections: density"), "{}", merged);
+    assert_eq!(rows(&merged), 7);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,40 @@
+// Collision stopping powers of electrons (Møller) and positrons (Bhabha) in liquid water
+// compared against ESTAR
+use system_rs::bethe_bloch::bb::stopping_power;
+use system_rs::bethe_bloch::electron::{collision_stopping_power, is_lepton};
+use system_rs::bethe_bloch::{materials, Corrections, Projectile};
+
+// Kinetic energy (MeV) and collision stopping power (MeV cm²/g) of electrons in water from NIST ESTAR
+const ESTAR_WATER_COLLISION: [(f64, f64); 4] = [(0.1, 4.115), (1.0, 1.849), (10.0, 1.968), (100.0, 2.197)];
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn electron_collision_stopping_power_matches_estar() {
+    let (electron, water) = (Projectile::electron(), materials::water());
+    for (energy, reference) in ESTAR_WATER_COLLISION {
+        let de_dx = stopping_power(energy, &electron, &water, &corrections()) / water.density;
+        let deviation = (de_dx - reference).abs() / reference;
+        assert!(deviation < 0.01, "{} MeV: {} vs ESTAR {} MeV cm²/g ({:.2}%)", energy, de_dx, reference, deviation * 100.0);
+    }
+}
+
+#[test]
+fn positrons_lose_more_than_electrons_only_when_slow() {
+    let (positron, water) = (Projectile::positron(), materials::water());
+    let ratio = |energy: f64| {
+        stopping_power(energy, &positron, &water, &corrections()) / collision_stopping_power(energy, false, &water, &corrections())
+    };
+    assert!(ratio(0.1) > 1.0, "{}", ratio(0.1));
+    for energy in [1.0, 10.0, 100.0] {
+        assert!(ratio(energy) < 1.0 && ratio(energy) > 0.95, "{} MeV: {}", energy, ratio(energy));
+    }
+}
+
+#[test]
+fn moller_and_bhabha_apply_to_electrons_and_positrons_only() {
+    assert!(is_lepton(&Projectile::electron()) && is_lepton(&Projectile::positron()));
+    assert!(!is_lepton(&Projectile::muon()) && !is_lepton(&Projectile::proton()));
+}
@@ -0,0 +1,23 @@
+// Energy grids refuse the limits their points cannot be computed from
+use system_rs::bethe_bloch::{EnergyGrid, Spacing};
+
+#[test]
+fn valid_grids_pass() {
+    assert!(EnergyGrid::default().check().is_ok());
+    assert!(EnergyGrid::logarithmic(1.0, 1000.0, 2).check().is_ok());
+}
+
+#[test]
+fn reversed_or_empty_ranges_are_refused() {
+    assert!(EnergyGrid::linear(100.0, 10.0, 50).check().is_err());
+    assert!(EnergyGrid::logarithmic(10.0, 10.0, 50).check().is_err());
+    assert!(EnergyGrid::logarithmic(1.0, 10.0, 1).check().is_err());
+}
+
+#[test]
+fn logarithmic_grids_need_positive_ends() {
+    for (min, max) in [(-5.0, 100.0), (0.0, 100.0), (1.0, f64::INFINITY), (f64::NAN, 100.0)] {
+        let grid = EnergyGrid { min, max, points: 10, spacing: Spacing::Logarithmic };
+        assert!(grid.check().is_err(), "{} to {} MeV", min, max);
+    }
+}
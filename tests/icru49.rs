@@ -0,0 +1,55 @@
+// Proton stopping powers in liquid water compared against ICRU 49 (PSTAR)
+use system_rs::bethe_bloch::bb::stopping_power;
+use system_rs::bethe_bloch::{materials, Corrections, Projectile};
+
+// Kinetic energy (MeV) and electronic stopping power (MeV cm²/g)
+const ICRU49_WATER: [(f64, f64); 5] = [
+    (10.0, 45.67),
+    (20.0, 26.07),
+    (50.0, 12.45),
+    (100.0, 7.289),
+    (200.0, 4.492),
+];
+
+fn corrections(barkas: bool, bloch: bool) -> Corrections {
+    Corrections { barkas, bloch, ..Corrections::shell() }
+}
+
+#[test]
+fn bloch_matches_icru49_within_one_percent() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+
+    for (energy, reference) in ICRU49_WATER {
+        let de_dx = stopping_power(energy, &proton, &water, &corrections(true, true)) / water.density;
+        let deviation = (de_dx - reference).abs() / reference;
+        assert!(deviation < 0.01, "{} MeV: {} vs ICRU 49 {} ({:.2}%)", energy, de_dx, reference, deviation * 100.0);
+    }
+}
+
+#[test]
+fn bloch_term_lowers_stopping_power() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+
+    for (energy, _) in ICRU49_WATER {
+        let without = stopping_power(energy, &proton, &water, &corrections(false, false));
+        let with = stopping_power(energy, &proton, &water, &corrections(false, true));
+        assert!(with < without);
+        assert!((without - with) / without < 1e-3);
+    }
+}
+
+#[test]
+fn bloch_selectable_with_barkas() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+
+    let barkas = stopping_power(10.0, &proton, &water, &corrections(true, false));
+    let both = stopping_power(10.0, &proton, &water, &corrections(true, true));
+    let bloch = stopping_power(10.0, &proton, &water, &corrections(false, true));
+    let none = stopping_power(10.0, &proton, &water, &corrections(false, false));
+
+    // The z³ and z⁴ terms are additive inside the stopping number
+    assert!(((both - none) - ((barkas - none) + (bloch - none))).abs() < 1e-9);
+}
@@ -0,0 +1,41 @@
+// Inverse lookups: the kinetic energy of a given CSDA range or stopping power recovers the
+// energy the range or stopping power was computed at
+use system_rs::bethe_bloch::bb::{bethe_bloch, stopping_power};
+use system_rs::bethe_bloch::{materials, range, Corrections, EnergyGrid, Projectile};
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn energy_for_range_inverts_the_csda_range() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let table = range::range_table(&EnergyGrid::logarithmic(1.0, 300.0, 200), &proton, &water, &corrections());
+
+    for energy in [3.0, 10.0, 75.0, 150.0, 250.0] {
+        let csda = range::csda_range(energy, &proton, &water, &corrections()).length;
+        let found = table.energy_for_range(csda).unwrap();
+        assert!((found - energy).abs() < 1e-3 * energy, "{} cm: {} vs {} MeV", csda, found, energy);
+    }
+
+    // Ranges beyond the table, or below the residual range at the cutoff, are not guessed
+    let longest = *table.ranges.last().unwrap();
+    assert_eq!(table.energy_for_range(1.1 * longest), None);
+    assert_eq!(table.energy_for_range(0.5 * table.ranges[0]), None);
+}
+
+#[test]
+fn energy_for_dedx_inverts_the_stopping_power_below_the_minimum() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let table = bethe_bloch(&proton, &water, &EnergyGrid::logarithmic(1.0, 1e5, 400), &corrections());
+
+    for energy in [2.0, 20.0, 200.0, 1000.0] {
+        let de_dx = stopping_power(energy, &proton, &water, &corrections());
+        let found = table.energy_for_dedx(de_dx).unwrap();
+        assert!((found - energy).abs() < 1e-3 * energy, "{} MeV/cm: {} vs {} MeV", de_dx, found, energy);
+    }
+
+    // No energy on the falling branch reaches below the minimum ionization
+    let (_, minimum) = table.minimum_ionizing().unwrap();
+    assert_eq!(table.energy_for_dedx(0.9 * minimum), None);
+}
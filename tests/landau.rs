@@ -0,0 +1,74 @@
+// Energy-loss distributions in thin absorbers: the Landau density and the tabulated Landau,
+// Vavilov and Gaussian distributions are normalised, and the last two keep the mean loss
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use system_rs::bethe_bloch::landau::landau_density;
+use system_rs::bethe_bloch::{materials, EnergyLossDistribution, LossModel, Material, Projectile};
+
+// Trapezoidal integral of the density and of loss × density over the tabulated losses
+fn moments(distribution: &EnergyLossDistribution) -> (f64, f64) {
+    let points: Vec<(f64, f64)> = distribution.table().collect();
+    points.windows(2).fold((0.0, 0.0), |(norm, first), pair| {
+        let ((l0, d0), (l1, d1)) = (pair[0], pair[1]);
+        (norm + 0.5 * (d0 + d1) * (l1 - l0), first + 0.5 * (l0 * d0 + l1 * d1) * (l1 - l0))
+    })
+}
+
+#[test]
+fn landau_density_is_normalised() {
+    // φ(λ) falls as 1/λ², leaving 1/L of the probability beyond λ = L
+    let (step, upper) = (0.02, 100.0);
+    let (mut integral, mut peak) = (0.0, (0.0, 0.0));
+    let mut lambda = -4.0;
+    while lambda < upper {
+        let density = landau_density(lambda);
+        integral += density * step;
+        if density > peak.1 {
+            peak = (lambda, density);
+        }
+        lambda += step;
+    }
+    assert!((integral - (1.0 - 1.0 / upper)).abs() < 2e-3, "∫φ = {}", integral);
+    assert!((peak.0 + 0.2228).abs() < step, "peak at λ = {}", peak.0);
+    assert!((peak.1 - 0.1807).abs() < 1e-3, "φ(peak) = {}", peak.1);
+}
+
+#[test]
+fn distributions_are_normalised_and_chosen_by_kappa() {
+    let (water, silicon) = (materials::water(), materials::by_name("silicon").unwrap());
+    let cases: [(Projectile, f64, &Material, f64, LossModel); 3] = [
+        (Projectile::muon(), 1000.0, &silicon, 0.03, LossModel::Landau),
+        (Projectile::proton(), 100.0, &water, 0.1, LossModel::Vavilov),
+        (Projectile::proton(), 10.0, &water, 0.1, LossModel::Gaussian),
+    ];
+
+    for (projectile, energy, material, thickness, model) in cases {
+        let distribution = EnergyLossDistribution::new(energy, &projectile, material, thickness);
+        assert_eq!(distribution.model, model, "κ = {}", distribution.kappa);
+
+        // The Landau table stops 100 ξ above the peak, leaving out about 1% of its tail
+        let (norm, first) = moments(&distribution);
+        let tolerance = if model == LossModel::Landau { 0.02 } else { 1e-3 };
+        assert!((norm - 1.0).abs() < tolerance, "{:?}: ∫f = {}", model, norm);
+        if model != LossModel::Landau {
+            let mean = first / norm;
+            assert!((mean - distribution.mean).abs() < 1e-3 * distribution.mean, "{:?}: mean {} vs {} MeV", model, mean, distribution.mean);
+        }
+        if model != LossModel::Gaussian {
+            assert!(distribution.most_probable < distribution.mean, "{:?}: peak at {} MeV above the mean", model, distribution.most_probable);
+        }
+    }
+}
+
+#[test]
+fn samples_follow_the_gaussian_limit() {
+    let distribution = EnergyLossDistribution::new(10.0, &Projectile::proton(), &materials::water(), 0.1);
+    let mut rng = StdRng::seed_from_u64(7);
+    let samples: Vec<f64> = (0..20000).map(|_| distribution.sample(&mut rng)).collect();
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|loss| (loss - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    let sigma = distribution.variance.sqrt();
+    assert!((mean - distribution.mean).abs() < 0.05 * sigma, "mean {} vs {} MeV", mean, distribution.mean);
+    assert!((variance.sqrt() / sigma - 1.0).abs() < 0.03, "sigma {} vs {} MeV", variance.sqrt(), sigma);
+}
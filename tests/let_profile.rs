@@ -0,0 +1,40 @@
+// LET profiles of proton beams in water: track- and dose-averaged LET start at the stopping
+// power of the beam, the dose average never falls below the track average and both rise
+// towards the end of the range
+use system_rs::bethe_bloch::bb::stopping_power;
+use system_rs::bethe_bloch::bragg::let_profile;
+use system_rs::bethe_bloch::{materials, range, Corrections, Projectile};
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn entrance_let_is_the_stopping_power_of_the_beam() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let profile = let_profile(150.0, &proton, &water, &corrections(), 0.01);
+    let de_dx = stopping_power(150.0, &proton, &water, &corrections());
+
+    // The beam is monoenergetic at the entrance, so both averages are its dE/dx
+    let (_, track, dose) = profile.iter().next().unwrap();
+    assert!((track - de_dx).abs() < 1e-9 * de_dx && (dose - de_dx).abs() < 1e-9 * de_dx, "{} and {} vs {} MeV/cm", track, dose, de_dx);
+}
+
+#[test]
+fn dose_average_exceeds_the_track_average() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let step = 0.01;
+    let profile = let_profile(100.0, &proton, &water, &corrections(), step);
+    assert!(profile.iter().all(|(_, track, dose)| dose >= track * (1.0 - 1e-12)));
+
+    // Both rise with depth over the plateau, and the spread of the spectrum separates them
+    let plateau = profile.len() * 9 / 10;
+    assert!(profile.track[..plateau].windows(2).all(|pair| pair[1] > pair[0]));
+    assert!(profile.dose[..plateau].windows(2).all(|pair| pair[1] > pair[0]));
+    assert!(profile.dose[plateau] > profile.track[plateau] * 1.001);
+
+    // The profile follows the mean energy down to the cutoff
+    let end = range::csda_range_from(range::cutoff(&proton), 100.0, &proton, &water, &corrections()).length;
+    let last = *profile.depths.last().unwrap();
+    assert!((last - end).abs() < 3.0 * step, "last point at {} cm, mean range {} cm", last, end);
+}
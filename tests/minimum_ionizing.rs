@@ -0,0 +1,50 @@
+// Minimum ionizing points found on stopping-power tables, compared against the PDG tables of
+// atomic and nuclear properties of materials
+use system_rs::bethe_bloch::bb::bethe_bloch;
+use system_rs::bethe_bloch::kinematics::beta_gamma;
+use system_rs::bethe_bloch::{materials, Corrections, EnergyGrid, Material, Projectile, StoppingPowerTable};
+
+// Minimum mass stopping power (MeV cm²/g) from the PDG tables
+const PDG_MINIMUM_IONIZATION: [(&str, f64); 6] =
+    [("water", 1.992), ("air", 1.815), ("aluminium", 1.615), ("silicon", 1.664), ("copper", 1.403), ("lead", 1.122)];
+
+fn table(projectile: &Projectile, material: &Material, grid: &EnergyGrid) -> StoppingPowerTable {
+    bethe_bloch(projectile, material, grid, &Corrections::all(&material.density_correction_params()))
+}
+
+#[test]
+fn minimum_matches_the_pdg_tables() {
+    let muon = Projectile::muon();
+    let grid = EnergyGrid::logarithmic(10.0, 1e5, 400);
+
+    for (name, reference) in PDG_MINIMUM_IONIZATION {
+        let material = materials::by_name(name).unwrap();
+        let (energy, minimum) = table(&muon, &material, &grid).minimum_ionizing().unwrap();
+        let minimum = minimum / material.density;
+        let deviation = (minimum - reference).abs() / reference;
+        assert!(deviation < 0.005, "{}: {} vs PDG {} MeV cm²/g ({:.2}%)", name, minimum, reference, deviation * 100.0);
+
+        // The minimum lies at βγ ≈ 3 to 4, lower in heavier materials
+        let bg = beta_gamma(energy, muon.mass);
+        assert!((2.8..4.0).contains(&bg), "{}: minimum at βγ = {}", name, bg);
+    }
+}
+
+#[test]
+fn minimum_is_at_the_same_velocity_for_every_mass() {
+    let water = materials::water();
+    let (muon, proton) = (Projectile::muon(), Projectile::proton());
+    let (muon_energy, muon_minimum) = table(&muon, &water, &EnergyGrid::logarithmic(10.0, 1e5, 400)).minimum_ionizing().unwrap();
+    let (proton_energy, proton_minimum) = table(&proton, &water, &EnergyGrid::logarithmic(100.0, 1e6, 400)).minimum_ionizing().unwrap();
+
+    let (muon_bg, proton_bg) = (beta_gamma(muon_energy, muon.mass), beta_gamma(proton_energy, proton.mass));
+    assert!((muon_bg / proton_bg - 1.0).abs() < 1e-3, "βγ {} for the muon, {} for the proton", muon_bg, proton_bg);
+    assert!((muon_minimum / proton_minimum - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn no_minimum_at_an_end_of_the_table() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    assert_eq!(table(&proton, &water, &EnergyGrid::logarithmic(1.0, 100.0, 50)).minimum_ionizing(), None);
+    assert_eq!(table(&proton, &water, &EnergyGrid::logarithmic(1e5, 1e6, 50)).minimum_ionizing(), None);
+}
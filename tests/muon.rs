@@ -0,0 +1,57 @@
+// Muon energy losses at high energy: the radiative losses are negligible at a GeV and
+// overtake ionization at the muon critical energies of the PDG tables
+use system_rs::bethe_bloch::bb::{radiative_stopping_power, stopping_power};
+use system_rs::bethe_bloch::muon::{bremsstrahlung_b, pair_production_b, photonuclear_b};
+use system_rs::bethe_bloch::{materials, Corrections, Material, Projectile};
+
+// Muon critical energy (GeV), where ionization and radiative losses are equal, from the PDG tables
+const PDG_MUON_CRITICAL_ENERGY: [(&str, f64); 3] = [("water", 1031.0), ("aluminium", 612.0), ("lead", 141.0)];
+
+fn critical_energy(muon: &Projectile, material: &Material) -> f64 {
+    let corrections = Corrections::all(&material.density_correction_params());
+    let (mut low, mut high) = (1e4f64, 1e8f64);
+    for _ in 0..60 {
+        let middle = (low * high).sqrt();
+        if radiative_stopping_power(middle, muon, material) < stopping_power(middle, muon, material, &corrections) {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low * high).sqrt() / 1e3
+}
+
+#[test]
+fn critical_energy_matches_the_pdg_tables() {
+    let muon = Projectile::muon();
+    for (name, reference) in PDG_MUON_CRITICAL_ENERGY {
+        let critical = critical_energy(&muon, &materials::by_name(name).unwrap());
+        let deviation = (critical - reference).abs() / reference;
+        assert!(deviation < 0.15, "{}: {} vs PDG {} GeV ({:.1}%)", name, critical, reference, deviation * 100.0);
+    }
+}
+
+#[test]
+fn radiative_losses_grow_with_the_energy() {
+    let (muon, water) = (Projectile::muon(), materials::water());
+    let corrections = Corrections::all(&water.density_correction_params());
+    let fraction = |energy: f64| radiative_stopping_power(energy, &muon, &water) / stopping_power(energy, &muon, &water, &corrections);
+
+    assert!(fraction(1e3) < 1e-3, "{} at 1 GeV", fraction(1e3));
+    assert!(fraction(1e3) < fraction(1e4) && fraction(1e4) < fraction(1e5) && fraction(1e5) < fraction(1e6));
+
+    // --radiative adds them to the ionization losses
+    let total = stopping_power(1e5, &muon, &water, &Corrections { radiative: true, ..corrections });
+    assert!((total / stopping_power(1e5, &muon, &water, &corrections) - 1.0 - fraction(1e5)).abs() < 1e-12);
+}
+
+#[test]
+fn pair_production_leads_at_a_tev() {
+    let (muon, water) = (Projectile::muon(), materials::water());
+    let (bremsstrahlung, pairs, photonuclear) = (bremsstrahlung_b(1e6, &muon, &water), pair_production_b(1e6, &muon, &water), photonuclear_b(1e6, &muon, &water));
+    assert!(pairs > bremsstrahlung && bremsstrahlung > photonuclear, "b = {:e}, {:e}, {:e} cm²/g", bremsstrahlung, pairs, photonuclear);
+
+    // b(E) changes slowly: the radiative losses are nearly proportional to the energy
+    let total = |energy: f64| bremsstrahlung_b(energy, &muon, &water) + pair_production_b(energy, &muon, &water) + photonuclear_b(energy, &muon, &water);
+    assert!((1.0..1.6).contains(&(total(1e6) / total(1e5))), "b(1 TeV)/b(100 GeV) = {}", total(1e6) / total(1e5));
+}
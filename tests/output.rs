@@ -0,0 +1,53 @@
+// Names of the plot files and protection of the files of earlier runs
+use system_rs::output::PlotNaming;
+use system_rs::OutputConfig;
+
+#[test]
+fn default_stem_is_a_slug_of_the_title() {
+    let naming = PlotNaming::default();
+    assert_eq!(naming.stem("Poder de Frenado en función de la energía sin correcciones"), "poder_de_frenado_en_funcion_de_la_energia_sin_correcciones");
+    assert_eq!(naming.stem("dE/dx (MeV/cm) vs. E"), "de_dx_mev_cm_vs_e");
+}
+
+#[test]
+fn files_of_earlier_runs_are_refused_unless_forced() {
+    let dir = std::env::temp_dir().join(format!("system_rs_output_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let output = OutputConfig::new(&dir);
+    std::fs::write(output.path("range_table.txt"), "").unwrap();
+
+    assert!(output.check_new_files(output.table_files("bragg_curve")).is_ok());
+    let files = [output.path("bragg_curve.txt"), output.path("range_table.txt")];
+    assert!(output.check_new_files(files.clone()).is_err());
+    assert!(OutputConfig { force: true, ..output.clone() }.check_new_files(files).is_ok());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_second_run_leaves_the_tables_of_the_first() {
+    let dir = std::env::temp_dir().join(format!("system_rs_rerun_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let run = |args: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_system_rs"))
+            .current_dir(&dir)
+            .args(["--no-plot", "--non-interactive", "-q", "stopping", "--corrections", "density", "--points", "5"])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let table = dir.join("output").join("fstopping_density_corrections.txt");
+
+    assert!(run(&[]).status.success());
+    let first = std::fs::read_to_string(&table).unwrap();
+
+    // Nothing is computed or written when a file of the run exists
+    let refused = run(&["--emax", "50"]);
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("give --force to replace it"));
+    assert_eq!(std::fs::read_to_string(&table).unwrap(), first);
+
+    assert!(run(&["--emax", "50", "--force"]).status.success());
+    assert_ne!(std::fs::read_to_string(&table).unwrap(), first);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,14 @@
+// Antiparticles keep the mass and spin, flip the charge and are named as the presets are
+use system_rs::bethe_bloch::Projectile;
+
+#[test]
+fn antiparticles_of_the_presets() {
+    assert_eq!(Projectile::proton().antiparticle(), Projectile::antiproton());
+    assert_eq!(Projectile::antiproton().antiparticle(), Projectile::proton());
+    assert_eq!(Projectile::electron().antiparticle(), Projectile::positron());
+    assert_eq!(Projectile::positron().antiparticle(), Projectile::electron());
+
+    let antimuon = Projectile::muon().antiparticle();
+    assert_eq!((antimuon.name.as_str(), antimuon.charge, antimuon.spin), ("antimuon", -1.0, 0.5));
+    assert_eq!(antimuon.antiparticle(), Projectile::muon());
+}
@@ -0,0 +1,50 @@
+// Radiative stopping power of electrons in water compared against ESTAR, its high-energy
+// limit E/X0 and the critical energy where it overtakes the collision losses
+use system_rs::bethe_bloch::bb::stopping_power;
+use system_rs::bethe_bloch::electron::collision_stopping_power;
+use system_rs::bethe_bloch::radiative::{critical_energy, radiative_stopping_power};
+use system_rs::bethe_bloch::{materials, Corrections, Projectile};
+
+// Kinetic energy (MeV) and radiative stopping power (MeV cm²/g) of electrons in water from NIST ESTAR
+const ESTAR_WATER_RADIATIVE: [(f64, f64); 3] = [(1.0, 0.01293), (10.0, 0.1832), (100.0, 2.399)];
+
+#[test]
+fn radiative_stopping_power_matches_estar() {
+    let water = materials::water();
+    for (energy, reference) in ESTAR_WATER_RADIATIVE {
+        let de_dx = radiative_stopping_power(energy, &water) / water.density;
+        let deviation = (de_dx - reference).abs() / reference;
+        assert!(deviation < 0.05, "{} MeV: {} vs ESTAR {} MeV cm²/g ({:.2}%)", energy, de_dx, reference, deviation * 100.0);
+    }
+}
+
+#[test]
+fn radiative_stopping_power_tends_to_e_over_x0() {
+    for material in [materials::water(), materials::aluminium(), materials::by_name("lead").unwrap()] {
+        let energy = 1e5;
+        let limit = (energy + Projectile::electron().mass) / material.radiation_length_cm();
+        let ratio = radiative_stopping_power(energy, &material) / limit;
+        assert!((ratio - 1.0).abs() < 0.03, "{}: {} of E/X0", material.name, ratio);
+    }
+}
+
+#[test]
+fn critical_energy_is_where_the_losses_cross() {
+    let (electron, water) = (Projectile::electron(), materials::water());
+    let corrections = Corrections::all(&water.density_correction_params());
+
+    // ESTAR has the two losses of water equal near 92 MeV
+    let critical = critical_energy(&electron, &water, &corrections).unwrap();
+    assert!((85.0..100.0).contains(&critical), "{} MeV", critical);
+    let collision = collision_stopping_power(critical, false, &water, &corrections);
+    assert!((radiative_stopping_power(critical, &water) / collision - 1.0).abs() < 1e-6);
+
+    // Heavier materials radiate sooner
+    let lead = materials::by_name("lead").unwrap();
+    let lead_critical = critical_energy(&electron, &lead, &Corrections::all(&lead.density_correction_params())).unwrap();
+    assert!(lead_critical < 0.2 * critical, "{} MeV in lead", lead_critical);
+
+    // --radiative adds the bremsstrahlung to the collision losses
+    let total = stopping_power(critical, &electron, &water, &Corrections { radiative: true, ..corrections });
+    assert!((total / collision - 2.0).abs() < 1e-6);
+}
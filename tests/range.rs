@@ -0,0 +1,102 @@
+// CSDA ranges in liquid water compared against PSTAR, and alpha ranges against the proton
+// ranges at the same velocity
+use system_rs::bethe_bloch::{materials, range, Corrections, EnergyGrid, Projectile};
+
+// Kinetic energy (MeV) and CSDA range (g/cm²) of protons in water from NIST PSTAR
+const PSTAR_WATER_RANGE: [(f64, f64); 8] = [
+    (2.0, 7.555e-3),
+    (5.0, 3.623e-2),
+    (10.0, 0.1230),
+    (20.0, 0.4260),
+    (50.0, 2.227),
+    (100.0, 7.718),
+    (150.0, 15.76),
+    (200.0, 25.96),
+];
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn proton_range_matches_pstar_within_one_percent() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+
+    for (energy, reference) in PSTAR_WATER_RANGE {
+        let range = range::csda_range(energy, &proton, &water, &corrections()).mass_thickness;
+        let deviation = (range - reference).abs() / reference;
+        assert!(deviation < 0.01, "{} MeV: {} vs PSTAR {} g/cm² ({:.2}%)", energy, range, reference, deviation * 100.0);
+    }
+}
+
+#[test]
+fn alpha_range_scales_with_the_proton_range() {
+    let (proton, alpha) = (Projectile::proton(), Projectile::alpha());
+    let water = materials::water();
+    let mass_ratio = alpha.mass / proton.mass;
+
+    // R_α(T) = (M_α/M_p) / z² R_p(T M_p/M_α), up to the charge exchange of the slow alpha
+    for (energy, reference) in PSTAR_WATER_RANGE.into_iter().filter(|(energy, _)| *energy >= 5.0 && *energy <= 50.0) {
+        let scaled = mass_ratio / 4.0 * reference;
+        let range = range::csda_range(energy * mass_ratio, &alpha, &water, &corrections()).mass_thickness;
+        let deviation = (range - scaled).abs() / scaled;
+        assert!(deviation < 0.03, "{} MeV alpha: {} vs {} g/cm² ({:.2}%)", energy * mass_ratio, range, scaled, deviation * 100.0);
+    }
+}
+
+#[test]
+fn cutoff_is_per_nucleon() {
+    assert_eq!(range::cutoff(&Projectile::proton()), range::DEFAULT_CUTOFF);
+    assert_eq!(range::cutoff(&Projectile::alpha()), 4.0 * range::DEFAULT_CUTOFF);
+    assert_eq!(range::cutoff(&Projectile::carbon()), 12.0 * range::DEFAULT_CUTOFF);
+    assert_eq!(range::cutoff(&Projectile::muon()), range::DEFAULT_CUTOFF);
+}
+
+#[test]
+fn range_table_starts_at_the_cutoff() {
+    let alpha = Projectile::alpha();
+    let water = materials::water();
+    let grid = EnergyGrid::logarithmic(1.0, 100.0, 50);
+    let table = range::range_table(&grid, &alpha, &water, &corrections());
+
+    assert_eq!(table.energies[0], range::cutoff(&alpha));
+    assert!(table.ranges[0] > 0.0);
+    assert!(table.ranges.windows(2).all(|pair| pair[1] > pair[0]));
+    let last = range::csda_range(100.0, &alpha, &water, &corrections()).length;
+    assert!((table.ranges.last().unwrap() - last).abs() < 1e-12);
+}
+
+#[test]
+fn bragg_kleeman_fit_recovers_a_power_law() {
+    let energies: Vec<f64> = (1..=20).map(|i| 10.0 * i as f64).collect();
+    let ranges = energies.iter().map(|e| 0.0022 * e.powf(1.77)).collect();
+    let table = range::RangeTable { straggling: vec![0.0; energies.len()], energies, ranges };
+
+    let fit = table.bragg_kleeman_fit().unwrap();
+    assert!((fit.alpha / 0.0022 - 1.0).abs() < 1e-9 && (fit.p - 1.77).abs() < 1e-9, "α = {}, p = {}", fit.alpha, fit.p);
+    assert!((fit.r_squared - 1.0).abs() < 1e-12 && fit.max_relative_error < 1e-9);
+}
+
+#[test]
+fn bragg_kleeman_fit_of_protons_in_water() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+    let table = range::range_table(&EnergyGrid::logarithmic(10.0, 250.0, 100), &proton, &water, &corrections());
+
+    // Without the residual range at the cutoff, which the power law does not follow down to 1 MeV
+    let table = range::RangeTable { energies: table.energies[1..].to_vec(), ranges: table.ranges[1..].to_vec(), straggling: table.straggling[1..].to_vec() };
+    let fit = table.bragg_kleeman_fit().unwrap();
+
+    // R ≈ 0.0022 E^1.77 cm for therapeutic protons in water
+    assert!((1.72..1.82).contains(&fit.p), "p = {}", fit.p);
+    assert!((fit.range(150.0) / range::csda_range(150.0, &proton, &water, &corrections()).length - 1.0).abs() < 0.05);
+    assert!(fit.r_squared > 0.999, "R² = {}", fit.r_squared);
+    assert!(fit.max_relative_error < 0.1, "largest deviation {}", fit.max_relative_error);
+}
+
+#[test]
+fn bragg_kleeman_fit_needs_two_ranges() {
+    let table = range::RangeTable { energies: vec![10.0, 20.0], ranges: vec![0.1, 0.0], straggling: vec![0.0; 2] };
+    assert_eq!(table.bragg_kleeman_fit(), None);
+}
@@ -0,0 +1,72 @@
+// The tables of a run laid out by the library: the variants of the corrections, the targets
+// of a batch run, the tables of a target and the conditions of a gas
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use system_rs::bethe_bloch::bb::{bethe_bloch, radiative_stopping_power};
+use system_rs::bethe_bloch::gas::at_conditions;
+use system_rs::bethe_bloch::run::{stopping_tables, targets, variants, TableSettings};
+use system_rs::bethe_bloch::{materials, Axis, Corrections, EnergyGrid, Projectile, Unit};
+use system_rs::i18n::Lang;
+use system_rs::output::grid_metadata;
+use system_rs::Error;
+
+fn settings(corrections: Option<Corrections>) -> TableSettings {
+    TableSettings { grid: EnergyGrid::logarithmic(10.0, 1000.0, 20), corrections, radiative: false, unit: Unit::MevPerCm, axis: Axis::KineticEnergy, i_uncertainty: None }
+}
+
+#[test]
+fn four_variants_unless_corrections_are_selected() {
+    let (labels, params) = (Lang::En.labels(), materials::water().density_correction_params());
+    let standard: Vec<String> = variants(None, &params, labels).into_iter().map(|variant| variant.file).collect();
+    assert_eq!(standard, ["fstopping_no_corrections", "fstopping_density_corrections", "fstopping_layer_corrections", "fstopping_all_corrections"]);
+
+    let selected = variants(Some("density,barkas".parse().unwrap()), &params, labels);
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].file, "fstopping_density_barkas_corrections");
+    assert_eq!(selected[0].corrections.density_params, params);
+}
+
+#[test]
+fn batch_targets_go_projectile_by_projectile() {
+    let targets = targets(&[Projectile::proton(), Projectile::alpha()], &[materials::water(), materials::air()]);
+    let names: Vec<(&str, &str)> = targets.iter().map(|(p, m)| (p.name.as_str(), m.name.as_str())).collect();
+    assert_eq!(names, [("proton", "water"), ("proton", "air"), ("alpha", "water"), ("alpha", "air")]);
+}
+
+#[test]
+fn stopping_tables_report_every_grid_point() {
+    let (muon, water) = (Projectile::muon(), materials::water());
+    let params = water.density_correction_params();
+    let steps = AtomicUsize::new(0);
+    let tables = stopping_tables(&settings(None), &params, &muon, &water, Lang::En.labels(), || { steps.fetch_add(1, Ordering::Relaxed); });
+    assert_eq!(tables.len(), 4);
+    assert_eq!(steps.load(Ordering::Relaxed), 4 * 20);
+
+    // The radiative losses are added in MeV/cm before the table is converted
+    let corrections = Corrections::all(&params);
+    let radiative = TableSettings { corrections: Some(corrections), radiative: true, unit: Unit::MevCm2PerG, ..settings(None) };
+    let (_, table, band) = stopping_tables(&radiative, &params, &muon, &water, Lang::En.labels(), || {}).swap_remove(0);
+    assert!(band.is_none());
+    let collision = bethe_bloch(&muon, &water, &radiative.grid, &corrections);
+    for ((energy, de_dx), (_, expected)) in table.iter().zip(collision.iter()) {
+        let expected = (expected + radiative_stopping_power(energy, &muon, &water)) / water.density;
+        assert!((de_dx - expected).abs() < 1e-12 * expected, "{} MeV: {} vs {}", energy, de_dx, expected);
+    }
+}
+
+#[test]
+fn only_gases_change_with_the_conditions() {
+    let air = materials::air();
+    let compressed = at_conditions(air.clone(), None, Some(2.0)).unwrap();
+    assert!((compressed.density / air.density - 2.0).abs() < 1e-12);
+    assert_eq!(at_conditions(materials::water(), None, None).unwrap(), materials::water());
+    assert!(matches!(at_conditions(materials::water(), Some(300.0), None), Err(Error::Invalid(_))));
+}
+
+#[test]
+fn grid_metadata_describes_the_run() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let metadata = grid_metadata(&proton, &water, &Corrections::none(), &EnergyGrid::logarithmic(1.0, 10.0, 5));
+    let keys: Vec<&str> = metadata.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, ["generator", "projectile", "material", "density_g_cm3", "mean_excitation_energy_ev", "corrections", "radiative", "emin_mev", "emax_mev", "points", "spacing"]);
+}
@@ -0,0 +1,82 @@
+// Low-energy proton stopping powers in liquid water compared against PSTAR, below the validity
+// limit of the shell polynomial
+use system_rs::bethe_bloch::bb::stopping_power;
+use system_rs::bethe_bloch::reference::PSTAR_WATER;
+use system_rs::bethe_bloch::shell::{read_table, POLYNOMIAL_LIMIT};
+use system_rs::bethe_bloch::{kinematics, materials, Corrections, Projectile, ShellCorrectionModel};
+
+#[test]
+fn default_corrections_follow_pstar_near_one_mev() {
+    let proton = Projectile::proton();
+    let water = materials::water();
+    let corrections = Corrections::all(&water.density_correction_params());
+
+    for (energy, reference) in PSTAR_WATER.into_iter().filter(|(energy, _)| *energy <= 5.0) {
+        let de_dx = stopping_power(energy, &proton, &water, &corrections) / water.density;
+        let deviation = (de_dx - reference).abs() / reference;
+        assert!(deviation < 0.03, "{} MeV: {} vs PSTAR {} ({:.2}%)", energy, de_dx, reference, deviation * 100.0);
+    }
+}
+
+#[test]
+fn polynomial_is_held_below_its_limit() {
+    let water = materials::water();
+    let model = ShellCorrectionModel::Polynomial;
+    let limit = model.correction(POLYNOMIAL_LIMIT, &water);
+
+    for energy in [0.1, 0.5, 1.0, 2.0] {
+        let bg = kinematics::beta_gamma(energy, Projectile::proton().mass);
+        assert_eq!(model.correction(bg, &water), limit);
+    }
+    assert!(model.correction(2.0 * POLYNOMIAL_LIMIT, &water) < limit);
+}
+
+// Illustrative (βγ, C/Z) table, decreasing with the velocity as shell corrections do
+const TABLE: [(f64, f64); 4] = [(0.05, 0.20), (0.1, 0.08), (0.2, 0.03), (0.5, 0.006)];
+
+#[test]
+fn tabulated_model_uses_the_table_of_the_material() {
+    let model = ShellCorrectionModel::Tabulated;
+    let water = materials::water().with_shell_corrections(TABLE.to_vec());
+
+    for (bg, correction) in TABLE {
+        assert!((model.correction(bg, &water) - correction).abs() < 1e-12, "βγ = {}", bg);
+    }
+    assert_eq!(model.correction(0.01, &water), TABLE[0].1);
+    assert!((model.correction(1.0, &water) - 0.006 * 0.25).abs() < 1e-12);
+    let (low, high) = (model.correction(0.12, &water), model.correction(0.15, &water));
+    assert!(low < 0.08 && high < low && high > 0.03);
+
+    // Other materials keep the polynomial until they are given a table of their own
+    let aluminium = materials::aluminium();
+    assert_eq!(model.correction(0.3, &aluminium), ShellCorrectionModel::Polynomial.correction(0.3, &aluminium));
+}
+
+#[test]
+fn shell_table_is_a_correction_name() {
+    let corrections: Corrections = "density,shell-table".parse().unwrap();
+    assert!(corrections.shell);
+    assert_eq!(corrections.shell_model, ShellCorrectionModel::Tabulated);
+    assert_eq!(corrections.names(), ["density", "shell-table"]);
+    assert_eq!("shell".parse::<Corrections>().unwrap().names(), ["shell"]);
+}
+
+#[test]
+fn read_table_checks_the_rows() {
+    let dir = std::env::temp_dir().join(format!("system_rs_shell_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let good = dir.join("good.txt");
+    std::fs::write(&good, "# βγ C/Z\n0.05 0.20\n0.1, 0.08\n0.2\t0.03\n0.5 0.006\n").unwrap();
+    assert_eq!(read_table(&good).unwrap(), TABLE.to_vec());
+
+    let unsorted = dir.join("unsorted.txt");
+    std::fs::write(&unsorted, "0.2 0.03\n0.1 0.08\n").unwrap();
+    assert!(read_table(&unsorted).is_err());
+
+    let short = dir.join("short.txt");
+    std::fs::write(&short, "0.1 0.08\n").unwrap();
+    assert!(read_table(&short).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,46 @@
+// Energy lost through slabs of water: the exit energy follows the CSDA range, thin slabs
+// lose dE/dx times their thickness and thick ones stop the projectile
+use system_rs::bethe_bloch::bb::stopping_power;
+use system_rs::bethe_bloch::slab::energy_after_slab;
+use system_rs::bethe_bloch::{materials, range, Corrections, EnergyGrid, Projectile, SlabOutcome};
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn exit_energy_follows_the_range() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let table = range::range_table(&EnergyGrid::logarithmic(1.0, 300.0, 400), &proton, &water, &corrections());
+
+    // The residual range of the exit energy is the range of the beam less the slab
+    for (energy, thickness) in [(150.0, 5.0), (150.0, 12.0), (250.0, 20.0), (60.0, 2.5)] {
+        let exit = energy_after_slab(&proton, &water, thickness, energy, &corrections()).exit_energy();
+        let csda = range::csda_range(energy, &proton, &water, &corrections()).length;
+        let expected = table.energy_for_range(csda - thickness).unwrap();
+        assert!((exit - expected).abs() < 5e-3 * expected, "{} MeV through {} cm: {} vs {} MeV", energy, thickness, exit, expected);
+    }
+}
+
+#[test]
+fn thin_slab_loses_the_stopping_power() {
+    let (muon, water) = (Projectile::muon(), materials::water());
+    let de_dx = stopping_power(1000.0, &muon, &water, &corrections());
+    let loss = 1000.0 - energy_after_slab(&muon, &water, 0.1, 1000.0, &corrections()).exit_energy();
+    assert!((loss - 0.1 * de_dx).abs() < 1e-4 * loss, "{} vs {} MeV", loss, 0.1 * de_dx);
+    assert_eq!(energy_after_slab(&muon, &water, 0.0, 1000.0, &corrections()), SlabOutcome::Exits(1000.0));
+}
+
+#[test]
+fn thick_slab_stops_at_the_end_of_the_range() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    for energy in [50.0, 150.0] {
+        let SlabOutcome::Stops(depth) = energy_after_slab(&proton, &water, 100.0, energy, &corrections()) else {
+            panic!("{} MeV crossed 100 cm of water", energy);
+        };
+        // The slab stops following the projectile at the cutoff, short of its residual range
+        let expected = range::csda_range_from(range::cutoff(&proton), energy, &proton, &water, &corrections()).length;
+        assert!((depth - expected).abs() < 5e-3 * expected, "{} MeV: stops at {} cm, expected {} cm", energy, depth, expected);
+        assert_eq!(SlabOutcome::Stops(depth).exit_energy(), 0.0);
+    }
+}
@@ -0,0 +1,52 @@
+// Projectiles through stacks of layers: each layer is credited with the energy lost in it
+// and a stack of one material behaves as a single slab of it
+use system_rs::bethe_bloch::slab::energy_after_slab;
+use system_rs::bethe_bloch::{materials, Corrections, Projectile, SlabOutcome, Stack};
+
+const STEP: f64 = 1e-3;
+
+fn corrections() -> Corrections {
+    Corrections::all(&materials::water().density_correction_params())
+}
+
+#[test]
+fn deposits_add_up_to_the_energy_lost() {
+    let proton = Projectile::proton();
+    let stack = Stack::new()
+        .layer(materials::by_name("mylar").unwrap(), 0.01)
+        .layer(materials::air(), 50.0)
+        .layer(materials::water(), 5.0);
+    let transport = stack.transport(150.0, &proton, &corrections(), STEP);
+
+    let SlabOutcome::Exits(exit) = transport.outcome else { panic!("150 MeV stopped in {:?}", transport.outcome) };
+    assert_eq!(transport.deposited.len(), 3);
+    assert!(transport.deposited.iter().all(|&deposit| deposit > 0.0), "{:?}", transport.deposited);
+    let deposited: f64 = transport.deposited.iter().sum();
+    assert!((deposited - (150.0 - exit)).abs() < 1e-9, "{} MeV deposited, {} MeV lost", deposited, 150.0 - exit);
+
+    // Each layer uses its own stopping power: 50 cm of air stop less than 5 cm of water
+    assert!(transport.deposited[1] < transport.deposited[2]);
+}
+
+#[test]
+fn stopped_projectile_deposits_everything_before_the_last_layers() {
+    let proton = Projectile::proton();
+    let stack = Stack::new().layer(materials::water(), 5.0).layer(materials::aluminium(), 100.0).layer(materials::water(), 10.0);
+    let transport = stack.transport(100.0, &proton, &corrections(), STEP);
+
+    let SlabOutcome::Stops(depth) = transport.outcome else { panic!("100 MeV crossed the stack") };
+    assert!(depth > 5.0 && depth < 105.0, "stops at {} cm", depth);
+    assert!((transport.deposited.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    assert_eq!(transport.deposited[2], 0.0);
+}
+
+#[test]
+fn layers_of_one_material_make_a_slab() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let split = Stack::new().layer(water.clone(), 4.0).layer(water.clone(), 6.0).transport(200.0, &proton, &corrections(), STEP);
+    let slab = energy_after_slab(&proton, &water, 10.0, 200.0, &corrections()).exit_energy();
+
+    let exit = split.outcome.exit_energy();
+    assert!((exit - slab).abs() < 1e-3 * slab, "{} vs {} MeV", exit, slab);
+    assert!((split.deposited[0] + split.deposited[1] - (200.0 - exit)).abs() < 1e-9);
+}
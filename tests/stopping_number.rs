@@ -0,0 +1,37 @@
+// The density effect enters the stopping number ln(2mc²β²γ²/I) - β² - δ/2 - C/Z as δ/2 and
+// the shell correction as C/Z. Both are subtracted from L, so the stopping power with the
+// terms doubled, as they were before, is 2 S(δ, C) - S(none).
+use system_rs::bethe_bloch::bb::{bethe_bloch, stopping_power};
+use system_rs::bethe_bloch::reference::PSTAR_WATER;
+use system_rs::bethe_bloch::{materials, Corrections, EnergyGrid, Projectile};
+
+// Minimum mass stopping power of liquid water (MeV cm²/g), from the PDG tables
+const WATER_MINIMUM_IONIZATION: f64 = 1.992;
+
+#[test]
+fn halved_terms_follow_pstar_closer_than_doubled_ones() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let corrections = Corrections::all(&water.density_correction_params());
+
+    for (energy, reference) in PSTAR_WATER.into_iter().filter(|(energy, _)| (5.0..=100.0).contains(energy)) {
+        let corrected = stopping_power(energy, &proton, &water, &corrections) / water.density;
+        let bare = stopping_power(energy, &proton, &water, &Corrections::none()) / water.density;
+        let doubled = 2.0 * corrected - bare;
+
+        let (deviation, doubled_deviation) = ((corrected - reference).abs() / reference, (doubled - reference).abs() / reference);
+        assert!(deviation < 0.01, "{} MeV: {} vs PSTAR {} ({:.2}%)", energy, corrected, reference, deviation * 100.0);
+        assert!(deviation < doubled_deviation, "{} MeV: {:.2}% against {:.2}% with the doubled terms", energy, deviation * 100.0, doubled_deviation * 100.0);
+    }
+}
+
+#[test]
+fn minimum_ionization_of_water() {
+    let (muon, water) = (Projectile::muon(), materials::water());
+    let grid = EnergyGrid::logarithmic(100.0, 10000.0, 2000);
+    let table = bethe_bloch(&muon, &water, &grid, &Corrections::all(&water.density_correction_params()));
+    let (_, minimum) = table.minimum_ionizing().unwrap();
+    let minimum = minimum / water.density;
+
+    let deviation = (minimum - WATER_MINIMUM_IONIZATION).abs() / WATER_MINIMUM_IONIZATION;
+    assert!(deviation < 0.005, "{} vs {} MeV cm²/g ({:.2}%)", minimum, WATER_MINIMUM_IONIZATION, deviation * 100.0);
+}
@@ -0,0 +1,82 @@
+// Tables written a row at a time: a finished writer leaves the whole table, and one dropped
+// before `finish` reports the incomplete file
+use std::sync::Mutex;
+
+use log::{Level, Log, Metadata, Record};
+use serde_json::json;
+use system_rs::bethe_bloch::{bb, materials, range, Corrections, EnergyGrid, Projectile};
+use system_rs::output::{ColumnWriter, Format, Table};
+
+// Error records logged by the library
+static ERRORS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Error
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            ERRORS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+fn dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("system_rs_writer_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn finished_tables_hold_every_row() {
+    let (proton, water) = (Projectile::proton(), materials::water());
+    let corrections = Corrections::all(&water.density_correction_params());
+    let grid = EnergyGrid::logarithmic(1.0, 1000.0, 50);
+    let dir = dir("finished");
+
+    for format in [Format::Text, Format::Csv, Format::Json] {
+        let table = bb::bethe_bloch_with_progress(&proton, &water, &grid, &corrections, || {});
+        let (metadata, header) = ([("material", json!("water"))], table.header());
+        let path = dir.join(format!("stopping.{}", format.extension()));
+        let mut writer = ColumnWriter::create(&path, format, &metadata, &header).unwrap();
+        for row in table.rows() {
+            writer.row(&row).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let written = Table::read(&path).unwrap();
+        assert_eq!(written.columns[0].len(), 50, "{:?}", format);
+        assert!((written.columns[1][49] - table.stopping_powers[49]).abs() < 1e-6 * table.stopping_powers[49]);
+    }
+
+    let ranges = range::range_table(&grid, &proton, &water, &corrections);
+    let path = dir.join("range.csv");
+    ranges.write_with(&path, Format::Csv, &Default::default(), &[]).unwrap();
+    assert_eq!(Table::read(&path).unwrap().columns[1], ranges.ranges);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dropped_writer_reports_the_incomplete_table() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Error);
+    let dir = dir("dropped");
+
+    let (header, path) = (vec![String::from("E (MeV)")], dir.join("dropped.json"));
+    let mut writer = ColumnWriter::create(&path, Format::Json, &[], &header).unwrap();
+    writer.row(&[1.0]).unwrap();
+    drop(writer);
+
+    let errors = ERRORS.lock().unwrap();
+    assert!(errors.iter().any(|error| error.contains("dropped.json")), "{:?}", errors);
+    assert!(!path.exists());
+    drop(errors);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}